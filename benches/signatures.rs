@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use file_format::Signature;
+use std::path::Path;
+
+/// Returns the first signature matched by the bytes, checking every signature in turn.
+fn find_linear(bytes: &[u8]) -> Option<&'static Signature> {
+    Signature::all().iter().find(|signature| {
+        signature
+            .parts()
+            .iter()
+            .all(|&(offset, value)| bytes.get(offset..offset + value.len()) == Some(value))
+    })
+}
+
+/// Returns the probes of every fixture, along with bytes matching no signature.
+fn probes() -> Vec<Vec<u8>> {
+    let mut probes = Vec::new();
+    for kind in Path::new("fixtures").read_dir().unwrap() {
+        for fixture in kind.unwrap().path().read_dir().unwrap() {
+            let mut bytes = std::fs::read(fixture.unwrap().path()).unwrap();
+            bytes.truncate(36_870);
+            probes.push(bytes);
+        }
+    }
+    probes.push(vec![0; 36_870]);
+    probes.push(b"Lorem ipsum dolor sit amet".repeat(1024));
+    probes
+}
+
+fn bench_signatures(c: &mut Criterion) {
+    // Checks that both lookups are equivalent before measuring them.
+    let probes = probes();
+    for probe in &probes {
+        assert_eq!(Signature::find(probe), find_linear(probe));
+    }
+
+    let mut group = c.benchmark_group("signatures");
+    group.bench_function("linear", |b| {
+        b.iter(|| {
+            for probe in &probes {
+                black_box(find_linear(black_box(probe)));
+            }
+        })
+    });
+    group.bench_function("dispatch", |b| {
+        b.iter(|| {
+            for probe in &probes {
+                black_box(Signature::find(black_box(probe)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_signatures);
+criterion_main!(benches);
@@ -0,0 +1,547 @@
+//! Asynchronous detection from `AsyncRead + AsyncSeek` streams.
+//!
+//! Rather than duplicating every reader, the blocking signature and reader logic runs once on a
+//! helper thread, over a [`Remote`] cursor which requests the blocks it lacks from the
+//! asynchronous task and waits for them. The task fetches the requested blocks from the
+//! asynchronous stream, reading ahead when the stream is read sequentially, so the detection is
+//! never replayed. The cursor keeps a bounded number of blocks, and a block is only fetched again
+//! once evicted. Readers being unaware of the exchange, the result is the same as the one of
+//! [`Detector::detect_reader`] over a blocking stream.
+//!
+//! The helper threads are reused by the following detections started from the same thread, and
+//! exit once idle for a while.
+
+use crate::{Detection, Detector, FileFormat};
+use futures_io::{AsyncRead, AsyncSeek};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    io::{Error, ErrorKind, Read, Result, Seek, SeekFrom},
+    ops::RangeInclusive,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Condvar, Mutex, MutexGuard, PoisonError,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+/// Size of a block fetched from the asynchronous stream (32 KB).
+const BLOCK_SIZE: u64 = 32_768;
+
+/// Maximum number of blocks fetched at once when the stream is read sequentially (1 MB).
+const READAHEAD_LIMIT: u64 = 32;
+
+/// Maximum number of blocks kept by the helper thread (4 MB).
+const CACHE_LIMIT: usize = 128;
+
+/// Duration after which an idle helper thread exits.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl FileFormat {
+    /// Determines file format from an asynchronous reader.
+    ///
+    /// The reader can be any [`futures_io::AsyncRead`] + [`futures_io::AsyncSeek`] stream, such as
+    /// an `async-std` or `smol` file. Tokio streams can be adapted with the `compat` extension of
+    /// the `tokio-util` crate.
+    ///
+    /// The format readers run on a helper thread, which waits for the blocks of the stream they
+    /// need while the calling task fetches them asynchronously. The helper thread is reused by the
+    /// following detections started from the same thread, and keeps about 4 MB of the stream.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    /// use futures::io::Cursor;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let fmt = FileFormat::from_async_reader(Cursor::new(b"\x1F\x8B")).await?;
+    /// assert_eq!(fmt, FileFormat::Gzip);
+    /// # Ok::<(), std::io::Error>(())
+    /// # })?;
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub async fn from_async_reader<R: AsyncRead + AsyncSeek + Unpin>(reader: R) -> Result<Self> {
+        Self::detect_async_reader(reader)
+            .await
+            .map(|detection| detection.format)
+    }
+
+    /// Determines file format from an asynchronous reader, along with the provenance of the
+    /// result.
+    ///
+    /// See [`FileFormat::from_async_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    /// use futures::io::Cursor;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let detection = FileFormat::detect_async_reader(Cursor::new(b"\x1F\x8B")).await?;
+    /// assert_eq!(detection.format, FileFormat::Gzip);
+    /// # Ok::<(), std::io::Error>(())
+    /// # })?;
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub async fn detect_async_reader<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: R,
+    ) -> Result<Detection> {
+        Detector::default().detect_async(reader).await
+    }
+}
+
+impl Detector {
+    /// Determines file format from an asynchronous reader, along with the provenance of the
+    /// result.
+    ///
+    /// See [`FileFormat::from_async_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat, Reader};
+    /// use futures::io::Cursor;
+    ///
+    /// let detector = Detector::builder().reader(Reader::Zip, false).build();
+    /// # futures::executor::block_on(async {
+    /// let file = std::fs::read("fixtures/document/sample.docx")?;
+    /// let detection = detector.detect_async(Cursor::new(file)).await?;
+    /// assert_eq!(detection.format, FileFormat::Zip);
+    /// # Ok::<(), std::io::Error>(())
+    /// # })?;
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    pub async fn detect_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &self,
+        mut reader: R,
+    ) -> Result<Detection> {
+        // Retrieves the stream length.
+        let len = poll_fn(|cx| Pin::new(&mut reader).poll_seek(cx, SeekFrom::End(0))).await?;
+
+        // Runs the detection on a helper thread, which requests the blocks it lacks.
+        let exchange = Arc::new(Exchange::default());
+        let _cancellation = Cancellation(exchange.clone());
+        let mut remote = Remote::new(len, exchange.clone());
+        let detector = self.clone();
+        run_on_helper(Box::new(move || {
+            let mut completion = Completion(remote.exchange.clone(), None);
+            completion.1 = Some(detector.detect_reader(&mut remote));
+        }))?;
+
+        // Serves the requests of the helper thread until the detection completes.
+        let mut fetcher = Fetcher::new(len);
+        loop {
+            match poll_fn(|cx| exchange.poll_message(cx)).await {
+                Message::Request(first, last, limit) => {
+                    let response = fetcher.fetch(&mut reader, first, last, limit).await;
+                    exchange.respond(response.map(|blocks| (blocks, fetcher.len)));
+                }
+                Message::Done(detection) => return detection,
+            }
+        }
+    }
+}
+
+/// A detection run by a helper thread.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Helper threads waiting for a detection to run, along with their identifier.
+type Idle = Arc<Mutex<Vec<(u64, Sender<Job>)>>>;
+
+/// Identifier of the next helper thread.
+static NEXT_HELPER_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Helper threads which ran a detection started from the current thread and are now idle.
+    static IDLE: Idle = Idle::default();
+}
+
+/// Runs a detection on an idle helper thread, or on a new one if none is idle.
+fn run_on_helper(mut job: Job) -> Result<()> {
+    let idle = IDLE.with(Arc::clone);
+
+    // Hands the detection to an idle helper thread, if any. The lock is held while sending, so
+    // that a helper thread found in the list cannot exit before receiving it.
+    {
+        let mut helpers = idle.lock().unwrap_or_else(PoisonError::into_inner);
+        while let Some((_, sender)) = helpers.pop() {
+            match sender.send(job) {
+                Ok(()) => return Ok(()),
+                Err(error) => job = error.0,
+            }
+        }
+    }
+
+    // Spawns a new helper thread.
+    let id = NEXT_HELPER_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = mpsc::channel::<Job>();
+    thread::Builder::new()
+        .name("file-format-async".into())
+        .spawn(move || loop {
+            job();
+
+            // Waits for the next detection, and exits once idle for too long unless a detection
+            // was handed over in the meantime.
+            idle.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((id, sender.clone()));
+            job = loop {
+                match receiver.recv_timeout(IDLE_TIMEOUT) {
+                    Ok(job) => break job,
+                    Err(RecvTimeoutError::Timeout) => {
+                        let mut helpers = idle.lock().unwrap_or_else(PoisonError::into_inner);
+                        if let Some(index) = helpers.iter().position(|&(other, _)| other == id) {
+                            helpers.remove(index);
+                            return;
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            };
+        })?;
+    Ok(())
+}
+
+/// A future polling a closure, like `std::future::poll_fn` which requires Rust 1.64.
+struct PollFn<F>(F);
+
+impl<T, F: FnMut(&mut Context<'_>) -> Poll<T> + Unpin> Future for PollFn<F> {
+    type Output = T;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        (self.0)(cx)
+    }
+}
+
+/// Creates a future polling the specified closure.
+#[inline]
+fn poll_fn<T, F: FnMut(&mut Context<'_>) -> Poll<T> + Unpin>(f: F) -> PollFn<F> {
+    PollFn(f)
+}
+
+/// Fetched blocks, along with their number.
+type Blocks = Vec<(u64, Vec<u8>)>;
+
+/// A message sent by the helper thread to the asynchronous task.
+enum Message {
+    /// Request of the specified range of blocks, which may be read ahead up to the specified
+    /// block.
+    Request(u64, u64, u64),
+    /// Result of the detection.
+    Done(Result<Detection>),
+}
+
+/// The state shared by the asynchronous task and the helper thread running the detection.
+#[derive(Default)]
+struct Exchange {
+    /// Pending messages and responses.
+    state: Mutex<State>,
+    /// Condition variable notified to the helper thread when a response is available.
+    responded: Condvar,
+}
+
+/// The pending messages and responses of an [`Exchange`].
+#[derive(Default)]
+struct State {
+    /// Range of blocks requested by the helper thread, along with the last block which may be
+    /// read ahead, if any.
+    request: Option<(u64, u64, u64)>,
+    /// Blocks fetched for the last request, along with the length of the stream, if any.
+    response: Option<Result<(Blocks, u64)>>,
+    /// Result of the detection, once the helper thread is done.
+    detection: Option<Result<Detection>>,
+    /// Whether the asynchronous task was dropped before the detection completed.
+    cancelled: bool,
+    /// Waker of the asynchronous task waiting for a message.
+    waker: Option<Waker>,
+}
+
+impl Exchange {
+    /// Locks the state, ignoring the poisoning since it is consistent between operations.
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Requests the specified range of blocks, which may be read ahead up to the specified block,
+    /// and waits for them, on the helper thread.
+    fn request(&self, first: u64, last: u64, limit: u64) -> Result<(Blocks, u64)> {
+        let mut state = self.lock();
+        state.request = Some((first, last, limit));
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        loop {
+            if let Some(response) = state.response.take() {
+                return response;
+            }
+            if state.cancelled {
+                // Not `Interrupted`, which `read_exact` would retry.
+                return Err(Error::new(ErrorKind::Other, "detection cancelled"));
+            }
+            state = self
+                .responded
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Hands the result of the detection to the asynchronous task, on the helper thread.
+    fn complete(&self, detection: Result<Detection>) {
+        let mut state = self.lock();
+        state.detection = Some(detection);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Polls the next message of the helper thread, on the asynchronous task.
+    fn poll_message(&self, cx: &mut Context<'_>) -> Poll<Message> {
+        let mut state = self.lock();
+        if let Some(detection) = state.detection.take() {
+            return Poll::Ready(Message::Done(detection));
+        }
+        if let Some((first, last, limit)) = state.request.take() {
+            return Poll::Ready(Message::Request(first, last, limit));
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Responds to the last request of the helper thread, on the asynchronous task.
+    fn respond(&self, response: Result<(Blocks, u64)>) {
+        self.lock().response = Some(response);
+        self.responded.notify_one();
+    }
+}
+
+/// Releases the helper thread waiting for blocks when the asynchronous task is dropped.
+struct Cancellation(Arc<Exchange>);
+
+impl Drop for Cancellation {
+    fn drop(&mut self) {
+        self.0.lock().cancelled = true;
+        self.0.responded.notify_one();
+    }
+}
+
+/// Hands the result of the detection to the asynchronous task when the helper thread is done,
+/// even if it panicked.
+struct Completion(Arc<Exchange>, Option<Result<Detection>>);
+
+impl Drop for Completion {
+    fn drop(&mut self) {
+        self.0.complete(
+            self.1
+                .take()
+                .unwrap_or_else(|| Err(Error::new(ErrorKind::Other, "detection panicked"))),
+        );
+    }
+}
+
+/// Fetches blocks from an asynchronous stream, on the asynchronous task.
+struct Fetcher {
+    /// Length of the stream.
+    len: u64,
+    /// Number of the block following the last fetched range.
+    next_block: u64,
+    /// Number of blocks fetched ahead when the stream is read sequentially.
+    readahead: u64,
+}
+
+impl Fetcher {
+    /// Creates a fetcher over a stream of the specified length.
+    #[inline]
+    fn new(len: u64) -> Self {
+        Self {
+            len,
+            next_block: 0,
+            readahead: 1,
+        }
+    }
+
+    /// Fetches the specified range of blocks from the asynchronous stream, reading ahead up to the
+    /// specified block when the stream is read sequentially.
+    async fn fetch<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        first: u64,
+        last: u64,
+        limit: u64,
+    ) -> Result<Blocks> {
+        // Doubles the readahead when the range follows the last fetched one.
+        self.readahead = if first == self.next_block {
+            std::cmp::min(self.readahead * 2, READAHEAD_LIMIT)
+        } else {
+            1
+        };
+        let last = std::cmp::max(last, std::cmp::min(first + self.readahead - 1, limit));
+        self.next_block = last + 1;
+
+        // Fetches the blocks.
+        let mut blocks = Vec::new();
+        for number in first..=last {
+            let offset = number * BLOCK_SIZE;
+            if offset >= self.len {
+                break;
+            }
+
+            // Fills the block.
+            let mut block = vec![0; std::cmp::min(BLOCK_SIZE, self.len - offset) as usize];
+            poll_fn(|cx| Pin::new(&mut *reader).poll_seek(cx, SeekFrom::Start(offset))).await?;
+            let mut nread = 0;
+            while nread < block.len() {
+                match poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, &mut block[nread..])).await
+                {
+                    Ok(0) => break,
+                    Ok(n) => nread += n,
+                    Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                    Err(error) => return Err(error),
+                }
+            }
+
+            // Truncates the stream if it ended earlier than expected.
+            if nread < block.len() {
+                block.truncate(nread);
+                self.len = offset + nread as u64;
+            }
+            blocks.push((number, block));
+        }
+        Ok(blocks)
+    }
+}
+
+/// A cursor serving the blocks fetched from an asynchronous stream, and requesting the missing
+/// ones, on the helper thread.
+struct Remote {
+    /// Length of the stream.
+    len: u64,
+    /// Current position.
+    pos: u64,
+    /// Fetched blocks, indexed by their number.
+    blocks: HashMap<u64, Vec<u8>>,
+    /// Numbers of the fetched blocks, from the oldest to the newest.
+    order: VecDeque<u64>,
+    /// State shared with the asynchronous task.
+    exchange: Arc<Exchange>,
+}
+
+impl Remote {
+    /// Creates a cursor over a stream of the specified length.
+    #[inline]
+    fn new(len: u64, exchange: Arc<Exchange>) -> Self {
+        Self {
+            len,
+            pos: 0,
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+            exchange,
+        }
+    }
+
+    /// Keeps the fetched blocks, evicting the oldest ones beyond the cache limit except the ones
+    /// of the specified range.
+    fn keep(&mut self, blocks: Blocks, needed: RangeInclusive<u64>) {
+        for (number, block) in blocks {
+            if self.blocks.insert(number, block).is_none() {
+                self.order.push_back(number);
+            }
+        }
+        while self.blocks.len() > CACHE_LIMIT {
+            match self.order.pop_front() {
+                Some(number) if needed.contains(&number) => self.order.push_back(number),
+                Some(number) => {
+                    self.blocks.remove(&number);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl Read for Remote {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // Calculates the range to read, up to the number of blocks fetched at once.
+        let end = std::cmp::min(self.pos.saturating_add(buf.len() as u64), self.len);
+        if self.pos >= end {
+            return Ok(0);
+        }
+        let first = self.pos / BLOCK_SIZE;
+        let end = std::cmp::min(end, (first + READAHEAD_LIMIT) * BLOCK_SIZE);
+        let last = (end - 1) / BLOCK_SIZE;
+
+        // Requests the runs of missing blocks, if any, until the stream ends, without reading
+        // ahead into the blocks already kept.
+        while let Some(missing) = (first..=last).find(|number| !self.blocks.contains_key(number)) {
+            let run_last = (missing..=last)
+                .take_while(|number| !self.blocks.contains_key(number))
+                .last()
+                .unwrap_or(missing);
+            let limit = self
+                .blocks
+                .keys()
+                .filter(|&&number| number > run_last)
+                .min()
+                .map_or(u64::MAX, |&number| number - 1);
+            let (blocks, len) = self.exchange.request(missing, run_last, limit)?;
+            self.len = len;
+            if blocks.is_empty() {
+                break;
+            }
+            self.keep(blocks, first..=last);
+        }
+
+        // Copies the data from the fetched blocks, up to the end of the stream.
+        let end = std::cmp::min(end, self.len);
+        let mut nread = 0;
+        while self.pos < end {
+            let block = match self.blocks.get(&(self.pos / BLOCK_SIZE)) {
+                Some(block) => block,
+                None => break,
+            };
+            let start = (self.pos % BLOCK_SIZE) as usize;
+            let len = std::cmp::min(block.len() - start, (end - self.pos) as usize);
+            buf[nread..nread + len].copy_from_slice(&block[start..start + len]);
+            nread += len;
+            self.pos += len as u64;
+        }
+        Ok(nread)
+    }
+}
+
+impl Seek for Remote {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (offset, 0),
+            SeekFrom::End(offset) => (self.len, offset),
+            SeekFrom::Current(offset) => (self.pos, offset),
+        };
+        let pos = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        };
+        self.pos = pos.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.pos)
+    }
+}
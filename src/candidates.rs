@@ -0,0 +1,171 @@
+//! Ranked detection of candidate file formats.
+
+use crate::{
+    dispatch::DISPATCH,
+    io::{Cursor, Read, Result, Seek},
+    Detection, Detector, FileFormat, Signature,
+};
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+#[cfg(feature = "std")]
+use std::{fs::File, path::Path};
+
+/// Confidence of a file format only determined by the generic reader, when it is not the default
+/// one.
+const GENERIC_CONFIDENCE: f32 = 0.25;
+
+/// Factor applied to the confidence of a signature which is not anchored at the start of the
+/// stream.
+const OFFSET_FACTOR: f32 = 0.9;
+
+impl FileFormat {
+    /// Determines candidate file formats from bytes, ranked by decreasing confidence.
+    ///
+    /// See [`FileFormat::candidates_from_reader`] for details about the confidence.
+    ///
+    /// # Examples
+    ///
+    /// Ranks the candidates of a
+    /// [Portable Network Graphics (PNG)](`FileFormat::PortableNetworkGraphics`) header:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let candidates = FileFormat::candidates_from_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A");
+    /// assert_eq!(candidates[0].0, FileFormat::PortableNetworkGraphics);
+    /// assert_eq!(candidates.last().unwrap().0, FileFormat::ArbitraryBinaryData);
+    ///```
+    #[inline]
+    pub fn candidates_from_bytes<B: AsRef<[u8]>>(bytes: B) -> Vec<(Self, f32)> {
+        Self::rank_candidates(Cursor::new(bytes.as_ref())).unwrap_or_default()
+    }
+
+    /// Determines candidate file formats from a file, ranked by decreasing confidence.
+    ///
+    /// See [`FileFormat::candidates_from_reader`] for details about the confidence.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let candidates = FileFormat::candidates_from_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(candidates[0].0, FileFormat::AudioVideoInterleave);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn candidates_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<(Self, f32)>> {
+        Self::candidates_from_reader(File::open(path)?)
+    }
+
+    /// Determines candidate file formats from a reader, ranked by decreasing confidence.
+    ///
+    /// Unlike [`FileFormat::from_reader`], which stops at the first matching signature, every
+    /// signature is checked and each file format is given a confidence between `0.0` and `1.0`:
+    ///
+    /// - the longer the matched signature, the higher the confidence;
+    /// - a signature which is not anchored at the start of the stream is slightly less trusted;
+    /// - a format reader which confirms the match raises the confidence, whereas a format reader
+    ///   which fails to read the stream halves it;
+    /// - a file format only determined by the generic reader gets a low confidence, and the
+    ///   [default](`FileFormat::default`) file format is always listed last with a confidence of
+    ///   `0.0`.
+    ///
+    /// Each file format is listed once, with its highest confidence.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let candidates = FileFormat::candidates_from_reader(std::io::empty())?;
+    /// assert_eq!(candidates, vec![(FileFormat::Empty, 1.0)]);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn candidates_from_reader<R: Read + Seek>(reader: R) -> Result<Vec<(Self, f32)>> {
+        Self::rank_candidates(reader)
+    }
+
+    /// Determines candidate file formats from a reader, whatever the I/O primitives in use.
+    pub(crate) fn rank_candidates<R: Read + Seek>(mut reader: R) -> Result<Vec<(Self, f32)>> {
+        // Creates and fills a buffer.
+        let detector = Detector::default();
+        let mut buf = vec![0; detector.probe_size];
+        let nread = reader.read(&mut buf)?;
+        if nread == 0 {
+            return Ok(vec![(Self::Empty, 1.0)]);
+        }
+
+        // Collects the candidates from every matching signature.
+        let mut candidates = Vec::new();
+        let mut matched = Vec::new();
+        for signature in DISPATCH
+            .candidates(&buf[..nread])
+            .filter(|sig| sig.matches(&buf[..nread]))
+        {
+            // Skips the file formats already matched by a longer signature.
+            if matched.contains(&signature.format) {
+                continue;
+            }
+            matched.push(signature.format);
+
+            // Confirms the match with the format reader, if any.
+            let confidence = signature.confidence();
+            candidates.push(if detector.reader(signature.format).is_some() {
+                let mut detection = Detection::new(signature.format);
+                match Self::from_fmt_reader(
+                    signature.format,
+                    &mut reader,
+                    &detector,
+                    &mut detection,
+                ) {
+                    Ok(fmt) => (fmt, confidence + (1.0 - confidence) / 2.0),
+                    Err(_) => (signature.format, confidence / 2.0),
+                }
+            } else {
+                (signature.format, confidence)
+            });
+        }
+
+        // Adds the file format determined by the generic reader.
+        let fmt = Self::from_generic_reader(&mut reader, &detector, &mut Detection::default());
+        if fmt != Self::default() {
+            candidates.push((fmt, GENERIC_CONFIDENCE));
+        }
+
+        // Ranks the candidates, keeping the highest confidence of each file format.
+        candidates.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        let mut ranked: Vec<(Self, f32)> = Vec::with_capacity(candidates.len() + 1);
+        for (fmt, confidence) in candidates {
+            if !ranked.iter().any(|&(ranked_fmt, _)| ranked_fmt == fmt) {
+                ranked.push((fmt, confidence));
+            }
+        }
+        if !ranked.iter().any(|&(fmt, _)| fmt == Self::default()) {
+            ranked.push((Self::default(), 0.0));
+        }
+        Ok(ranked)
+    }
+}
+
+impl Signature {
+    /// Returns the confidence given by a match of the signature, based on its length and offset.
+    #[inline]
+    fn confidence(&self) -> f32 {
+        let len = self.size() as f32;
+        let confidence = len / (len + 4.0);
+        if self.offset() > 0 {
+            confidence * OFFSET_FACTOR
+        } else {
+            confidence
+        }
+    }
+}
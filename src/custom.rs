@@ -0,0 +1,251 @@
+//! File formats defined at runtime.
+
+use crate::{FileFormat, Kind};
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+/// A file format defined at runtime, identified by its signatures.
+///
+/// Custom formats are registered on a [`Detector`](`crate::Detector`) and identified through its
+/// `identify_*` functions, which return a [`DetectedFormat`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{CustomFormat, Kind};
+///
+/// let fmt = CustomFormat::new("Foo Archive", "application/x-foo", "foo", Kind::Archive)
+///     .with_short_name("FOO")
+///     .with_signature(&[(0, &b"FOO"[..]), (8, &b"\x01\x02"[..])]);
+/// assert_eq!(fmt.name(), "Foo Archive");
+/// assert_eq!(fmt.short_name(), Some("FOO"));
+/// assert_eq!(fmt.media_type(), "application/x-foo");
+/// assert_eq!(fmt.extension(), "foo");
+/// assert_eq!(fmt.kind(), Kind::Archive);
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CustomFormat {
+    /// Name of the file format.
+    name: String,
+    /// Short name of the file format, if any.
+    short_name: Option<String>,
+    /// Common media type of the file format.
+    media_type: String,
+    /// Preferred extension of the file format.
+    extension: String,
+    /// Kind of the file format.
+    kind: Kind,
+    /// Signatures of the file format, as lists of bytes and their offsets.
+    signatures: Vec<Vec<(usize, Vec<u8>)>>,
+}
+
+impl CustomFormat {
+    /// Creates a custom format without any signature.
+    #[inline]
+    pub fn new<N, M, E>(name: N, media_type: M, extension: E, kind: Kind) -> Self
+    where
+        N: Into<String>,
+        M: Into<String>,
+        E: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            short_name: None,
+            media_type: media_type.into(),
+            extension: extension.into(),
+            kind,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Sets the short name of the custom format.
+    #[inline]
+    #[must_use]
+    pub fn with_short_name<S: Into<String>>(mut self, short_name: S) -> Self {
+        self.short_name = Some(short_name.into());
+        self
+    }
+
+    /// Adds a signature to the custom format, as a list of bytes and their offsets which must all
+    /// match.
+    ///
+    /// A signature without any part never matches.
+    #[inline]
+    #[must_use]
+    pub fn with_signature<B: AsRef<[u8]>>(mut self, parts: &[(usize, B)]) -> Self {
+        self.signatures.push(
+            parts
+                .iter()
+                .map(|(offset, value)| (*offset, value.as_ref().to_vec()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Returns the name of the custom format.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the short name of the custom format, if any.
+    #[inline]
+    pub fn short_name(&self) -> Option<&str> {
+        self.short_name.as_deref()
+    }
+
+    /// Returns the common media type of the custom format.
+    #[inline]
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+
+    /// Returns the preferred extension of the custom format.
+    #[inline]
+    pub fn extension(&self) -> &str {
+        &self.extension
+    }
+
+    /// Returns the kind of the custom format.
+    #[inline]
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns `true` if one of the signatures matches the bytes.
+    #[inline]
+    pub(crate) fn matches(&self, bytes: &[u8]) -> bool {
+        self.signatures.iter().any(|parts| {
+            !parts.is_empty()
+                && parts.iter().all(|(offset, value)| {
+                    bytes.get(*offset..offset.saturating_add(value.len())) == Some(value)
+                })
+        })
+    }
+}
+
+impl Display for CustomFormat {
+    #[inline]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.name())
+    }
+}
+
+/// Precedence of a custom format over the built-in signatures.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Precedence {
+    /// The custom format is checked before the built-in signatures, and can shadow them.
+    BeforeBuiltin,
+    /// The custom format is only checked when none of the built-in signatures matches.
+    AfterBuiltin,
+}
+
+/// A file format identified by a [`Detector`](`crate::Detector`), either built-in or custom.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{DetectedFormat, FileFormat, Kind};
+///
+/// let fmt = DetectedFormat::Builtin(FileFormat::Gzip);
+/// assert_eq!(fmt.name(), "gzip");
+/// assert_eq!(fmt.kind(), Kind::Compressed);
+///```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DetectedFormat<'a> {
+    /// A built-in file format.
+    Builtin(FileFormat),
+    /// A custom format registered on the detector.
+    Custom(&'a CustomFormat),
+}
+
+impl DetectedFormat<'_> {
+    /// Returns the name of the file format.
+    #[inline]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Builtin(fmt) => fmt.name(),
+            Self::Custom(fmt) => fmt.name(),
+        }
+    }
+
+    /// Returns the short name of the file format, if any.
+    #[inline]
+    pub fn short_name(&self) -> Option<&str> {
+        match self {
+            Self::Builtin(fmt) => fmt.short_name(),
+            Self::Custom(fmt) => fmt.short_name(),
+        }
+    }
+
+    /// Returns the common media type of the file format.
+    #[inline]
+    pub fn media_type(&self) -> &str {
+        match self {
+            Self::Builtin(fmt) => fmt.media_type(),
+            Self::Custom(fmt) => fmt.media_type(),
+        }
+    }
+
+    /// Returns the preferred extension of the file format.
+    #[inline]
+    pub fn extension(&self) -> &str {
+        match self {
+            Self::Builtin(fmt) => fmt.extension(),
+            Self::Custom(fmt) => fmt.extension(),
+        }
+    }
+
+    /// Returns the kind of the file format.
+    #[inline]
+    pub fn kind(&self) -> Kind {
+        match self {
+            Self::Builtin(fmt) => fmt.kind(),
+            Self::Custom(fmt) => fmt.kind(),
+        }
+    }
+
+    /// Returns the built-in file format, if any.
+    #[inline]
+    pub fn builtin(&self) -> Option<FileFormat> {
+        match self {
+            Self::Builtin(fmt) => Some(*fmt),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Returns the custom format, if any.
+    #[inline]
+    pub fn custom(&self) -> Option<&CustomFormat> {
+        match self {
+            Self::Builtin(_) => None,
+            Self::Custom(fmt) => Some(fmt),
+        }
+    }
+}
+
+impl Default for DetectedFormat<'_> {
+    /// Returns the [default](`FileFormat::default`) built-in file format.
+    #[inline]
+    fn default() -> Self {
+        Self::Builtin(FileFormat::default())
+    }
+}
+
+impl Display for DetectedFormat<'_> {
+    #[inline]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.name())
+    }
+}
+
+impl From<FileFormat> for DetectedFormat<'_> {
+    #[inline]
+    fn from(fmt: FileFormat) -> Self {
+        Self::Builtin(fmt)
+    }
+}
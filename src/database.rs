@@ -0,0 +1,492 @@
+//! Loading of custom formats from a signature database.
+
+use crate::{CustomFormat, Kind};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "std")]
+use std::{
+    error::Error,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+/// An error raised while parsing a signature database.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DatabaseError {
+    /// Line at which the error occurred, starting from 1.
+    line: usize,
+    /// Description of the error.
+    message: String,
+}
+
+impl DatabaseError {
+    /// Creates an error occurring at the specified line.
+    #[inline]
+    fn new<M: Into<String>>(line: usize, message: M) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the line at which the error occurred, starting from 1.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the description of the error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for DatabaseError {
+    #[inline]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DatabaseError {}
+
+impl CustomFormat {
+    /// Parses the custom formats of a signature database.
+    ///
+    /// A database uses the syntax of the built-in format definitions: each entry starts with a
+    /// `format` line naming it, followed by its `name`, optional `short_name`, `media_type`,
+    /// `extension` and `kind`, and by at least one `value` line. Each `value` line is an
+    /// alternative signature, made of comma-separated byte strings which must all match, each at
+    /// offset `0` unless followed by `offset = <n>`. Comments start with `#` or `//`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DatabaseError`] locating the first malformed entry, e.g. an unknown key, a
+    /// missing field, an invalid byte string or an entry without any signature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{CustomFormat, Kind};
+    ///
+    /// let formats = CustomFormat::from_database(
+    ///     r#"
+    ///     format = FooArchive
+    ///     name = "Foo Archive"
+    ///     media_type = "application/x-foo"
+    ///     extension = "foo"
+    ///     kind = Archive
+    ///     value = b"FOO\x01"
+    ///     value = b"FOO\x02", b"\x00\x01" offset = 8
+    ///     "#,
+    /// )?;
+    /// assert_eq!(formats[0].name(), "Foo Archive");
+    /// assert_eq!(formats[0].kind(), Kind::Archive);
+    /// # Ok::<(), file_format::DatabaseError>(())
+    ///```
+    pub fn from_database(source: &str) -> Result<Vec<Self>, DatabaseError> {
+        let mut formats = Vec::new();
+        let mut identifiers = Vec::new();
+        let mut entry: Option<Entry> = None;
+        for (index, line) in source.lines().enumerate() {
+            // Skips blank lines and comments.
+            let number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+
+            // Splits the line into a key and a value.
+            let (key, value) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| DatabaseError::new(number, "expected `<key> = <value>`"))?;
+
+            // Starts a new entry.
+            if key == "format" {
+                if !is_identifier(value) {
+                    return Err(DatabaseError::new(
+                        number,
+                        format!("invalid format identifier `{value}`"),
+                    ));
+                }
+                if identifiers.iter().any(|identifier| identifier == value) {
+                    return Err(DatabaseError::new(
+                        number,
+                        format!("duplicate format `{value}`"),
+                    ));
+                }
+                identifiers.push(value.to_string());
+                if let Some(entry) = entry.replace(Entry::new(number, value)) {
+                    formats.push(entry.finish()?);
+                }
+                continue;
+            }
+
+            // Fills the current entry.
+            let entry = entry
+                .as_mut()
+                .ok_or_else(|| DatabaseError::new(number, "expected `format = <identifier>`"))?;
+            match key {
+                "name" => Entry::set(&mut entry.name, number, key, parse_string(number, value)?)?,
+                "short_name" => Entry::set(
+                    &mut entry.short_name,
+                    number,
+                    key,
+                    parse_string(number, value)?,
+                )?,
+                "media_type" => Entry::set(
+                    &mut entry.media_type,
+                    number,
+                    key,
+                    parse_string(number, value)?,
+                )?,
+                "extension" => Entry::set(
+                    &mut entry.extension,
+                    number,
+                    key,
+                    parse_string(number, value)?,
+                )?,
+                "kind" => {
+                    let kind = parse_kind(value).ok_or_else(|| {
+                        DatabaseError::new(number, format!("unknown kind `{value}`"))
+                    })?;
+                    Entry::set(&mut entry.kind, number, key, kind)?
+                }
+                "value" => entry.signatures.push(parse_signature(number, value)?),
+                _ => return Err(DatabaseError::new(number, format!("unknown key `{key}`"))),
+            }
+        }
+        if let Some(entry) = entry {
+            formats.push(entry.finish()?);
+        }
+        Ok(formats)
+    }
+
+    /// Loads the custom formats of a signature database file.
+    ///
+    /// See [`CustomFormat::from_database`] for the syntax of the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or an error of kind
+    /// [`InvalidData`](`std::io::ErrorKind::InvalidData`) wrapping a [`DatabaseError`] if it is
+    /// malformed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{CustomFormat, Detector, Precedence};
+    ///
+    /// let formats = CustomFormat::from_database_file("signatures.db")?;
+    /// let detector = Detector::builder()
+    ///     .custom_formats(formats, Precedence::AfterBuiltin)
+    ///     .build();
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    pub fn from_database_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<Self>> {
+        Self::from_database(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+    }
+}
+
+/// An entry of a signature database being parsed.
+struct Entry {
+    /// Line of the `format` key.
+    line: usize,
+    /// Identifier of the entry.
+    identifier: String,
+    /// Name of the file format.
+    name: Option<String>,
+    /// Short name of the file format.
+    short_name: Option<String>,
+    /// Common media type of the file format.
+    media_type: Option<String>,
+    /// Preferred extension of the file format.
+    extension: Option<String>,
+    /// Kind of the file format.
+    kind: Option<Kind>,
+    /// Signatures of the file format.
+    signatures: Vec<Vec<(usize, Vec<u8>)>>,
+}
+
+impl Entry {
+    /// Creates an empty entry.
+    #[inline]
+    fn new(line: usize, identifier: &str) -> Self {
+        Self {
+            line,
+            identifier: identifier.to_string(),
+            name: None,
+            short_name: None,
+            media_type: None,
+            extension: None,
+            kind: None,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Sets a field of the entry, which must not have been set yet.
+    #[inline]
+    fn set<T>(
+        field: &mut Option<T>,
+        line: usize,
+        key: &str,
+        value: T,
+    ) -> Result<(), DatabaseError> {
+        if field.replace(value).is_some() {
+            return Err(DatabaseError::new(line, format!("duplicate key `{key}`")));
+        }
+        Ok(())
+    }
+
+    /// Checks the entry and converts it into a custom format.
+    fn finish(self) -> Result<CustomFormat, DatabaseError> {
+        // Checks the required fields.
+        let missing = |key: &str| {
+            DatabaseError::new(
+                self.line,
+                format!("format `{}` is missing `{key}`", self.identifier),
+            )
+        };
+        let name = self.name.clone().ok_or_else(|| missing("name"))?;
+        let media_type = self
+            .media_type
+            .clone()
+            .ok_or_else(|| missing("media_type"))?;
+        let extension = self.extension.clone().ok_or_else(|| missing("extension"))?;
+        let kind = self.kind.ok_or_else(|| missing("kind"))?;
+        if self.signatures.is_empty() {
+            return Err(missing("value"));
+        }
+
+        // Creates the custom format.
+        let mut fmt = CustomFormat::new(name, media_type, extension, kind);
+        if let Some(short_name) = self.short_name {
+            fmt = fmt.with_short_name(short_name);
+        }
+        for parts in &self.signatures {
+            fmt = fmt.with_signature(parts);
+        }
+        Ok(fmt)
+    }
+}
+
+/// Returns `true` if the text is a valid format identifier.
+#[inline]
+fn is_identifier(text: &str) -> bool {
+    text.chars()
+        .next()
+        .map_or(false, |char| char.is_ascii_alphabetic())
+        && text
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Parses the name of a [`Kind`].
+fn parse_kind(name: &str) -> Option<Kind> {
+    Some(match name {
+        "Archive" => Kind::Archive,
+        "Audio" => Kind::Audio,
+        "Compressed" => Kind::Compressed,
+        "Database" => Kind::Database,
+        "Diagram" => Kind::Diagram,
+        "Disk" => Kind::Disk,
+        "Document" => Kind::Document,
+        "Ebook" => Kind::Ebook,
+        "Executable" => Kind::Executable,
+        "Font" => Kind::Font,
+        "Formula" => Kind::Formula,
+        "Geospatial" => Kind::Geospatial,
+        "Image" => Kind::Image,
+        "Metadata" => Kind::Metadata,
+        "Model" => Kind::Model,
+        "Other" => Kind::Other,
+        "Package" => Kind::Package,
+        "Playlist" => Kind::Playlist,
+        "Presentation" => Kind::Presentation,
+        "Rom" => Kind::Rom,
+        "Spreadsheet" => Kind::Spreadsheet,
+        "Subtitle" => Kind::Subtitle,
+        "Video" => Kind::Video,
+        _ => return None,
+    })
+}
+
+/// Parses a string literal, such as `"Foo Archive"`, which must be the whole text.
+fn parse_string(line: usize, text: &str) -> Result<String, DatabaseError> {
+    let mut parser = Parser { line, text };
+    let bytes = parser.literal(false)?;
+    parser.end()?;
+    String::from_utf8(bytes).map_err(|_| DatabaseError::new(line, "invalid UTF-8 string"))
+}
+
+/// Parses a signature, such as `b"FOO", b"\x01" offset = 8`.
+fn parse_signature(line: usize, text: &str) -> Result<Vec<(usize, Vec<u8>)>, DatabaseError> {
+    let mut parser = Parser { line, text };
+    let mut parts = Vec::new();
+    loop {
+        // Reads the value and its offset.
+        let value = parser.literal(true)?;
+        if value.is_empty() {
+            return Err(DatabaseError::new(line, "empty value"));
+        }
+        let offset = if parser.keyword("offset") {
+            parser.expect('=')?;
+            parser.offset()?
+        } else {
+            0
+        };
+        parts.push((offset, value));
+
+        // Reads the next value, if any.
+        if !parser.eat(',') {
+            parser.end()?;
+            return Ok(parts);
+        }
+    }
+}
+
+/// A parser of the values of a database line.
+struct Parser<'a> {
+    /// Line being parsed.
+    line: usize,
+    /// Remaining text.
+    text: &'a str,
+}
+
+impl Parser<'_> {
+    /// Returns an error at the line being parsed.
+    #[inline]
+    fn error<M: Into<String>>(&self, message: M) -> DatabaseError {
+        DatabaseError::new(self.line, message)
+    }
+
+    /// Consumes the specified character, after any whitespace, if present.
+    #[inline]
+    fn eat(&mut self, char: char) -> bool {
+        self.text = self.text.trim_start();
+        match self.text.strip_prefix(char) {
+            Some(text) => {
+                self.text = text;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consumes the specified character, after any whitespace, or returns an error.
+    #[inline]
+    fn expect(&mut self, char: char) -> Result<(), DatabaseError> {
+        if self.eat(char) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{char}`")))
+        }
+    }
+
+    /// Consumes the specified keyword, after any whitespace, if present.
+    #[inline]
+    fn keyword(&mut self, keyword: &str) -> bool {
+        self.text = self.text.trim_start();
+        match self.text.strip_prefix(keyword) {
+            Some(text) if !text.starts_with(|char: char| char.is_alphanumeric()) => {
+                self.text = text;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks that the whole text has been consumed.
+    #[inline]
+    fn end(&mut self) -> Result<(), DatabaseError> {
+        match self.text.trim() {
+            "" => Ok(()),
+            rest => Err(self.error(format!("unexpected `{rest}`"))),
+        }
+    }
+
+    /// Consumes an offset, written in decimal or in hexadecimal with a `0x` prefix.
+    fn offset(&mut self) -> Result<usize, DatabaseError> {
+        self.text = self.text.trim_start();
+        let len = self
+            .text
+            .find(|char: char| !char.is_ascii_alphanumeric() && char != '_')
+            .unwrap_or(self.text.len());
+        let (literal, text) = self.text.split_at(len);
+        let digits = literal.replace('_', "");
+        let offset = match digits.strip_prefix("0x") {
+            Some(digits) => usize::from_str_radix(digits, 16),
+            None => digits.parse(),
+        }
+        .map_err(|_| self.error(format!("invalid offset `{literal}`")))?;
+        self.text = text;
+        Ok(offset)
+    }
+
+    /// Consumes a string literal, optionally prefixed with `b` when it is a byte string.
+    fn literal(&mut self, bytes: bool) -> Result<Vec<u8>, DatabaseError> {
+        self.text = self.text.trim_start();
+        if bytes {
+            self.text = self.text.strip_prefix('b').unwrap_or(self.text);
+        }
+        let mut chars = self
+            .text
+            .strip_prefix('"')
+            .ok_or_else(|| self.error("expected a string literal"))?
+            .char_indices();
+        let mut value = Vec::new();
+        while let Some((_, char)) = chars.next() {
+            match char {
+                '"' => {
+                    self.text = chars.as_str();
+                    return Ok(value);
+                }
+                '\\' => {
+                    let byte = match chars.next().map(|(_, char)| char) {
+                        Some('0') => 0,
+                        Some('n') => b'\n',
+                        Some('r') => b'\r',
+                        Some('t') => b'\t',
+                        Some('\\') => b'\\',
+                        Some('"') => b'"',
+                        Some('\'') => b'\'',
+                        Some('x') => {
+                            let digits: String = chars.by_ref().take(2).map(|(_, c)| c).collect();
+                            Some(&digits)
+                                .filter(|digits| {
+                                    digits.len() == 2
+                                        && digits.chars().all(|char| char.is_ascii_hexdigit())
+                                })
+                                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                                .ok_or_else(|| {
+                                    self.error(format!("invalid escape sequence `\\x{digits}`"))
+                                })?
+                        }
+                        Some(char) => {
+                            return Err(self.error(format!("invalid escape sequence `\\{char}`")))
+                        }
+                        None => break,
+                    };
+                    value.push(byte);
+                }
+                _ => value.extend_from_slice(char.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+        Err(self.error("unterminated string literal"))
+    }
+}
@@ -0,0 +1,156 @@
+//! Detection results, along with their provenance.
+
+use crate::{FileFormat, MediaInfo, Reader, Signature};
+use alloc::string::{String, ToString};
+
+/// A file format detection, along with the provenance of the result.
+///
+/// It records which signature was matched, which [`Reader`] determined the file format and the
+/// marker it relied on, making it possible to log and audit every decision.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::FileFormat;
+///
+/// let detection = FileFormat::detect_bytes(b"\x1F\x8B");
+/// assert_eq!(detection.format, FileFormat::Gzip);
+/// assert_eq!(detection.signature.unwrap().parts(), &[(0, &b"\x1F\x8B"[..])]);
+/// assert!(!detection.fell_back());
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct Detection {
+    /// Detected file format.
+    pub format: FileFormat,
+    /// Signature matched at the start of the stream, if any.
+    pub signature: Option<Signature>,
+    /// Reader which determined the file format, if any.
+    pub reader: Option<Reader>,
+    /// Marker on which the reader relied to determine the file format, if any (e.g. the name of a
+    /// ZIP entry, the CLSID of a CFB root entry or the root element of an XML document).
+    pub marker: Option<String>,
+    /// Error raised by the format reader, if the detection fell back to the generic reader.
+    pub reader_error: Option<String>,
+    /// `true` if the reader found the stream truncated and determined the file format from the
+    /// data available (e.g. by walking the local file headers of a ZIP stream whose central
+    /// directory lies beyond the prefix read from a stream which cannot seek).
+    pub truncated: bool,
+    /// `true` if the content is encrypted or password-protected, as far as the reader and the
+    /// header could tell:
+    ///
+    /// - a ZIP entry, among the first [`zip_entry_limit`](`crate::DetectorBuilder::zip_entry_limit`)
+    ///   ones, has the encryption flag set or uses AES;
+    /// - a PDF has an `/Encrypt` entry in its trailer or cross-reference stream dictionary;
+    /// - a CFB holds the `EncryptionInfo` and `EncryptedPackage` streams of an encrypted Office
+    ///   document;
+    /// - a 7z has an AES-encoded header, or a RAR has encrypted headers or a first encrypted file.
+    pub encrypted: bool,
+    /// `true` if the document holds a VBA project, as far as the reader could tell:
+    ///
+    /// - an Office Open XML package declares or holds a `vbaProject.bin` part;
+    /// - a CFB holds the `Macros` storage of a Word document or the `_VBA_PROJECT_CUR` storage of
+    ///   an Excel workbook.
+    pub has_macros: bool,
+    /// Name of the [`Detector`](`crate::Detector`) limit reached by the reader before it could
+    /// conclude, if any (e.g. `"zip_entry_limit"`).
+    pub limit_exceeded: Option<&'static str>,
+    /// Extension which refined a generic file format determined from the content, if any (e.g.
+    /// `"docx"` for a [ZIP](`FileFormat::Zip`) detected with `FileFormat::detect_file_with_hint`).
+    pub hint: Option<String>,
+    /// Offset and file format of the data appended to an executable image, if any (e.g. the
+    /// [7-Zip](`FileFormat::SevenZip`) payload of a self-extracting archive).
+    ///
+    /// Only ELF and PE images are read. The overlay of a signed PE image ends at its certificate
+    /// table. Images nested in the overlays of four others have their overlay left unread. Mach-O
+    /// images are out of scope, so their overlay is never reported.
+    pub overlay: Option<(u64, FileFormat)>,
+    /// Version of the file format matched by the signature, read from its header, if known:
+    ///
+    /// - the version of a PDF (e.g. `"1.4"`), a GIF (`"87a"` or `"89a"`) or a Java class
+    ///   (`"<major>.<minor>"`, e.g. `"52.0"`);
+    /// - the archive format of a RAR (`"1.5"` or `"5.0"`) and the version needed to extract the
+    ///   first entry of a ZIP (e.g. `"2.0"`);
+    /// - the version of SQLite which last wrote a SQLite 3 database (e.g. `"3.45.1"`);
+    /// - the variant of a TIFF (`"TIFF"` or `"BigTIFF"`) or a PNG (`"PNG"` or `"APNG"`) and the
+    ///   class of an ELF (`"ELF32"` or `"ELF64"`);
+    /// - the outlines of an OpenType or TrueType font (`"CFF"`, `"CFF2"` or `"TrueType"`);
+    /// - the DocTypeVersion of an EBML document, such as a Matroska one (e.g. `"4"`).
+    pub version: Option<String>,
+    /// Duration and tracks of a media container, collected by its reader, if any (e.g. the
+    /// CodecID of every track of a Matroska).
+    pub media: Option<MediaInfo>,
+}
+
+impl Detection {
+    /// Creates a detection of the specified file format, without any provenance.
+    #[inline]
+    pub(crate) fn new(format: FileFormat) -> Self {
+        Self {
+            format,
+            signature: None,
+            reader: None,
+            marker: None,
+            reader_error: None,
+            truncated: false,
+            encrypted: false,
+            has_macros: false,
+            limit_exceeded: None,
+            hint: None,
+            overlay: None,
+            version: None,
+            media: None,
+        }
+    }
+
+    /// Returns `true` if the format reader failed and the detection fell back to the generic
+    /// reader.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A");
+    /// assert!(!detection.fell_back());
+    ///```
+    #[inline]
+    pub fn fell_back(&self) -> bool {
+        self.reader_error.is_some()
+    }
+
+    /// Records the failure of the format reader, discarding the provenance it produced.
+    #[inline]
+    pub(crate) fn fall_back<E: ToString>(&mut self, error: E) {
+        self.reader = None;
+        self.marker = None;
+        self.reader_error = Some(error.to_string());
+        self.truncated = false;
+        self.encrypted = false;
+        self.has_macros = false;
+        self.limit_exceeded = None;
+        self.overlay = None;
+        self.media = None;
+    }
+
+    /// Records the marker on which the reader relied and returns the file format it determined.
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn mark<M: AsRef<[u8]>>(&mut self, marker: M, fmt: FileFormat) -> FileFormat {
+        self.marker = Some(String::from_utf8_lossy(marker.as_ref()).into_owned());
+        fmt
+    }
+}
+
+impl Default for Detection {
+    /// Returns a detection of the [default](`FileFormat::default`) file format, without any
+    /// provenance.
+    #[inline]
+    fn default() -> Self {
+        Self::new(FileFormat::default())
+    }
+}
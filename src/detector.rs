@@ -0,0 +1,802 @@
+//! Configurable detection, with runtime limits and reader toggles.
+
+use crate::{
+    encryption,
+    io::{Cursor, Read, Result, Seek},
+    version, CustomFormat, DetectedFormat, Detection, DetectionError, FileFormat, Precedence,
+    Reader, Signature,
+};
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use crate::Stream;
+#[cfg(feature = "std")]
+use std::{fs::File, path::Path};
+
+/// A reusable file format detector, holding the budgets of the detection and the readers it may
+/// use.
+///
+/// The functions of [`FileFormat`] use the [default](`Detector::default`) detector. A detector
+/// built with [`Detector::builder`] makes it possible to pick a different tradeoff between cost
+/// and accuracy, e.g. for each class of input handled by the same program.
+///
+/// Format readers are only available when their respective `reader-*` feature is enabled, a
+/// detector can only disable them at runtime.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{Detector, FileFormat, Reader};
+///
+/// let detector = Detector::builder()
+///     .probe_size(1024)
+///     .zip_entry_limit(16)
+///     .reader(Reader::Txt, false)
+///     .build();
+///
+/// let fmt = detector.from_bytes(b"hello");
+/// assert_eq!(fmt, FileFormat::ArbitraryBinaryData);
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Detector {
+    /// Number of bytes read to match the signatures.
+    pub(crate) probe_size: usize,
+    /// Maximum number of bytes on which a stream which cannot seek is detected.
+    pub(crate) stream_prefix_size: u64,
+    /// Maximum number of EBML elements processed by the EBML reader.
+    pub(crate) ebml_element_limit: usize,
+    /// Maximum number of boxes processed by the MP4 reader.
+    pub(crate) mp4_box_limit: usize,
+    /// Maximum number of bytes processed by the PDF reader.
+    pub(crate) pdf_read_limit: usize,
+    /// Maximum number of lines processed by the TXT reader.
+    pub(crate) txt_line_limit: usize,
+    /// Maximum number of entries processed by the ZIP reader.
+    pub(crate) zip_entry_limit: usize,
+    /// Maximum number of compressed layers decompressed by the layered detection.
+    pub(crate) layer_limit: usize,
+    /// Maximum number of bytes read and decompressed for each layer by the layered detection.
+    pub(crate) layer_prefix_size: usize,
+    /// Whether the detection runs on the prefix of a stream which cannot seek.
+    pub(crate) stream: bool,
+    /// Whether the readers raise an error on inconsistent data instead of stopping where it
+    /// starts.
+    pub(crate) strict: bool,
+    /// Number of executable images in whose overlay the detection runs.
+    pub(crate) overlay_depth: usize,
+    /// Readers disabled at runtime, as a bit set indexed by [`Reader`].
+    disabled_readers: u16,
+    /// Custom formats registered at runtime, along with their precedence.
+    custom_formats: Vec<(Precedence, CustomFormat)>,
+}
+
+impl Detector {
+    /// Returns a builder of detector, initialized with the default budgets and every available
+    /// reader enabled.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::Detector;
+    ///
+    /// let detector = Detector::builder().build();
+    /// assert_eq!(detector, Detector::default());
+    ///```
+    #[inline]
+    pub fn builder() -> DetectorBuilder {
+        DetectorBuilder(Self::default())
+    }
+
+    /// Determines file format from bytes.
+    ///
+    /// See [`FileFormat::from_bytes`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let fmt = Detector::default().from_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A");
+    /// assert_eq!(fmt, FileFormat::PortableNetworkGraphics);
+    ///```
+    #[inline]
+    pub fn from_bytes<B: AsRef<[u8]>>(&self, bytes: B) -> FileFormat {
+        self.detect_bytes(bytes).format
+    }
+
+    /// Determines file format from a file.
+    ///
+    /// See [`FileFormat::from_file`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let fmt = Detector::default().from_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(fmt, FileFormat::AudioVideoInterleave);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_file<P: AsRef<Path>>(&self, path: P) -> Result<FileFormat> {
+        self.from_reader(File::open(path)?)
+    }
+
+    /// Determines file format from a memory-mapped file.
+    ///
+    /// See [`FileFormat::from_file_mmap`] for details.
+    ///
+    /// # Safety
+    ///
+    /// See [`FileFormat::from_file_mmap`] for the contract the caller must uphold.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// // SAFETY: the fixture is not modified during the detection.
+    /// let fmt = unsafe { Detector::default().from_file_mmap("fixtures/document/sample.pdf")? };
+    /// assert_eq!(fmt, FileFormat::PortableDocumentFormat);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(feature = "mmap", allow(unsafe_code))]
+    #[inline]
+    pub unsafe fn from_file_mmap<P: AsRef<Path>>(&self, path: P) -> Result<FileFormat> {
+        // SAFETY: the caller upholds the contract of `detect_file_mmap`, which is the same.
+        unsafe { self.detect_file_mmap(path) }.map(|detection| detection.format)
+    }
+
+    /// Determines file format from a reader.
+    ///
+    /// See [`FileFormat::from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let fmt = Detector::default().from_reader(std::io::empty())?;
+    /// assert_eq!(fmt, FileFormat::Empty);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_reader<R: Read + Seek>(&self, reader: R) -> Result<FileFormat> {
+        self.detect_reader(reader).map(|detection| detection.format)
+    }
+
+    /// Determines file format from a stream which cannot seek.
+    ///
+    /// See [`FileFormat::from_stream`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let (fmt, _) = Detector::default().from_stream(std::io::empty())?;
+    /// assert_eq!(fmt, FileFormat::Empty);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_stream<R: Read>(&self, reader: R) -> Result<(FileFormat, Stream<R>)> {
+        self.detect_stream(reader)
+            .map(|(detection, stream)| (detection.format, stream))
+    }
+
+    /// Determines file format from bytes, along with the provenance of the result.
+    ///
+    /// See [`FileFormat::detect_bytes`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let detection = Detector::default().detect_bytes(b"\x1F\x8B");
+    /// assert_eq!(detection.format, FileFormat::Gzip);
+    ///```
+    #[inline]
+    pub fn detect_bytes<B: AsRef<[u8]>>(&self, bytes: B) -> Detection {
+        self.detect(Cursor::new(bytes.as_ref())).unwrap_or_default()
+    }
+
+    /// Determines file format from a file, along with the provenance of the result.
+    ///
+    /// See [`FileFormat::detect_file`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let detection = Detector::default().detect_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(detection.format, FileFormat::AudioVideoInterleave);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn detect_file<P: AsRef<Path>>(&self, path: P) -> Result<Detection> {
+        self.detect_reader(File::open(path)?)
+    }
+
+    /// Determines file format from a memory-mapped file, along with the provenance of the result.
+    ///
+    /// See [`FileFormat::from_file_mmap`] for details.
+    ///
+    /// # Safety
+    ///
+    /// See [`FileFormat::from_file_mmap`] for the contract the caller must uphold.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// // SAFETY: the fixture is not modified during the detection.
+    /// let detection =
+    ///     unsafe { Detector::default().detect_file_mmap("fixtures/document/sample.pdf")? };
+    /// assert_eq!(detection.format, FileFormat::PortableDocumentFormat);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(feature = "mmap", allow(unsafe_code))]
+    pub unsafe fn detect_file_mmap<P: AsRef<Path>>(&self, path: P) -> Result<Detection> {
+        let file = File::open(path)?;
+
+        // Maps the file, unless it is empty since some platforms cannot map it.
+        if file.metadata()?.len() == 0 {
+            return Ok(Detection::new(FileFormat::Empty));
+        }
+        // SAFETY: the caller guarantees that the file is not modified while it is mapped.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+
+        // Determines file format, using the first bytes of the map as the probe.
+        let probe = &map[..core::cmp::min(map.len(), self.probe_size)];
+        let mut reader = Cursor::new(&map[..]);
+        reader.set_position(probe.len() as u64);
+        Ok(self.detect_probe(reader, probe))
+    }
+
+    /// Determines file format from a reader, along with the provenance of the result.
+    ///
+    /// See [`FileFormat::detect_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let detection = Detector::default().detect_reader(std::io::empty())?;
+    /// assert_eq!(detection.format, FileFormat::Empty);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn detect_reader<R: Read + Seek>(&self, reader: R) -> Result<Detection> {
+        self.detect(reader)
+    }
+
+    /// Determines file format from a reader, whatever the I/O primitives in use.
+    pub(crate) fn detect<R: Read + Seek>(&self, mut reader: R) -> Result<Detection> {
+        // Creates and fills a buffer.
+        let mut buf = vec![0; self.probe_size];
+        let nread = reader.read(&mut buf)?;
+
+        // Determines file format.
+        Ok(self.detect_probe(reader, &buf[..nread]))
+    }
+
+    /// Determines file format from a stream which cannot seek, along with the provenance of the
+    /// result.
+    ///
+    /// See [`FileFormat::from_stream`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let (detection, _) = Detector::default().detect_stream(std::io::empty())?;
+    /// assert_eq!(detection.format, FileFormat::Empty);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    pub fn detect_stream<R: Read>(&self, mut reader: R) -> Result<(Detection, Stream<R>)> {
+        // Reads the prefix.
+        let mut prefix = Vec::new();
+        reader
+            .by_ref()
+            .take(self.stream_prefix_size)
+            .read_to_end(&mut prefix)?;
+
+        // Determines file format, letting the readers know the prefix may end abruptly.
+        let detector = Self {
+            stream: true,
+            ..self.clone()
+        };
+        let detection = detector.detect(Cursor::new(&prefix))?;
+
+        // Chains the prefix with the rest of the stream.
+        Ok((detection, Cursor::new(prefix).chain(reader)))
+    }
+
+    /// Determines file format from bytes, surfacing the errors preventing a conclusive
+    /// detection.
+    ///
+    /// See [`FileFormat::try_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let detection = Detector::default().try_detect_bytes(b"\x1F\x8B")?;
+    /// assert_eq!(detection.format, FileFormat::Gzip);
+    /// # Ok::<(), file_format::DetectionError>(())
+    ///```
+    #[inline]
+    pub fn try_detect_bytes<B: AsRef<[u8]>>(
+        &self,
+        bytes: B,
+    ) -> core::result::Result<Detection, DetectionError> {
+        let bytes = bytes.as_ref();
+        let probe = &bytes[..core::cmp::min(bytes.len(), self.probe_size)];
+        self.try_detect_probe(Cursor::new(bytes), probe)
+    }
+
+    /// Determines file format from a file, surfacing the errors preventing a conclusive
+    /// detection.
+    ///
+    /// See [`FileFormat::try_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let detection = Detector::default().try_detect_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(detection.format, FileFormat::AudioVideoInterleave);
+    /// # Ok::<(), file_format::DetectionError>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_detect_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> core::result::Result<Detection, DetectionError> {
+        self.try_detect_reader(File::open(path)?)
+    }
+
+    /// Determines file format from a reader, surfacing the errors preventing a conclusive
+    /// detection.
+    ///
+    /// See [`FileFormat::try_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let detection = Detector::default().try_detect_reader(std::io::empty())?;
+    /// assert_eq!(detection.format, FileFormat::Empty);
+    /// # Ok::<(), file_format::DetectionError>(())
+    ///```
+    #[cfg(feature = "std")]
+    pub fn try_detect_reader<R: Read + Seek>(
+        &self,
+        mut reader: R,
+    ) -> core::result::Result<Detection, DetectionError> {
+        // Creates and fills a buffer.
+        let mut buf = vec![0; self.probe_size];
+        let nread = reader.read(&mut buf)?;
+
+        // Determines file format.
+        self.try_detect_probe(reader, &buf[..nread])
+    }
+
+    /// Identifies the format of bytes, among the built-in and custom file formats.
+    ///
+    /// See [`Detector::identify_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{CustomFormat, DetectedFormat, Detector, FileFormat, Kind, Precedence};
+    ///
+    /// let detector = Detector::builder()
+    ///     .custom_format(
+    ///         CustomFormat::new("Foo Archive", "application/x-foo", "foo", Kind::Archive)
+    ///             .with_signature(&[(0, b"FOO\x01")]),
+    ///         Precedence::AfterBuiltin,
+    ///     )
+    ///     .build();
+    ///
+    /// let fmt = detector.identify_bytes(b"FOO\x01\x00\x00");
+    /// assert_eq!(fmt.name(), "Foo Archive");
+    ///
+    /// let fmt = detector.identify_bytes(b"\x1F\x8B");
+    /// assert_eq!(fmt, DetectedFormat::Builtin(FileFormat::Gzip));
+    ///```
+    #[inline]
+    pub fn identify_bytes<B: AsRef<[u8]>>(&self, bytes: B) -> DetectedFormat<'_> {
+        self.identify(Cursor::new(bytes.as_ref()))
+            .unwrap_or_default()
+    }
+
+    /// Identifies the format of a file, among the built-in and custom file formats.
+    ///
+    /// See [`Detector::identify_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{DetectedFormat, Detector, FileFormat};
+    ///
+    /// let detector = Detector::default();
+    /// let fmt = detector.identify_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(fmt, DetectedFormat::Builtin(FileFormat::AudioVideoInterleave));
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn identify_file<P: AsRef<Path>>(&self, path: P) -> Result<DetectedFormat<'_>> {
+        self.identify_reader(File::open(path)?)
+    }
+
+    /// Identifies the format of a reader, among the built-in and custom file formats.
+    ///
+    /// The custom formats registered with [`Precedence::BeforeBuiltin`] are checked first, in
+    /// their registration order. Then come the built-in signatures, and the custom formats
+    /// registered with [`Precedence::AfterBuiltin`] are only checked when none of them matches,
+    /// before falling back to the generic reader.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{DetectedFormat, Detector, FileFormat};
+    ///
+    /// let detector = Detector::default();
+    /// let fmt = detector.identify_reader(std::io::empty())?;
+    /// assert_eq!(fmt, DetectedFormat::Builtin(FileFormat::Empty));
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn identify_reader<R: Read + Seek>(&self, reader: R) -> Result<DetectedFormat<'_>> {
+        self.identify(reader)
+    }
+
+    /// Identifies the format of a reader, whatever the I/O primitives in use.
+    fn identify<R: Read + Seek>(&self, mut reader: R) -> Result<DetectedFormat<'_>> {
+        // Creates and fills a buffer.
+        let mut buf = vec![0; self.probe_size];
+        let nread = reader.read(&mut buf)?;
+        let probe = &buf[..nread];
+
+        // Checks the custom formats taking precedence over the built-in signatures.
+        if let Some(fmt) = self.find_custom_format(probe, Precedence::BeforeBuiltin) {
+            return Ok(DetectedFormat::Custom(fmt));
+        }
+
+        // Checks the custom formats coming after the built-in signatures.
+        if !probe.is_empty() && Signature::find(probe).is_none() {
+            if let Some(fmt) = self.find_custom_format(probe, Precedence::AfterBuiltin) {
+                return Ok(DetectedFormat::Custom(fmt));
+            }
+        }
+
+        // Determines the built-in file format.
+        Ok(DetectedFormat::Builtin(
+            self.detect_probe(reader, probe).format,
+        ))
+    }
+
+    /// Determines file format from a reader, whose first bytes have already been read.
+    fn detect_probe<R: Read + Seek>(&self, mut reader: R, probe: &[u8]) -> Detection {
+        let mut detection = Detection::new(FileFormat::Empty);
+        if probe.is_empty() {
+            return detection;
+        }
+        if let Err(error) = self.read_probe(&mut reader, probe, &mut detection) {
+            detection.fall_back(error);
+            detection.format = FileFormat::from_generic_reader(&mut reader, self, &mut detection);
+        }
+        detection
+    }
+
+    /// Determines file format from a reader, whose first bytes have already been read, surfacing
+    /// the errors preventing a conclusive detection.
+    fn try_detect_probe<R: Read + Seek>(
+        &self,
+        mut reader: R,
+        probe: &[u8],
+    ) -> core::result::Result<Detection, DetectionError> {
+        let mut detection = Detection::new(FileFormat::Empty);
+        if probe.is_empty() {
+            return Ok(detection);
+        }
+        let detector = Self {
+            strict: true,
+            ..self.clone()
+        };
+        if let Err(error) = detector.read_probe(&mut reader, probe, &mut detection) {
+            return Err(DetectionError::from_reader_error(
+                detection
+                    .signature
+                    .map_or(detection.format, |sig| sig.format),
+                error,
+            ));
+        }
+        let format = detection
+            .signature
+            .map_or(detection.format, |sig| sig.format);
+        if detection.truncated {
+            return Err(DetectionError::Truncated { format });
+        }
+        if let Some(limit) = detection.limit_exceeded {
+            return Err(DetectionError::LimitExceeded { format, limit });
+        }
+        Ok(detection)
+    }
+
+    /// Determines file format from the format reader of the signature matched by the first bytes,
+    /// or from the generic reader if none matches.
+    fn read_probe<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        probe: &[u8],
+        detection: &mut Detection,
+    ) -> Result<()> {
+        detection.signature = Signature::find(probe).copied();
+        detection.format = match detection.signature {
+            Some(signature) => {
+                let fmt =
+                    FileFormat::from_fmt_reader(signature.format, &mut *reader, self, detection)?;
+                detection.version = version::from_probe(signature.format, probe);
+                detection.encrypted |=
+                    encryption::from_reader(signature.format, &mut *reader, probe);
+                fmt
+            }
+            None => FileFormat::from_generic_reader(&mut *reader, self, detection),
+        };
+        Ok(())
+    }
+
+    /// Returns the first registered custom format of the specified precedence matching the bytes,
+    /// if any.
+    #[inline]
+    fn find_custom_format(&self, bytes: &[u8], precedence: Precedence) -> Option<&CustomFormat> {
+        self.custom_formats
+            .iter()
+            .filter(|(custom_precedence, _)| *custom_precedence == precedence)
+            .map(|(_, fmt)| fmt)
+            .find(|fmt| fmt.matches(bytes))
+    }
+
+    /// Returns the format reader used for the specified file format, if it is available and
+    /// enabled.
+    #[inline]
+    pub(crate) fn reader(&self, fmt: FileFormat) -> Option<Reader> {
+        Reader::from_format(fmt).filter(|&reader| self.is_enabled(reader))
+    }
+
+    /// Returns `true` if the specified reader is enabled.
+    #[inline]
+    pub(crate) fn is_enabled(&self, reader: Reader) -> bool {
+        self.disabled_readers & 1 << reader as u16 == 0
+    }
+}
+
+impl Default for Detector {
+    /// Returns a detector with the default budgets and every available reader enabled.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            probe_size: 36_870,
+            stream_prefix_size: 1_048_576,
+            ebml_element_limit: 256,
+            mp4_box_limit: 256,
+            pdf_read_limit: 33_554_432,
+            txt_line_limit: 16,
+            zip_entry_limit: 1024,
+            layer_limit: 4,
+            layer_prefix_size: 1_048_576,
+            stream: false,
+            strict: false,
+            overlay_depth: 0,
+            disabled_readers: 0,
+            custom_formats: Vec::new(),
+        }
+    }
+}
+
+/// A builder of [`Detector`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{Detector, FileFormat, Reader};
+///
+/// let detector = Detector::builder().reader(Reader::Zip, false).build();
+/// let fmt = detector.from_file("fixtures/document/sample.docx")?;
+/// assert_eq!(fmt, FileFormat::Zip);
+/// # Ok::<(), std::io::Error>(())
+///```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DetectorBuilder(Detector);
+
+impl DetectorBuilder {
+    /// Sets the number of bytes read to match the signatures (36 870 bytes by default).
+    ///
+    /// Signatures extending beyond the probe cannot match, which is the case of every signature
+    /// when the probe is shorter than its first byte offset plus its length, e.g. a probe of 4
+    /// bytes no longer matches [PNG](`FileFormat::PortableNetworkGraphics`). A size of 0 is raised
+    /// to 1 byte, so that only empty inputs are detected as [`FileFormat::Empty`], including by
+    /// an [`IncrementalDetector`](`crate::IncrementalDetector`) or an
+    /// [`EmbeddedScanner`](`crate::EmbeddedScanner`) using the detector.
+    #[inline]
+    #[must_use]
+    pub fn probe_size(mut self, size: usize) -> Self {
+        self.0.probe_size = size.max(1);
+        self
+    }
+
+    /// Sets the maximum number of bytes on which a stream which cannot seek is detected (1 MB by
+    /// default).
+    #[inline]
+    #[must_use]
+    pub fn stream_prefix_size(mut self, size: u64) -> Self {
+        self.0.stream_prefix_size = size;
+        self
+    }
+
+    /// Sets the maximum number of elements processed by the EBML reader (256 by default).
+    #[inline]
+    #[must_use]
+    pub fn ebml_element_limit(mut self, limit: usize) -> Self {
+        self.0.ebml_element_limit = limit;
+        self
+    }
+
+    /// Sets the maximum number of boxes processed by the MP4 reader (256 by default).
+    #[inline]
+    #[must_use]
+    pub fn mp4_box_limit(mut self, limit: usize) -> Self {
+        self.0.mp4_box_limit = limit;
+        self
+    }
+
+    /// Sets the maximum number of bytes processed by the PDF reader (32 MB by default).
+    #[inline]
+    #[must_use]
+    pub fn pdf_read_limit(mut self, limit: usize) -> Self {
+        self.0.pdf_read_limit = limit;
+        self
+    }
+
+    /// Sets the maximum number of lines processed by the TXT reader (16 by default).
+    #[inline]
+    #[must_use]
+    pub fn txt_line_limit(mut self, limit: usize) -> Self {
+        self.0.txt_line_limit = limit;
+        self
+    }
+
+    /// Sets the maximum number of entries processed by the ZIP reader (1024 by default).
+    #[inline]
+    #[must_use]
+    pub fn zip_entry_limit(mut self, limit: usize) -> Self {
+        self.0.zip_entry_limit = limit;
+        self
+    }
+
+    /// Sets the maximum number of compressed layers decompressed by the layered detection (4 by
+    /// default).
+    #[inline]
+    #[must_use]
+    pub fn layer_limit(mut self, limit: usize) -> Self {
+        self.0.layer_limit = limit;
+        self
+    }
+
+    /// Sets the maximum number of bytes read and decompressed for each layer by the layered
+    /// detection (1 MB by default).
+    #[inline]
+    #[must_use]
+    pub fn layer_prefix_size(mut self, size: usize) -> Self {
+        self.0.layer_prefix_size = size;
+        self
+    }
+
+    /// Enables or disables the specified reader (every available reader is enabled by default).
+    ///
+    /// A disabled format reader leaves the file format determined by the signature, whereas a
+    /// disabled [TXT reader](`Reader::Txt`) leaves the [default](`FileFormat::default`) file
+    /// format when no signature matches.
+    #[inline]
+    #[must_use]
+    pub fn reader(mut self, reader: Reader, enabled: bool) -> Self {
+        if enabled {
+            self.0.disabled_readers &= !(1 << reader as u16);
+        } else {
+            self.0.disabled_readers |= 1 << reader as u16;
+        }
+        self
+    }
+
+    /// Registers a custom format, checked before or after the built-in signatures depending on
+    /// its precedence.
+    ///
+    /// Custom formats are only identified by the `identify_*` functions of [`Detector`].
+    #[inline]
+    #[must_use]
+    pub fn custom_format(mut self, format: CustomFormat, precedence: Precedence) -> Self {
+        self.0.custom_formats.push((precedence, format));
+        self
+    }
+
+    /// Registers custom formats, such as the ones loaded from a signature database, checked
+    /// before or after the built-in signatures depending on their precedence.
+    ///
+    /// See [`DetectorBuilder::custom_format`] for details.
+    #[inline]
+    #[must_use]
+    pub fn custom_formats<I>(mut self, formats: I, precedence: Precedence) -> Self
+    where
+        I: IntoIterator<Item = CustomFormat>,
+    {
+        self.0
+            .custom_formats
+            .extend(formats.into_iter().map(|format| (precedence, format)));
+        self
+    }
+
+    /// Builds the detector.
+    #[inline]
+    pub fn build(self) -> Detector {
+        self.0
+    }
+}
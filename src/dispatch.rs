@@ -0,0 +1,165 @@
+//! Compiled dispatch of the known signatures, indexed by the offset and byte they start with.
+
+use crate::{signatures::SIGNATURES, Signature};
+
+/// Number of known signatures.
+const LEN: usize = SIGNATURES.len();
+
+/// Maximum number of distinct offsets at which the known signatures start.
+const OFFSET_LIMIT: usize = 32;
+
+// Indices of signatures are stored as `u16`.
+const _: () = assert!(LEN <= u16::MAX as usize);
+
+/// Dispatch structure of the known signatures, built at compile time.
+pub(crate) static DISPATCH: Dispatch = Dispatch::new();
+
+/// An index of the known signatures, in compressed sparse row layout.
+///
+/// Signatures are grouped by the offset of their first part, then by the first byte of this part,
+/// so that only the ones expecting the bytes actually present at their offset are checked. Each
+/// group keeps the order of [`SIGNATURES`], and the lookup merges the groups to preserve the
+/// precedence of the signatures listed first.
+pub(crate) struct Dispatch {
+    /// Distinct offsets at which the signatures start, in ascending order.
+    offsets: [usize; OFFSET_LIMIT],
+    /// Number of distinct offsets.
+    offsets_len: usize,
+    /// Start of each group in `indices`, for each offset and byte, followed by the end of the last
+    /// group of the offset.
+    starts: [[u16; 257]; OFFSET_LIMIT],
+    /// Indices of the signatures, grouped by offset and byte.
+    indices: [u16; LEN],
+}
+
+impl Dispatch {
+    /// Builds the dispatch structure of the known signatures.
+    const fn new() -> Self {
+        let mut offsets = [0; OFFSET_LIMIT];
+        let mut offsets_len = 0;
+        let mut starts = [[0; 257]; OFFSET_LIMIT];
+        let mut indices = [0; LEN];
+
+        // Collects the distinct offsets, in ascending order.
+        let mut index = 0;
+        while index < LEN {
+            let offset = SIGNATURES[index].parts[0].0;
+            let mut position = 0;
+            while position < offsets_len && offsets[position] < offset {
+                position += 1;
+            }
+            if position == offsets_len || offsets[position] != offset {
+                assert!(offsets_len < OFFSET_LIMIT, "too many signature offsets");
+                let mut shifted = offsets_len;
+                while shifted > position {
+                    offsets[shifted] = offsets[shifted - 1];
+                    shifted -= 1;
+                }
+                offsets[position] = offset;
+                offsets_len += 1;
+            }
+            index += 1;
+        }
+
+        // Counts the signatures of each group.
+        let mut index = 0;
+        while index < LEN {
+            let (group, byte) = key(&offsets, offsets_len, &SIGNATURES[index]);
+            starts[group][byte] += 1;
+            index += 1;
+        }
+
+        // Computes the start of each group from the counts, the groups of an offset following the
+        // ones of the previous offset.
+        let mut total = 0;
+        let mut group = 0;
+        while group < offsets_len {
+            let mut byte = 0;
+            while byte < 257 {
+                let count = starts[group][byte];
+                starts[group][byte] = total;
+                total += count;
+                byte += 1;
+            }
+            group += 1;
+        }
+
+        // Fills the groups, keeping the order of the signatures.
+        let mut next = starts;
+        let mut index = 0;
+        while index < LEN {
+            let (group, byte) = key(&offsets, offsets_len, &SIGNATURES[index]);
+            indices[next[group][byte] as usize] = index as u16;
+            next[group][byte] += 1;
+            index += 1;
+        }
+
+        Self {
+            offsets,
+            offsets_len,
+            starts,
+            indices,
+        }
+    }
+
+    /// Returns the signatures which may match the bytes, in the order in which they are checked.
+    #[inline]
+    pub(crate) fn candidates(&'static self, bytes: &[u8]) -> Candidates {
+        let mut candidates = Candidates {
+            groups: [&[]; OFFSET_LIMIT],
+            len: 0,
+        };
+        for (group, &offset) in self.offsets[..self.offsets_len].iter().enumerate() {
+            let byte = match bytes.get(offset) {
+                Some(&byte) => byte as usize,
+                None => break,
+            };
+            let start = self.starts[group][byte] as usize;
+            let end = self.starts[group][byte + 1] as usize;
+            if start < end {
+                candidates.groups[candidates.len] = &self.indices[start..end];
+                candidates.len += 1;
+            }
+        }
+        candidates
+    }
+}
+
+/// An iterator over the signatures which may match some bytes, merging their groups in the order
+/// in which they are checked.
+pub(crate) struct Candidates {
+    /// Remaining signatures of the non-empty groups.
+    groups: [&'static [u16]; OFFSET_LIMIT],
+    /// Number of non-empty groups.
+    len: usize,
+}
+
+impl Iterator for Candidates {
+    type Item = &'static Signature;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // Finds the group holding the next signature.
+        let groups = &mut self.groups[..self.len];
+        let group = (0..groups.len()).min_by_key(|&group| groups[group][0])?;
+        let index = groups[group][0];
+
+        // Removes the signature, and the group once exhausted.
+        groups[group] = &groups[group][1..];
+        if groups[group].is_empty() {
+            self.len -= 1;
+            self.groups[group] = self.groups[self.len];
+        }
+        Some(&SIGNATURES[index as usize])
+    }
+}
+
+/// Returns the group and the byte under which the signature is indexed.
+const fn key(offsets: &[usize; OFFSET_LIMIT], len: usize, signature: &Signature) -> (usize, usize) {
+    let (offset, value) = signature.parts[0];
+    let mut group = 0;
+    while group < len && offsets[group] != offset {
+        group += 1;
+    }
+    (group, value[0] as usize)
+}
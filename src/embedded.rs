@@ -0,0 +1,281 @@
+//! Detection of file formats embedded at arbitrary offsets.
+
+use crate::{
+    dispatch::DISPATCH,
+    io::{Cursor, Read, Result, Seek, SeekFrom, Shifted},
+    Detection, Detector, FileFormat, Kind, Signature,
+};
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::{fs::File, path::Path};
+
+/// Number of offsets checked between two refills of the buffer.
+const CHUNK_SIZE: usize = 0x10000;
+
+/// A scanner of file formats embedded at arbitrary offsets, such as the ones of a firmware blob or
+/// a disk dump.
+///
+/// Unlike [`FileFormat::from_reader`], which only matches the signatures at the start of the
+/// stream, every offset of the stream is checked:
+///
+/// - the first signature matching at the offset, in the order of [`Signature::all`], is retained,
+///   provided that it checks at least [`EmbeddedScannerBuilder::min_signature_size`] bytes;
+/// - the structure is validated by the format reader of the signature, if any, reading from the
+///   offset onward. A signature whose format reader fails is discarded in favor of the next one;
+/// - the file format is reported along with the offset, provided that its kind is one of
+///   [`EmbeddedScannerBuilder::kinds`].
+///
+/// Embedded structures are not skipped, hence nested structures, such as the entries of a ZIP, are
+/// reported as well.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{EmbeddedScanner, FileFormat};
+///
+/// let mut blob = vec![0; 64];
+/// blob.extend_from_slice(b"\x89PNG\r\n\x1A\n");
+///
+/// let matches = EmbeddedScanner::default().scan_bytes(&blob);
+/// assert_eq!(matches, vec![(64, FileFormat::PortableNetworkGraphics)]);
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EmbeddedScanner {
+    /// Detector whose budgets and readers are used.
+    detector: Detector,
+    /// Kinds of the reported file formats, all of them if empty.
+    kinds: Vec<Kind>,
+    /// Minimum number of bytes checked by a signature.
+    min_signature_size: usize,
+}
+
+impl EmbeddedScanner {
+    /// Returns a builder of embedded scanner, initialized with the default options.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::EmbeddedScanner;
+    ///
+    /// let scanner = EmbeddedScanner::builder().build();
+    /// assert_eq!(scanner, EmbeddedScanner::default());
+    ///```
+    #[inline]
+    pub fn builder() -> EmbeddedScannerBuilder {
+        EmbeddedScannerBuilder(Self::default())
+    }
+
+    /// Scans bytes for embedded file formats.
+    ///
+    /// See [`EmbeddedScanner`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{EmbeddedScanner, FileFormat};
+    ///
+    /// let matches = EmbeddedScanner::default().scan_bytes(b"\0\0\0\07z\xBC\xAF\x27\x1C");
+    /// assert_eq!(matches, vec![(4, FileFormat::SevenZip)]);
+    ///```
+    #[inline]
+    pub fn scan_bytes<B: AsRef<[u8]>>(&self, bytes: B) -> Vec<(u64, FileFormat)> {
+        self.scan(Cursor::new(bytes.as_ref())).unwrap_or_default()
+    }
+
+    /// Scans a file for embedded file formats.
+    ///
+    /// See [`EmbeddedScanner`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::EmbeddedScanner;
+    ///
+    /// for (offset, fmt) in EmbeddedScanner::default().scan_file("firmware.bin")? {
+    ///     println!("{:#010x}: {}", offset, fmt);
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn scan_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<(u64, FileFormat)>> {
+        self.scan(File::open(path)?)
+    }
+
+    /// Scans a reader for embedded file formats.
+    ///
+    /// The reader is read by chunks, hence the memory in use does not depend on its length.
+    ///
+    /// See [`EmbeddedScanner`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::EmbeddedScanner;
+    ///
+    /// let matches = EmbeddedScanner::default().scan_reader(std::io::empty())?;
+    /// assert!(matches.is_empty());
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn scan_reader<R: Read + Seek>(&self, reader: R) -> Result<Vec<(u64, FileFormat)>> {
+        self.scan(reader)
+    }
+
+    /// Scans a reader for embedded file formats, whatever the I/O primitives in use.
+    fn scan<R: Read + Seek>(&self, mut reader: R) -> Result<Vec<(u64, FileFormat)>> {
+        // Number of bytes following an offset which may be checked by a signature.
+        let extent = Signature::all()
+            .iter()
+            .flat_map(|signature| signature.parts)
+            .map(|(offset, value)| offset + value.len())
+            .max()
+            .unwrap_or_default();
+
+        let mut matches = Vec::new();
+        let mut buf = vec![0; CHUNK_SIZE + extent];
+        let mut len = 0;
+        let mut base = 0;
+        loop {
+            // Fills the buffer, the reader being sought back since a format reader may have moved.
+            reader.seek(SeekFrom::Start(base + len as u64))?;
+            let mut eof = false;
+            while len < buf.len() {
+                match reader.read(&mut buf[len..])? {
+                    0 => {
+                        eof = true;
+                        break;
+                    }
+                    nread => len += nread,
+                }
+            }
+
+            // Checks every offset followed by enough bytes, or every remaining one at the end.
+            let count = if eof {
+                len
+            } else {
+                core::cmp::min(CHUNK_SIZE, len)
+            };
+            for index in 0..count {
+                if let Some(fmt) = self.check(&mut reader, &buf[index..len], base + index as u64) {
+                    matches.push((base + index as u64, fmt));
+                }
+            }
+            if eof {
+                return Ok(matches);
+            }
+
+            // Discards the checked offsets.
+            buf.copy_within(count..len, 0);
+            len -= count;
+            base += count as u64;
+        }
+    }
+
+    /// Returns the file format embedded at an offset of a reader, if any.
+    fn check<R: Read + Seek>(&self, reader: R, bytes: &[u8], offset: u64) -> Option<FileFormat> {
+        let mut reader = Shifted::new(reader, offset);
+        DISPATCH
+            .candidates(bytes)
+            .filter(|signature| {
+                signature.size() >= self.min_signature_size && signature.matches(bytes)
+            })
+            .find_map(|signature| {
+                // Validates the structure with the format reader, if any.
+                let fmt = if self.detector.reader(signature.format).is_some() {
+                    let mut detection = Detection::new(signature.format);
+                    FileFormat::from_fmt_reader(
+                        signature.format,
+                        &mut reader,
+                        &self.detector,
+                        &mut detection,
+                    )
+                    .ok()?
+                } else {
+                    signature.format
+                };
+                Some(fmt)
+            })
+            .filter(|fmt| self.kinds.is_empty() || self.kinds.contains(&fmt.kind()))
+    }
+}
+
+impl Default for EmbeddedScanner {
+    /// Returns an embedded scanner using the [default](`Detector::default`) detector, which
+    /// reports every kind of file format and ignores the signatures checking less than 4 bytes.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            detector: Detector::default(),
+            kinds: Vec::new(),
+            min_signature_size: 4,
+        }
+    }
+}
+
+/// A builder of [`EmbeddedScanner`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{EmbeddedScanner, Kind};
+///
+/// let scanner = EmbeddedScanner::builder()
+///     .kinds([Kind::Archive, Kind::Image])
+///     .min_signature_size(6)
+///     .build();
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EmbeddedScannerBuilder(EmbeddedScanner);
+
+impl EmbeddedScannerBuilder {
+    /// Sets the detector whose budgets and readers are used (the [default](`Detector::default`)
+    /// one by default).
+    #[inline]
+    #[must_use]
+    pub fn detector(mut self, detector: Detector) -> Self {
+        self.0.detector = detector;
+        self
+    }
+
+    /// Sets the kinds of the reported file formats (all of them by default).
+    ///
+    /// An empty list reports every kind.
+    #[inline]
+    #[must_use]
+    pub fn kinds<I: IntoIterator<Item = Kind>>(mut self, kinds: I) -> Self {
+        self.0.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Sets the minimum number of bytes checked by a signature (4 by default).
+    ///
+    /// Short magic numbers, such as the 2 bytes of [gzip](`FileFormat::Gzip`), are likely to
+    /// occur by chance in large blobs, hence they are ignored unless this minimum is lowered.
+    #[inline]
+    #[must_use]
+    pub fn min_signature_size(mut self, size: usize) -> Self {
+        self.0.min_signature_size = size;
+        self
+    }
+
+    /// Builds the embedded scanner.
+    #[inline]
+    pub fn build(self) -> EmbeddedScanner {
+        self.0
+    }
+}
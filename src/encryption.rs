@@ -0,0 +1,200 @@
+//! Encryption of archives, read from their headers.
+
+use crate::{
+    io::{Read, Result, Seek, SeekFrom},
+    readers::{FindBytes, ReadData},
+    FileFormat,
+};
+
+/// Returns `true` if the headers of an archive, held by the first bytes or pointed to by them, are
+/// encrypted or declare encrypted entries.
+///
+/// Errors, such as the ones raised by a truncated stream, are treated as the absence of
+/// encryption.
+pub(crate) fn from_reader<R: Read + Seek>(fmt: FileFormat, reader: R, probe: &[u8]) -> bool {
+    match fmt {
+        FileFormat::RoshalArchive => match probe.get(6) {
+            Some(0) => from_rar4_headers(probe),
+            Some(1) => from_rar5_headers(probe),
+            _ => false,
+        },
+        FileFormat::SevenZip => from_7z_header(reader).unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the next header of a 7z is encoded with AES.
+fn from_7z_header<R: Read + Seek>(mut reader: R) -> Result<bool> {
+    // Identifier of the AES-256 + SHA-256 coder.
+    const AES_CODER_ID: &[u8] = b"\x06\xF1\x07\x01";
+
+    // Property ID of an encoded header.
+    const ENCODED_HEADER_ID: u8 = 0x17;
+
+    // Maximum size of an encoded header that can be processed by the reader.
+    const ENCODED_HEADER_LIMIT: u64 = 1024;
+
+    // Size of the signature header.
+    const SIGNATURE_HEADER_SIZE: u64 = 32;
+
+    // Reads the offset and the size of the next header.
+    reader.seek(SeekFrom::Start(12))?;
+    let offset = reader.read_u64_le()?;
+    let size = reader.read_u64_le()?;
+    if size == 0 || size > ENCODED_HEADER_LIMIT {
+        return Ok(false);
+    }
+
+    // Reads the next header.
+    reader.seek(SeekFrom::Start(
+        SIGNATURE_HEADER_SIZE.saturating_add(offset),
+    ))?;
+    let header = reader.read_bytes(size as usize)?;
+
+    // Checks whether the header is encoded by a folder using the AES coder.
+    Ok(header[0] == ENCODED_HEADER_ID && header.holds(AES_CODER_ID))
+}
+
+/// Returns `true` if the RAR 1.5 blocks held by the first bytes have encrypted headers or
+/// encrypted files.
+fn from_rar4_headers(probe: &[u8]) -> bool {
+    // Type of the archive header, along with the flag of its encrypted block headers.
+    const ARCHIVE_HEADER_TYPE: u8 = 0x73;
+    const ENCRYPTED_HEADERS_FLAG: u16 = 0x0080;
+
+    // Type of the end of archive header.
+    const END_OF_ARCHIVE_TYPE: u8 = 0x7B;
+
+    // Type of the file header, along with the flag of its encrypted file.
+    const FILE_HEADER_TYPE: u8 = 0x74;
+    const ENCRYPTED_FILE_FLAG: u16 = 0x0004;
+
+    // Flag indicating that the block is followed by data, whose size is stored after its size.
+    const LONG_BLOCK_FLAG: u16 = 0x8000;
+
+    // Iterates through the blocks following the signature.
+    let mut offset = 7;
+    while let Some(block) = probe.get(offset..).and_then(|block| block.get(..11)) {
+        // Reads the type, the flags and the size of the block.
+        let block_type = block[2];
+        let flags = u16::from_le_bytes([block[3], block[4]]);
+        let size = u16::from_le_bytes([block[5], block[6]]) as usize;
+
+        // Checks the type of the block.
+        match block_type {
+            ARCHIVE_HEADER_TYPE if flags & ENCRYPTED_HEADERS_FLAG != 0 => return true,
+            FILE_HEADER_TYPE if flags & ENCRYPTED_FILE_FLAG != 0 => return true,
+            END_OF_ARCHIVE_TYPE => return false,
+            _ => {}
+        }
+
+        // Skips to the next block.
+        let data_size = if flags & LONG_BLOCK_FLAG != 0 {
+            u32::from_le_bytes([block[7], block[8], block[9], block[10]])
+        } else {
+            0
+        };
+        if size < 7 {
+            return false;
+        }
+        offset = offset
+            .saturating_add(size)
+            .saturating_add(data_size as usize);
+    }
+    false
+}
+
+/// Returns `true` if the RAR 5.0 blocks held by the first bytes have encrypted headers or
+/// encrypted files.
+fn from_rar5_headers(probe: &[u8]) -> bool {
+    // Type of the archive encryption header.
+    const ENCRYPTION_HEADER_TYPE: u64 = 4;
+
+    // Type of the end of archive header.
+    const END_OF_ARCHIVE_TYPE: u64 = 5;
+
+    // Types of the file and service headers.
+    const FILE_HEADER_TYPE: u64 = 2;
+    const SERVICE_HEADER_TYPE: u64 = 3;
+
+    // Type of the file encryption record of the extra area.
+    const ENCRYPTION_RECORD_TYPE: u64 = 1;
+
+    // Iterates through the blocks following the signature.
+    let mut offset = 8;
+    while offset < probe.len() {
+        // Reads the size, the type and the flags of the header, following its CRC32.
+        let mut position = offset + 4;
+        let size = match read_vint(probe, &mut position) {
+            Some(size) => size as usize,
+            None => return false,
+        };
+        let end = position.saturating_add(size);
+        let (header_type, flags) = match (
+            read_vint(probe, &mut position),
+            read_vint(probe, &mut position),
+        ) {
+            (Some(header_type), Some(flags)) => (header_type, flags),
+            _ => return false,
+        };
+
+        // Reads the sizes of the extra area and of the data, if any.
+        let extra_area_size = if flags & 0x01 != 0 {
+            read_vint(probe, &mut position)
+        } else {
+            Some(0)
+        };
+        let data_size = if flags & 0x02 != 0 {
+            read_vint(probe, &mut position)
+        } else {
+            Some(0)
+        };
+        let (extra_area_size, data_size) = match (extra_area_size, data_size) {
+            (Some(extra_area_size), Some(data_size)) => (extra_area_size as usize, data_size),
+            _ => return false,
+        };
+
+        // Checks the type of the header.
+        match header_type {
+            ENCRYPTION_HEADER_TYPE => return true,
+            END_OF_ARCHIVE_TYPE => return false,
+            FILE_HEADER_TYPE | SERVICE_HEADER_TYPE if extra_area_size > 0 => {
+                // Iterates through the records of the extra area, ending the header.
+                let mut position = end.saturating_sub(extra_area_size);
+                while position < end {
+                    let record_size = match read_vint(probe, &mut position) {
+                        Some(record_size) if record_size > 0 => record_size as usize,
+                        _ => return false,
+                    };
+                    let record_end = position.saturating_add(record_size);
+                    if read_vint(probe, &mut position) == Some(ENCRYPTION_RECORD_TYPE) {
+                        return true;
+                    }
+                    position = record_end;
+                }
+            }
+            _ => {}
+        }
+
+        // Skips to the next block.
+        if size == 0 {
+            return false;
+        }
+        offset = end.saturating_add(usize::try_from(data_size).unwrap_or(usize::MAX));
+    }
+    false
+}
+
+/// Reads a RAR 5.0 variable-length integer, advancing the position past it.
+fn read_vint(bytes: &[u8], position: &mut usize) -> Option<u64> {
+    let mut value = 0;
+    for shift in (0..70).step_by(7) {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
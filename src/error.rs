@@ -0,0 +1,136 @@
+//! Errors surfaced by the strict detection.
+
+use crate::{io, FileFormat};
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+
+/// An error preventing a conclusive detection, surfaced by the strict functions such as
+/// [`FileFormat::try_from_reader`] instead of falling back to the generic reader.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{DetectionError, FileFormat};
+///
+/// let error = DetectionError::Truncated {
+///     format: FileFormat::Zip,
+/// };
+/// assert_eq!(error.to_string(), "truncated ZIP stream");
+///```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DetectionError {
+    /// The underlying reader failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// The stream ended before the format reader could conclude.
+    Truncated {
+        /// File format identified by the signature.
+        format: FileFormat,
+    },
+    /// The format reader encountered inconsistent data.
+    Malformed {
+        /// File format identified by the signature.
+        format: FileFormat,
+        /// Description of the inconsistency.
+        reason: String,
+    },
+    /// The format reader reached a limit of the [`Detector`](`crate::Detector`) before it could
+    /// conclude.
+    LimitExceeded {
+        /// File format identified by the signature.
+        format: FileFormat,
+        /// Name of the limit, as set with the [`DetectorBuilder`](`crate::DetectorBuilder`)
+        /// (e.g. `"zip_entry_limit"`).
+        limit: &'static str,
+    },
+}
+
+impl DetectionError {
+    /// Returns the file format identified by the signature, if the error was raised by its format
+    /// reader.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{DetectionError, FileFormat};
+    ///
+    /// let error = DetectionError::LimitExceeded {
+    ///     format: FileFormat::Mpeg4Part14,
+    ///     limit: "mp4_box_limit",
+    /// };
+    /// assert_eq!(error.format(), Some(FileFormat::Mpeg4Part14));
+    ///```
+    #[inline]
+    pub fn format(&self) -> Option<FileFormat> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(_) => None,
+            Self::Truncated { format }
+            | Self::Malformed { format, .. }
+            | Self::LimitExceeded { format, .. } => Some(*format),
+        }
+    }
+
+    /// Classifies an error raised by the format reader of the specified file format.
+    pub(crate) fn from_reader_error(format: FileFormat, error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => Self::Truncated { format },
+            io::ErrorKind::InvalidData | io::ErrorKind::InvalidInput => Self::Malformed {
+                format,
+                reason: error.to_string(),
+            },
+            #[cfg(feature = "std")]
+            _ => Self::Io(error),
+        }
+    }
+}
+
+impl Display for DetectionError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(error) => write!(formatter, "I/O error: {error}"),
+            Self::Truncated { format } => {
+                write!(formatter, "truncated {} stream", display_name(format))
+            }
+            Self::Malformed { format, reason } => {
+                write!(formatter, "malformed {}: {reason}", display_name(format))
+            }
+            Self::LimitExceeded { format, limit } => write!(
+                formatter,
+                "{} reader exceeded the `{limit}` limit",
+                display_name(format)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DetectionError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for DetectionError {
+    #[inline]
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Returns the short name of the file format if any, its name otherwise.
+#[inline]
+fn display_name(format: &FileFormat) -> &str {
+    format.short_name().unwrap_or_else(|| format.name())
+}
@@ -268,9 +268,18 @@ extension_mapping! {
     extension = "doc"
     format = MicrosoftWordDocument
 
+    extension = "docm"
+    format = OfficeOpenXmlMacroEnabledDocument
+
     extension = "docx"
     format = OfficeOpenXmlDocument
 
+    extension = "dotm"
+    format = OfficeOpenXmlMacroEnabledDocumentTemplate
+
+    extension = "dotx"
+    format = OfficeOpenXmlDocumentTemplate
+
     extension = "dpx"
     format = DigitalPictureExchange
 
@@ -321,10 +330,14 @@ extension_mapping! {
     format = ElectronicPublication
 
     extension = "exe"
+    format = CabinetSelfExtractingArchive
     format = LinearExecutable
     format = MsDosExecutable
     format = NewExecutable
     format = PortableExecutable
+    format = RoshalSelfExtractingArchive
+    format = SevenZipSelfExtractingArchive
+    format = ZipSelfExtractingArchive
 
     extension = "exr"
     format = Openexr
@@ -831,12 +844,30 @@ extension_mapping! {
     extension = "png"
     format = PortableNetworkGraphics
 
+    extension = "potm"
+    format = OfficeOpenXmlMacroEnabledPresentationTemplate
+
+    extension = "potx"
+    format = OfficeOpenXmlPresentationTemplate
+
+    extension = "ppam"
+    format = OfficeOpenXmlMacroEnabledPresentationAddIn
+
     extension = "ppm"
     format = PortablePixmap
 
+    extension = "ppsm"
+    format = OfficeOpenXmlMacroEnabledSlideshow
+
+    extension = "ppsx"
+    format = OfficeOpenXmlSlideshow
+
     extension = "ppt"
     format = MicrosoftPowerpointPresentation
 
+    extension = "pptm"
+    format = OfficeOpenXmlMacroEnabledPresentation
+
     extension = "pptx"
     format = OfficeOpenXmlPresentation
 
@@ -1229,6 +1260,9 @@ extension_mapping! {
     extension = "xex"
     format = Xbox360Executable
 
+    extension = "xlam"
+    format = OfficeOpenXmlMacroEnabledSpreadsheetAddIn
+
     extension = "xlf"
     format = XmlLocalizationInterchangeFileFormat
 
@@ -1238,9 +1272,21 @@ extension_mapping! {
     extension = "xls"
     format = MicrosoftExcelSpreadsheet
 
+    extension = "xlsb"
+    format = OfficeOpenXmlBinarySpreadsheet
+
+    extension = "xlsm"
+    format = OfficeOpenXmlMacroEnabledSpreadsheet
+
     extension = "xlsx"
     format = OfficeOpenXmlSpreadsheet
 
+    extension = "xltm"
+    format = OfficeOpenXmlMacroEnabledSpreadsheetTemplate
+
+    extension = "xltx"
+    format = OfficeOpenXmlSpreadsheetTemplate
+
     extension = "xm"
     format = Fasttracker2ExtendedModule
 
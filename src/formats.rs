@@ -450,6 +450,12 @@ formats! {
     extension = "cab"
     kind = Archive
 
+    format = CabinetSelfExtractingArchive
+    name = "Cabinet Self-Extracting Archive"
+    media_type = "application/vnd.microsoft.portable-executable"
+    extension = "exe"
+    kind = Executable
+
     format = CanonRaw
     name = "Canon Raw"
     short_name = "CRW"
@@ -1751,6 +1757,13 @@ formats! {
     extension = "xci"
     kind = Rom
 
+    format = OfficeOpenXmlBinarySpreadsheet
+    name = "Office Open XML Binary Spreadsheet"
+    short_name = "XLSB"
+    media_type = "application/vnd.ms-excel.sheet.binary.macroenabled.12"
+    extension = "xlsb"
+    kind = Spreadsheet
+
     format = OfficeOpenXmlDocument
     name = "Office Open XML Document"
     short_name = "DOCX"
@@ -1758,6 +1771,13 @@ formats! {
     extension = "docx"
     kind = Document
 
+    format = OfficeOpenXmlDocumentTemplate
+    name = "Office Open XML Document Template"
+    short_name = "DOTX"
+    media_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.template"
+    extension = "dotx"
+    kind = Document
+
     format = OfficeOpenXmlDrawing
     name = "Office Open XML Drawing"
     short_name = "VSDX"
@@ -1765,6 +1785,69 @@ formats! {
     extension = "vsdx"
     kind = Diagram
 
+    format = OfficeOpenXmlMacroEnabledDocument
+    name = "Office Open XML Macro-Enabled Document"
+    short_name = "DOCM"
+    media_type = "application/vnd.ms-word.document.macroenabled.12"
+    extension = "docm"
+    kind = Document
+
+    format = OfficeOpenXmlMacroEnabledDocumentTemplate
+    name = "Office Open XML Macro-Enabled Document Template"
+    short_name = "DOTM"
+    media_type = "application/vnd.ms-word.template.macroenabled.12"
+    extension = "dotm"
+    kind = Document
+
+    format = OfficeOpenXmlMacroEnabledPresentation
+    name = "Office Open XML Macro-Enabled Presentation"
+    short_name = "PPTM"
+    media_type = "application/vnd.ms-powerpoint.presentation.macroenabled.12"
+    extension = "pptm"
+    kind = Presentation
+
+    format = OfficeOpenXmlMacroEnabledPresentationAddIn
+    name = "Office Open XML Macro-Enabled Presentation Add-In"
+    short_name = "PPAM"
+    media_type = "application/vnd.ms-powerpoint.addin.macroenabled.12"
+    extension = "ppam"
+    kind = Presentation
+
+    format = OfficeOpenXmlMacroEnabledPresentationTemplate
+    name = "Office Open XML Macro-Enabled Presentation Template"
+    short_name = "POTM"
+    media_type = "application/vnd.ms-powerpoint.template.macroenabled.12"
+    extension = "potm"
+    kind = Presentation
+
+    format = OfficeOpenXmlMacroEnabledSlideshow
+    name = "Office Open XML Macro-Enabled Slideshow"
+    short_name = "PPSM"
+    media_type = "application/vnd.ms-powerpoint.slideshow.macroenabled.12"
+    extension = "ppsm"
+    kind = Presentation
+
+    format = OfficeOpenXmlMacroEnabledSpreadsheet
+    name = "Office Open XML Macro-Enabled Spreadsheet"
+    short_name = "XLSM"
+    media_type = "application/vnd.ms-excel.sheet.macroenabled.12"
+    extension = "xlsm"
+    kind = Spreadsheet
+
+    format = OfficeOpenXmlMacroEnabledSpreadsheetAddIn
+    name = "Office Open XML Macro-Enabled Spreadsheet Add-In"
+    short_name = "XLAM"
+    media_type = "application/vnd.ms-excel.addin.macroenabled.12"
+    extension = "xlam"
+    kind = Spreadsheet
+
+    format = OfficeOpenXmlMacroEnabledSpreadsheetTemplate
+    name = "Office Open XML Macro-Enabled Spreadsheet Template"
+    short_name = "XLTM"
+    media_type = "application/vnd.ms-excel.template.macroenabled.12"
+    extension = "xltm"
+    kind = Spreadsheet
+
     format = OfficeOpenXmlPresentation
     name = "Office Open XML Presentation"
     short_name = "PPTX"
@@ -1772,6 +1855,20 @@ formats! {
     extension = "pptx"
     kind = Presentation
 
+    format = OfficeOpenXmlPresentationTemplate
+    name = "Office Open XML Presentation Template"
+    short_name = "POTX"
+    media_type = "application/vnd.openxmlformats-officedocument.presentationml.template"
+    extension = "potx"
+    kind = Presentation
+
+    format = OfficeOpenXmlSlideshow
+    name = "Office Open XML Slideshow"
+    short_name = "PPSX"
+    media_type = "application/vnd.openxmlformats-officedocument.presentationml.slideshow"
+    extension = "ppsx"
+    kind = Presentation
+
     format = OfficeOpenXmlSpreadsheet
     name = "Office Open XML Spreadsheet"
     short_name = "XLSX"
@@ -1779,6 +1876,13 @@ formats! {
     extension = "xlsx"
     kind = Spreadsheet
 
+    format = OfficeOpenXmlSpreadsheetTemplate
+    name = "Office Open XML Spreadsheet Template"
+    short_name = "XLTX"
+    media_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.template"
+    extension = "xltx"
+    kind = Spreadsheet
+
     format = OggFlac
     name = "Ogg FLAC"
     short_name = "OGA"
@@ -2253,6 +2357,12 @@ formats! {
     extension = "rar"
     kind = Archive
 
+    format = RoshalSelfExtractingArchive
+    name = "Roshal Self-Extracting Archive"
+    media_type = "application/vnd.microsoft.portable-executable"
+    extension = "exe"
+    kind = Executable
+
     format = RubyScript
     name = "Ruby Script"
     media_type = "text/x-ruby"
@@ -2301,6 +2411,12 @@ formats! {
     extension = "7z"
     kind = Archive
 
+    format = SevenZipSelfExtractingArchive
+    name = "7-Zip Self-Extracting Archive"
+    media_type = "application/vnd.microsoft.portable-executable"
+    extension = "exe"
+    kind = Executable
+
     format = Shapefile
     name = "Shapefile"
     short_name = "SHP"
@@ -3038,6 +3154,12 @@ formats! {
     extension = "zip"
     kind = Archive
 
+    format = ZipSelfExtractingArchive
+    name = "ZIP Self-Extracting Archive"
+    media_type = "application/vnd.microsoft.portable-executable"
+    extension = "exe"
+    kind = Executable
+
     format = Zoo
     name = "zoo"
     media_type = "application/x-zoo"
@@ -0,0 +1,157 @@
+//! Detection refined by the extension of a filename.
+
+use crate::{
+    verify::{base_format, extension_formats},
+    Detection, FileFormat,
+};
+use std::{
+    fs::File,
+    io::{Read, Result, Seek},
+    path::Path,
+};
+
+impl FileFormat {
+    /// Determines file format from a file, using its extension to refine a generic result.
+    ///
+    /// See [`FileFormat::detect_reader_with_hint`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let fmt = FileFormat::from_file_with_hint("fixtures/document/sample.docx")?;
+    /// assert_eq!(fmt, FileFormat::OfficeOpenXmlDocument);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn from_file_with_hint<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::detect_file_with_hint(path).map(|detection| detection.format)
+    }
+
+    /// Determines file format from a reader, using the extension of its filename to refine a
+    /// generic result.
+    ///
+    /// See [`FileFormat::detect_reader_with_hint`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    /// use std::io::Cursor;
+    ///
+    /// let fmt = FileFormat::from_reader_with_hint(Cursor::new(b"PK\x05\x06"), "sample.docx")?;
+    /// assert_eq!(fmt, FileFormat::OfficeOpenXmlDocument);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn from_reader_with_hint<R: Read + Seek>(reader: R, filename: &str) -> Result<Self> {
+        Self::detect_reader_with_hint(reader, filename).map(|detection| detection.format)
+    }
+
+    /// Determines file format from a file, using its extension to refine a generic result, along
+    /// with the provenance of the result.
+    ///
+    /// See [`FileFormat::detect_reader_with_hint`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_file_with_hint("fixtures/document/sample.docx")?;
+    /// assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn detect_file_with_hint<P: AsRef<Path>>(path: P) -> Result<Detection> {
+        let path = path.as_ref();
+        let mut detection = Self::detect_reader(File::open(path)?)?;
+        detection.refine(path);
+        Ok(detection)
+    }
+
+    /// Determines file format from a reader, using the extension of its filename to refine a
+    /// generic result, along with the provenance of the result.
+    ///
+    /// The content always comes first. The extension is only used when the content ends in a
+    /// generic result, and only if it maps through [`FileFormat::from_extension`] to a more
+    /// specific file format of the same family:
+    ///
+    /// - a file format based on the container identified by the content, such as
+    ///   [DOCX](`FileFormat::OfficeOpenXmlDocument`) for [ZIP](`FileFormat::Zip`) or
+    ///   [SVG](`FileFormat::ScalableVectorGraphics`) for
+    ///   [XML](`FileFormat::ExtensibleMarkupLanguage`);
+    /// - a text-based file format for [Plain Text (TXT)](`FileFormat::PlainText`);
+    /// - a file format based on the matched signature for
+    ///   [Arbitrary Binary Data (BIN)](`FileFormat::ArbitraryBinaryData`), when the format reader
+    ///   failed.
+    ///
+    /// The extension which refined the file format is recorded in [`Detection::hint`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    /// use std::io::Cursor;
+    ///
+    /// let detection = FileFormat::detect_reader_with_hint(Cursor::new(b"PK\x05\x06"), "a.xlsx")?;
+    /// assert_eq!(detection.format, FileFormat::OfficeOpenXmlSpreadsheet);
+    /// assert_eq!(detection.hint.as_deref(), Some("xlsx"));
+    ///
+    /// let detection = FileFormat::detect_reader_with_hint(Cursor::new(b"\x1F\x8B"), "a.xlsx")?;
+    /// assert_eq!(detection.format, FileFormat::Gzip);
+    /// assert_eq!(detection.hint, None);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn detect_reader_with_hint<R: Read + Seek>(reader: R, filename: &str) -> Result<Detection> {
+        let mut detection = Self::detect_reader(reader)?;
+        detection.refine(Path::new(filename));
+        Ok(detection)
+    }
+}
+
+impl Detection {
+    /// Refines a generic file format with the extension of the path, if it maps to a more
+    /// specific file format of the same family.
+    fn refine(&mut self, path: &Path) {
+        let extension = match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) => extension,
+            None => return,
+        };
+        let generic = self.format;
+        let signature = self.signature.map(|signature| signature.format);
+        let specific = extension_formats(extension).iter().copied().find(|&fmt| {
+            fmt != generic
+                && match generic {
+                    FileFormat::ArbitraryBinaryData => signature.map_or(false, |signature| {
+                        fmt == signature || base_format(fmt) == Some(signature)
+                    }),
+                    FileFormat::PlainText => is_text(fmt),
+                    _ => base_format(fmt) == Some(generic),
+                }
+        });
+        if let Some(fmt) = specific {
+            self.format = fmt;
+            self.hint = Some(extension.to_string());
+        }
+    }
+}
+
+/// Returns `true` if the file format is text-based, according to its media type.
+fn is_text(fmt: FileFormat) -> bool {
+    let media_type = fmt.media_type();
+    media_type.starts_with("text/")
+        || ["+json", "+xml", "/json", "/xml"]
+            .iter()
+            .any(|suffix| media_type.ends_with(suffix))
+}
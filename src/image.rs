@@ -0,0 +1,748 @@
+//! Dimensions and pixel formats of images, read from their headers.
+
+use crate::{
+    io::{Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom},
+    readers::ReadData,
+    Detector, FileFormat,
+};
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::{fs::File, path::Path};
+
+/// Dimensions and pixel format of an image, read from its header without decoding it.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{ColorModel, FileFormat};
+///
+/// let info = FileFormat::image_info_from_bytes(b"qoif\0\0\x01\0\0\0\0\x80\x04\0").unwrap();
+/// assert_eq!(info.format, FileFormat::QuiteOkImage);
+/// assert_eq!((info.width, info.height), (256, 128));
+/// assert_eq!(info.bit_depth, Some(8));
+/// assert_eq!(info.color_model, Some(ColorModel::Rgba));
+///```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct ImageInfo {
+    /// File format of the image.
+    pub format: FileFormat,
+    /// Width, in pixels.
+    pub width: u32,
+    /// Height, in pixels.
+    pub height: u32,
+    /// Number of bits per sample, i.e. per channel or per palette index, if known.
+    pub bit_depth: Option<u8>,
+    /// Color model of the pixels, if known.
+    pub color_model: Option<ColorModel>,
+}
+
+impl ImageInfo {
+    /// Creates the information of an image of the specified file format, whose header is not read
+    /// yet.
+    #[inline]
+    fn new(format: FileFormat) -> Self {
+        Self {
+            format,
+            width: 0,
+            height: 0,
+            bit_depth: None,
+            color_model: None,
+        }
+    }
+}
+
+/// Color model of the pixels of an image.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "extended-enums",
+    derive(strum::EnumIter, strum::Display, strum::AsRefStr, strum::FromRepr)
+)]
+pub enum ColorModel {
+    /// Cyan, magenta, yellow and key (black) channels.
+    Cmyk,
+    /// Single luminance channel.
+    Grayscale,
+    /// Luminance and alpha channels.
+    GrayscaleAlpha,
+    /// Indices into a palette of colors.
+    Indexed,
+    /// Red, green and blue channels.
+    Rgb,
+    /// Red, green, blue and alpha channels.
+    Rgba,
+    /// Luma and chroma channels.
+    YCbCr,
+}
+
+impl FileFormat {
+    /// Determines the dimensions and pixel format of an image from bytes.
+    ///
+    /// See [`FileFormat::image_info_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let info = FileFormat::image_info_from_bytes(b"GIF89a\x40\x01\xF0\x00\xF7\0\0").unwrap();
+    /// assert_eq!((info.width, info.height), (320, 240));
+    /// assert_eq!(info.bit_depth, Some(8));
+    ///```
+    #[inline]
+    pub fn image_info_from_bytes<B: AsRef<[u8]>>(bytes: B) -> Option<ImageInfo> {
+        Self::image_info(Cursor::new(bytes.as_ref())).ok().flatten()
+    }
+
+    /// Determines the dimensions and pixel format of an image from a file.
+    ///
+    /// See [`FileFormat::image_info_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// if let Some(info) = FileFormat::image_info_from_file("upload.png")? {
+    ///     assert!(info.width <= 8192 && info.height <= 8192, "oversized upload");
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn image_info_from_file<P: AsRef<Path>>(path: P) -> Result<Option<ImageInfo>> {
+        Self::image_info(File::open(path)?)
+    }
+
+    /// Determines the dimensions and pixel format of an image from a reader.
+    ///
+    /// The file format is determined first, then only the header of the image is read. Returns
+    /// `None` if the file format is none of
+    /// [APNG](`FileFormat::AnimatedPortableNetworkGraphics`),
+    /// [AVIF](`FileFormat::Av1ImageFileFormat`), [BMP](`FileFormat::WindowsBitmap`),
+    /// [GIF](`FileFormat::GraphicsInterchangeFormat`),
+    /// [JPEG](`FileFormat::JointPhotographicExpertsGroup`), [JPEG XL](`FileFormat::JpegXl`),
+    /// [OpenEXR](`FileFormat::Openexr`), [PNG](`FileFormat::PortableNetworkGraphics`),
+    /// [QOI](`FileFormat::QuiteOkImage`), [TIFF](`FileFormat::TagImageFileFormat`) and
+    /// [WebP](`FileFormat::Webp`), and an error if the header is invalid or truncated.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let info = FileFormat::image_info_from_reader(std::io::Cursor::new(b"\x1F\x8B"))?;
+    /// assert_eq!(info, None);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn image_info_from_reader<R: Read + Seek>(reader: R) -> Result<Option<ImageInfo>> {
+        Self::image_info(reader)
+    }
+
+    /// Determines the dimensions and pixel format of an image from a reader, whatever the I/O
+    /// primitives in use.
+    fn image_info<R: Read + Seek>(mut reader: R) -> Result<Option<ImageInfo>> {
+        let mut info = ImageInfo::new(Detector::default().detect(&mut reader)?.format);
+        match info.format {
+            Self::AnimatedPortableNetworkGraphics | Self::PortableNetworkGraphics => {
+                read_png_header(reader, &mut info)?
+            }
+            Self::Av1ImageFileFormat => read_avif_header(reader, &mut info)?,
+            Self::GraphicsInterchangeFormat => read_gif_header(reader, &mut info)?,
+            Self::JointPhotographicExpertsGroup => read_jpeg_header(reader, &mut info)?,
+            Self::JpegXl => read_jxl_header(reader, &mut info)?,
+            Self::Openexr => read_exr_header(reader, &mut info)?,
+            Self::QuiteOkImage => read_qoi_header(reader, &mut info)?,
+            Self::TagImageFileFormat => read_tiff_header(reader, &mut info)?,
+            Self::Webp => read_webp_header(reader, &mut info)?,
+            Self::WindowsBitmap => read_bmp_header(reader, &mut info)?,
+            _ => return Ok(None),
+        }
+        Ok(Some(info))
+    }
+}
+
+/// Reads the header of an AVIF, from the properties of its items.
+fn read_avif_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Locates the item properties container box, in the item properties box of the meta box.
+    let len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+    let (start, end) = find_box(&mut reader, len, b"meta")?;
+    reader.seek(SeekFrom::Start(start + 4))?;
+    let (start, end) = find_box(&mut reader, end, b"iprp")?;
+    reader.seek(SeekFrom::Start(start))?;
+    let (ipco_start, ipco_end) = find_box(&mut reader, end, b"ipco")?;
+
+    // Reads the dimensions from the image spatial extents property.
+    reader.seek(SeekFrom::Start(ipco_start))?;
+    let (start, _) = find_box(&mut reader, ipco_end, b"ispe")?;
+    reader.seek(SeekFrom::Start(start + 4))?;
+    info.width = reader.read_u32_be()?;
+    info.height = reader.read_u32_be()?;
+
+    // Reads the bit depth and the color model from the AV1 codec configuration, if any.
+    reader.seek(SeekFrom::Start(ipco_start))?;
+    if let Ok((start, _)) = find_box(&mut reader, ipco_end, b"av1C") {
+        reader.seek(SeekFrom::Start(start + 2))?;
+        let flags = reader.read_u8()?;
+        info.bit_depth = Some(match (flags & 0x40 != 0, flags & 0x20 != 0) {
+            (true, true) => 12,
+            (true, false) => 10,
+            _ => 8,
+        });
+        info.color_model = Some(if flags & 0x10 != 0 {
+            ColorModel::Grayscale
+        } else {
+            ColorModel::YCbCr
+        });
+    }
+    Ok(())
+}
+
+/// Reads the header of a BMP.
+fn read_bmp_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Size of the BITMAPCOREHEADER.
+    const CORE_HEADER_SIZE: u32 = 12;
+
+    // Minimum size of the headers holding an alpha mask.
+    const ALPHA_HEADER_SIZE: u32 = 56;
+
+    // Reads the dimensions and the number of bits per pixel.
+    reader.seek(SeekFrom::Start(14))?;
+    let header_size = reader.read_u32_le()?;
+    let bits_per_pixel = if header_size == CORE_HEADER_SIZE {
+        info.width = reader.read_u16_le()? as u32;
+        info.height = reader.read_u16_le()? as u32;
+        reader.seek(SeekFrom::Current(2))?;
+        reader.read_u16_le()?
+    } else {
+        info.width = (reader.read_u32_le()? as i32).unsigned_abs();
+        info.height = (reader.read_u32_le()? as i32).unsigned_abs();
+        reader.seek(SeekFrom::Current(2))?;
+        reader.read_u16_le()?
+    };
+
+    // Checks whether the pixels of 32 bits hold an alpha channel.
+    let alpha = bits_per_pixel == 32 && header_size >= ALPHA_HEADER_SIZE && {
+        reader.seek(SeekFrom::Start(66))?;
+        reader.read_u32_le()? != 0
+    };
+
+    // Determines the bit depth and the color model.
+    let (bit_depth, color_model) = match bits_per_pixel {
+        1 | 2 | 4 | 8 => (bits_per_pixel as u8, ColorModel::Indexed),
+        16 => (5, ColorModel::Rgb),
+        24 | 32 if !alpha => (8, ColorModel::Rgb),
+        32 => (8, ColorModel::Rgba),
+        _ => return Ok(()),
+    };
+    info.bit_depth = Some(bit_depth);
+    info.color_model = Some(color_model);
+    Ok(())
+}
+
+/// Reads the header of an OpenEXR, from its data window and channel list attributes.
+fn read_exr_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Maximum number of attributes read.
+    const ATTRIBUTE_LIMIT: usize = 256;
+
+    // Maximum size of a channel list that can be processed by the reader.
+    const CHANNELS_LIMIT: u32 = 65_536;
+
+    // Iterates through the attributes of the header.
+    reader.seek(SeekFrom::Start(8))?;
+    let mut data_window = None;
+    for _ in 0..ATTRIBUTE_LIMIT {
+        // Reads the name, the type and the size of the attribute.
+        let name = read_null_terminated(&mut reader)?;
+        if name.is_empty() {
+            break;
+        }
+        read_null_terminated(&mut reader)?;
+        let size = reader.read_u32_le()?;
+
+        // Checks the name of the attribute.
+        match name.as_slice() {
+            b"dataWindow" if size == 16 => {
+                let x_min = reader.read_u32_le()? as i32 as i64;
+                let y_min = reader.read_u32_le()? as i32 as i64;
+                let x_max = reader.read_u32_le()? as i32 as i64;
+                let y_max = reader.read_u32_le()? as i32 as i64;
+                data_window = Some((x_max - x_min + 1, y_max - y_min + 1));
+            }
+            b"channels" if size <= CHANNELS_LIMIT => {
+                let channels = reader.read_bytes(size as usize)?;
+                read_exr_channels(&channels, info);
+            }
+            _ => {
+                reader.seek(SeekFrom::Current(size as i64))?;
+            }
+        }
+    }
+
+    // Checks the dimensions.
+    match data_window {
+        Some((width, height)) if width > 0 && height > 0 => {
+            info.width = u32::try_from(width).unwrap_or(u32::MAX);
+            info.height = u32::try_from(height).unwrap_or(u32::MAX);
+            Ok(())
+        }
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            "missing OpenEXR data window",
+        )),
+    }
+}
+
+/// Reads the bit depth and the color model from an OpenEXR channel list.
+fn read_exr_channels(mut channels: &[u8], info: &mut ImageInfo) {
+    // Size of the fields following the name of a channel.
+    const CHANNEL_FIELDS_SIZE: usize = 16;
+
+    // Collects the names of the channels.
+    let mut names = Vec::new();
+    while let Some(len) = channels.iter().position(|&byte| byte == 0) {
+        if len == 0 || channels.len() < len + 1 + CHANNEL_FIELDS_SIZE {
+            break;
+        }
+        let name = &channels[..len];
+        let pixel_type = channels[len + 1];
+        if names.is_empty() {
+            info.bit_depth = match pixel_type {
+                0 | 2 => Some(32),
+                1 => Some(16),
+                _ => None,
+            };
+        }
+        names.push(name);
+        channels = &channels[len + 1 + CHANNEL_FIELDS_SIZE..];
+    }
+
+    // Determines the color model from the names of the channels.
+    let has = |name: &[u8]| names.contains(&name);
+    info.color_model = if has(b"R") && has(b"G") && has(b"B") {
+        Some(if has(b"A") {
+            ColorModel::Rgba
+        } else {
+            ColorModel::Rgb
+        })
+    } else if has(b"Y") && has(b"RY") && has(b"BY") {
+        Some(ColorModel::YCbCr)
+    } else if has(b"Y") {
+        Some(if has(b"A") {
+            ColorModel::GrayscaleAlpha
+        } else {
+            ColorModel::Grayscale
+        })
+    } else {
+        None
+    };
+}
+
+/// Reads the header of a GIF, from its logical screen descriptor.
+fn read_gif_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    reader.seek(SeekFrom::Start(6))?;
+    info.width = reader.read_u16_le()? as u32;
+    info.height = reader.read_u16_le()? as u32;
+
+    // Reads the size of the global color table, or the color resolution if there is none.
+    let flags = reader.read_u8()?;
+    info.bit_depth = Some(if flags & 0x80 != 0 {
+        (flags & 0x07) + 1
+    } else {
+        (flags >> 4 & 0x07) + 1
+    });
+    info.color_model = Some(ColorModel::Indexed);
+    Ok(())
+}
+
+/// Reads the header of a JPEG, from its first start of frame segment.
+fn read_jpeg_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Iterates through the segments.
+    reader.seek(SeekFrom::Start(2))?;
+    loop {
+        // Reads the marker, skipping the fill bytes.
+        if reader.read_u8()? != 0xFF {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid JPEG marker"));
+        }
+        let mut marker = reader.read_u8()?;
+        while marker == 0xFF {
+            marker = reader.read_u8()?;
+        }
+
+        // Checks the marker.
+        match marker {
+            0x01 | 0xD0..=0xD7 => {}
+            0xC0..=0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => {
+                reader.seek(SeekFrom::Current(2))?;
+                info.bit_depth = Some(reader.read_u8()?);
+                info.height = reader.read_u16_be()? as u32;
+                info.width = reader.read_u16_be()? as u32;
+                info.color_model = match reader.read_u8()? {
+                    1 => Some(ColorModel::Grayscale),
+                    3 => Some(ColorModel::YCbCr),
+                    4 => Some(ColorModel::Cmyk),
+                    _ => None,
+                };
+                return Ok(());
+            }
+            0xD9 | 0xDA => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "missing JPEG start of frame",
+                ))
+            }
+            _ => {
+                // Seeks to the next segment.
+                let len = reader.read_u16_be()?;
+                if len < 2 {
+                    return Err(Error::new(ErrorKind::InvalidData, "invalid JPEG segment"));
+                }
+                reader.seek(SeekFrom::Current(len as i64 - 2))?;
+            }
+        }
+    }
+}
+
+/// Reads the header of a JPEG XL, from the size header and the image metadata of its codestream.
+fn read_jxl_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Signature of a JPEG XL codestream.
+    const CODESTREAM_SIGNATURE: &[u8] = b"\xFF\x0A";
+
+    // Distribution of the dimensions.
+    const DIMENSION: [(u32, u32); 4] = [(1, 9), (1, 13), (1, 18), (1, 30)];
+
+    // Ratios between the width and the height.
+    const RATIOS: [(u64, u64); 7] = [(1, 1), (12, 10), (4, 3), (3, 2), (16, 9), (5, 4), (2, 1)];
+
+    // Locates the codestream, which may be stored in a container.
+    reader.seek(SeekFrom::Start(0))?;
+    if reader.read_bytes(2)? != CODESTREAM_SIGNATURE {
+        let len = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+        let start = match find_box(&mut reader, len, b"jxlc") {
+            Ok((start, _)) => start,
+            Err(_) => {
+                reader.seek(SeekFrom::Start(0))?;
+                find_box(&mut reader, len, b"jxlp")?.0 + 4
+            }
+        };
+        reader.seek(SeekFrom::Start(start))?;
+        if reader.read_bytes(2)? != CODESTREAM_SIGNATURE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "invalid JPEG XL codestream",
+            ));
+        }
+    }
+
+    // Reads the first bytes of the codestream headers.
+    let mut buf = [0; 32];
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..])? {
+            0 => break,
+            nread => len += nread,
+        }
+    }
+    let mut bits = BitReader {
+        data: &buf[..len],
+        position: 0,
+    };
+
+    // Reads the size header.
+    let small = bits.read(1)? == 1;
+    let height = if small {
+        (bits.read(5)? + 1) * 8
+    } else {
+        bits.read_u32(DIMENSION)?
+    };
+    let ratio = bits.read(3)? as usize;
+    info.height = height;
+    info.width = match ratio {
+        0 if small => (bits.read(5)? + 1) * 8,
+        0 => bits.read_u32(DIMENSION)?,
+        _ => {
+            let (numerator, denominator) = RATIOS[ratio - 1];
+            u32::try_from(height as u64 * numerator / denominator).unwrap_or(u32::MAX)
+        }
+    };
+
+    // Reads the bit depth from the image metadata, unless it is preceded by extra fields.
+    if bits.read(1)? == 1 {
+        info.bit_depth = Some(8);
+        info.color_model = Some(ColorModel::Rgb);
+    } else if bits.read(1)? == 0 {
+        let bit_depth = if bits.read(1)? == 0 {
+            bits.read_u32([(8, 0), (10, 0), (12, 0), (1, 6)])?
+        } else {
+            bits.read_u32([(32, 0), (16, 0), (24, 0), (1, 6)])?
+        };
+        info.bit_depth = u8::try_from(bit_depth).ok();
+    }
+    Ok(())
+}
+
+/// Reads the header of a PNG, from its image header chunk.
+fn read_png_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    reader.seek(SeekFrom::Start(12))?;
+    if reader.read_bytes(4)? != b"IHDR" {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "missing PNG image header",
+        ));
+    }
+    info.width = reader.read_u32_be()?;
+    info.height = reader.read_u32_be()?;
+    info.bit_depth = Some(reader.read_u8()?);
+    info.color_model = match reader.read_u8()? {
+        0 => Some(ColorModel::Grayscale),
+        2 => Some(ColorModel::Rgb),
+        3 => Some(ColorModel::Indexed),
+        4 => Some(ColorModel::GrayscaleAlpha),
+        6 => Some(ColorModel::Rgba),
+        _ => None,
+    };
+    Ok(())
+}
+
+/// Reads the header of a QOI.
+fn read_qoi_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    reader.seek(SeekFrom::Start(4))?;
+    info.width = reader.read_u32_be()?;
+    info.height = reader.read_u32_be()?;
+    info.bit_depth = Some(8);
+    info.color_model = match reader.read_u8()? {
+        3 => Some(ColorModel::Rgb),
+        4 => Some(ColorModel::Rgba),
+        _ => None,
+    };
+    Ok(())
+}
+
+/// Reads the header of a TIFF, from the entries of its first image file directory.
+fn read_tiff_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Maximum number of directory entries read.
+    const ENTRY_LIMIT: u64 = 4096;
+
+    // Reads the byte order and the variant.
+    reader.seek(SeekFrom::Start(0))?;
+    let header = reader.read_bytes(4)?;
+    let big_endian = header.starts_with(b"MM");
+    let big_tiff = header[if big_endian { 3 } else { 2 }] == 43;
+
+    // Reads the offset of the first directory.
+    let (count_size, entry_size, field_size) = if big_tiff { (8, 20, 8) } else { (2, 12, 4) };
+    if big_tiff {
+        reader.seek(SeekFrom::Current(4))?;
+    }
+    let offset = read_uint(&mut reader, field_size, big_endian)?;
+
+    // Iterates through the directory entries.
+    reader.seek(SeekFrom::Start(offset))?;
+    let count = read_uint(&mut reader, count_size, big_endian)?;
+    let mut width = None;
+    let mut height = None;
+    let mut bits_per_sample = 1;
+    let mut photometric_interpretation = None;
+    let mut samples_per_pixel = 1;
+    for index in 0..core::cmp::min(count, ENTRY_LIMIT) {
+        reader.seek(SeekFrom::Start(
+            offset + count_size as u64 + index * entry_size,
+        ))?;
+        let tag = read_uint(&mut reader, 2, big_endian)?;
+        let value_size = match read_uint(&mut reader, 2, big_endian)? {
+            3 => 2,
+            4 => 4,
+            16 => 8,
+            _ => continue,
+        };
+        let number = read_uint(&mut reader, field_size, big_endian)?;
+
+        // Reads the first value, stored at an offset if the values do not fit in the field.
+        if number.saturating_mul(value_size as u64) > field_size as u64 {
+            let offset = read_uint(&mut reader, field_size, big_endian)?;
+            reader.seek(SeekFrom::Start(offset))?;
+        }
+        let value = read_uint(&mut reader, value_size, big_endian)?;
+
+        // Checks the tag.
+        match tag {
+            256 => width = Some(value),
+            257 => height = Some(value),
+            258 => bits_per_sample = value,
+            262 => photometric_interpretation = Some(value),
+            277 => samples_per_pixel = value,
+            _ => {}
+        }
+    }
+
+    // Checks the dimensions.
+    match (width, height) {
+        (Some(width), Some(height)) => {
+            info.width = u32::try_from(width).unwrap_or(u32::MAX);
+            info.height = u32::try_from(height).unwrap_or(u32::MAX);
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "missing TIFF dimensions",
+            ))
+        }
+    }
+
+    // Determines the bit depth and the color model.
+    info.bit_depth = u8::try_from(bits_per_sample).ok();
+    info.color_model = match photometric_interpretation {
+        Some(0 | 1) if samples_per_pixel >= 2 => Some(ColorModel::GrayscaleAlpha),
+        Some(0 | 1) => Some(ColorModel::Grayscale),
+        Some(2) if samples_per_pixel >= 4 => Some(ColorModel::Rgba),
+        Some(2) => Some(ColorModel::Rgb),
+        Some(3) => Some(ColorModel::Indexed),
+        Some(5) => Some(ColorModel::Cmyk),
+        Some(6) => Some(ColorModel::YCbCr),
+        _ => None,
+    };
+    Ok(())
+}
+
+/// Reads the header of a WebP, from its first chunk.
+fn read_webp_header<R: Read + Seek>(mut reader: R, info: &mut ImageInfo) -> Result<()> {
+    // Reads the chunk type, skipping the chunk size.
+    reader.seek(SeekFrom::Start(12))?;
+    let chunk_type = reader.read_bytes(4)?;
+    reader.seek(SeekFrom::Current(4))?;
+
+    // Checks the chunk type.
+    let alpha = match chunk_type.as_slice() {
+        b"VP8 " => {
+            reader.seek(SeekFrom::Current(3))?;
+            if reader.read_bytes(3)? != b"\x9D\x01\x2A" {
+                return Err(Error::new(ErrorKind::InvalidData, "invalid VP8 start code"));
+            }
+            info.width = (reader.read_u16_le()? & 0x3FFF) as u32;
+            info.height = (reader.read_u16_le()? & 0x3FFF) as u32;
+            false
+        }
+        b"VP8L" => {
+            if reader.read_u8()? != 0x2F {
+                return Err(Error::new(ErrorKind::InvalidData, "invalid VP8L signature"));
+            }
+            let bits = reader.read_u32_le()?;
+            info.width = (bits & 0x3FFF) + 1;
+            info.height = (bits >> 14 & 0x3FFF) + 1;
+            bits >> 28 & 1 == 1
+        }
+        b"VP8X" => {
+            let flags = reader.read_u8()?;
+            reader.seek(SeekFrom::Current(3))?;
+            info.width = read_uint(&mut reader, 3, false)? as u32 + 1;
+            info.height = read_uint(&mut reader, 3, false)? as u32 + 1;
+            flags & 0x10 != 0
+        }
+        _ => return Err(Error::new(ErrorKind::InvalidData, "invalid WebP chunk")),
+    };
+    info.bit_depth = Some(8);
+    info.color_model = Some(if alpha {
+        ColorModel::Rgba
+    } else {
+        ColorModel::Rgb
+    });
+    Ok(())
+}
+
+/// Searches for a box of the specified type among the boxes starting at the current position and
+/// ending at the specified offset, and returns the offsets of the start and the end of its payload.
+fn find_box<R: Read + Seek>(mut reader: R, end: u64, box_type: &[u8]) -> Result<(u64, u64)> {
+    let mut position = reader.stream_position()?;
+    while position < end {
+        // Reads the size and the type of the box.
+        let size = reader.read_u32_be()? as u64;
+        let current_type = reader.read_bytes(4)?;
+        let (header_size, size) = match size {
+            0 => (8, end - position),
+            1 => (16, reader.read_u64_be()?),
+            size => (8, size),
+        };
+        if size < header_size {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid box size"));
+        }
+
+        // Checks the type of the box.
+        let box_end = position.saturating_add(size);
+        if current_type == box_type {
+            return Ok((position + header_size, core::cmp::min(box_end, end)));
+        }
+        position = box_end;
+        reader.seek(SeekFrom::Start(position))?;
+    }
+    Err(Error::new(ErrorKind::InvalidData, "missing box"))
+}
+
+/// Reads a null-terminated name of at most 255 bytes.
+fn read_null_terminated<R: Read>(mut reader: R) -> Result<Vec<u8>> {
+    let mut name = Vec::new();
+    loop {
+        match reader.read_u8()? {
+            0 => return Ok(name),
+            _ if name.len() == 255 => {
+                return Err(Error::new(ErrorKind::InvalidData, "name too long"));
+            }
+            byte => name.push(byte),
+        }
+    }
+}
+
+/// Reads an unsigned integer of the specified number of bytes, in the specified byte order.
+fn read_uint<R: Read>(mut reader: R, size: usize, big_endian: bool) -> Result<u64> {
+    let bytes = reader.read_bytes(size)?;
+    let fold = |value: u64, &byte: &u8| value << 8 | byte as u64;
+    Ok(if big_endian {
+        bytes.iter().fold(0, fold)
+    } else {
+        bytes.iter().rev().fold(0, fold)
+    })
+}
+
+/// A reader of the bits of a JPEG XL codestream, from the least significant bit of each byte.
+struct BitReader<'a> {
+    /// Bytes being read.
+    data: &'a [u8],
+    /// Position of the next bit.
+    position: usize,
+}
+
+impl BitReader<'_> {
+    /// Reads an unsigned integer of the specified number of bits.
+    fn read(&mut self, count: u32) -> Result<u32> {
+        let mut value = 0;
+        for index in 0..count {
+            let byte = *self
+                .data
+                .get(self.position / 8)
+                .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated JPEG XL header"))?;
+            value |= (((byte >> (self.position % 8)) & 1) as u32) << index;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    /// Reads an unsigned integer whose offset and number of bits are selected among four
+    /// distributions.
+    fn read_u32(&mut self, distributions: [(u32, u32); 4]) -> Result<u32> {
+        let (offset, count) = distributions[self.read(2)? as usize];
+        Ok(offset + self.read(count)?)
+    }
+}
@@ -0,0 +1,269 @@
+//! Push-based detection, fed with chunks of bytes.
+//!
+//! The signatures take precedence in the same order as with [`FileFormat::from_bytes`], and a few
+//! of them are located up to 36 KB into the stream. Until that many bytes have been fed, a
+//! signature matching the first bytes cannot be told apart from data which also matches one of
+//! them, so most detections need about 36 KB before concluding, unless the stream ends first.
+
+use crate::{
+    io::{Cursor, Tracked},
+    Detector, FileFormat, Signature,
+};
+use alloc::vec::Vec;
+
+/// A push-based file format detector, fed with chunks of bytes as they arrive instead of pulling
+/// them from a reader.
+///
+/// Each call to [`IncrementalDetector::feed`] reports whether more data is needed to conclude:
+///
+/// - the signatures are checked in the same order as [`FileFormat::from_bytes`], so that the
+///   detection waits until every signature which takes precedence is either matched or ruled out
+///   by a mismatching byte. Since a few signatures are located up to 36 KB into the stream, the
+///   offsets of the signatures determine how many bytes are needed;
+/// - a matched signature without format reader concludes the detection at once;
+/// - a matched signature with a format reader concludes the detection as soon as the reader
+///   identifies a more specific file format from the data fed so far, or confirms the format of
+///   the signature without running out of data (e.g. once the 32 KB searched by the SQLite 3
+///   reader have been fed). Since the data cannot be sought, readers relying on the end of the
+///   stream degrade as with [`FileFormat::from_stream`], e.g. the ZIP reader walks the local file
+///   headers instead of the central directory;
+/// - data which matches no signature is determined by the generic reader once a probe has been
+///   fed.
+///
+/// At most [`DetectorBuilder::stream_prefix_size`](`crate::DetectorBuilder::stream_prefix_size`)
+/// bytes are retained. Once they have been fed, the file format determined by the format reader is
+/// reported, unless the reader found the data truncated or failed, in which case the detection is
+/// reported as ambiguous, along with the ranked candidates. The end of the stream is signaled with
+/// [`IncrementalDetector::finish`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{FileFormat, IncrementalDetector, Progress};
+///
+/// let mut detector = IncrementalDetector::new();
+/// assert_eq!(detector.feed(b"\x89PNG\r\n\x1A\n"), Progress::NeedMoreData);
+/// assert_eq!(
+///     detector.feed(&[0; 36870]),
+///     Progress::Done(FileFormat::PortableNetworkGraphics)
+/// );
+///```
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalDetector {
+    /// Detector whose budgets and readers are used.
+    detector: Detector,
+    /// Bytes fed so far, up to the stream prefix size.
+    buf: Vec<u8>,
+    /// Current state of the detection.
+    state: State,
+}
+
+impl IncrementalDetector {
+    /// Creates an incremental detector using the [default](`Detector::default`) detector.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an incremental detector using the budgets and readers of the specified detector.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat, IncrementalDetector, Progress};
+    ///
+    /// let detector = Detector::builder().probe_size(4).build();
+    /// let mut detector = IncrementalDetector::with_detector(detector);
+    /// assert_eq!(detector.feed(b"\0\0\0\0"), Progress::Done(FileFormat::ArbitraryBinaryData));
+    ///```
+    #[inline]
+    pub fn with_detector(detector: Detector) -> Self {
+        Self {
+            detector,
+            ..Self::default()
+        }
+    }
+
+    /// Feeds the next chunk of bytes and reports the progress of the detection.
+    ///
+    /// Once the detection has concluded, further chunks are ignored and the same progress is
+    /// reported.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{FileFormat, IncrementalDetector, Progress};
+    ///
+    /// let mut detector = IncrementalDetector::new();
+    /// assert_eq!(detector.feed(b"\x1F\x8B\x08"), Progress::NeedMoreData);
+    /// assert_eq!(detector.feed(&[0; 36870]), Progress::Done(FileFormat::Gzip));
+    /// assert_eq!(detector.feed(b"\x00"), Progress::Done(FileFormat::Gzip));
+    ///```
+    pub fn feed(&mut self, chunk: &[u8]) -> Progress {
+        if let State::Concluded(progress) = &self.state {
+            return progress.clone();
+        }
+
+        // Retains the chunk, up to the stream prefix size.
+        let limit = usize::try_from(self.detector.stream_prefix_size).unwrap_or(usize::MAX);
+        let room = limit.saturating_sub(self.buf.len());
+        self.buf
+            .extend_from_slice(&chunk[..core::cmp::min(chunk.len(), room)]);
+        let full = self.buf.len() >= limit;
+
+        // Matches the signatures.
+        if let State::Matching = self.state {
+            match self.match_signature() {
+                Match::Pending if !full => return Progress::NeedMoreData,
+                Match::Matched(signature) if self.detector.reader(signature.format).is_some() => {
+                    self.state = State::Reading {
+                        signature,
+                        next_attempt: 0,
+                    };
+                }
+                Match::Matched(signature) => {
+                    return self.conclude(Progress::Done(signature.format))
+                }
+                Match::None if self.buf.len() < self.detector.probe_size && !full => {
+                    return Progress::NeedMoreData
+                }
+                _ => return self.conclude(Progress::Done(self.finish())),
+            }
+        }
+
+        // Runs the format reader, each time the data fed has doubled.
+        if let State::Reading {
+            signature,
+            next_attempt,
+        } = &mut self.state
+        {
+            if self.buf.len() < *next_attempt && !full {
+                return Progress::NeedMoreData;
+            }
+            *next_attempt = self.buf.len().saturating_mul(2);
+            let signature = *signature;
+            let mut reader = Tracked::new(Cursor::new(&self.buf[..]));
+            let detection = self.detector.detect(&mut reader).unwrap_or_default();
+            let confirmed = !detection.truncated && !detection.fell_back();
+            if detection.limit_exceeded.is_some()
+                || (confirmed
+                    && (detection.format != signature.format || !reader.reached_end() || full))
+            {
+                return self.conclude(Progress::Done(detection.format));
+            }
+            if full {
+                let candidates =
+                    FileFormat::rank_candidates(Cursor::new(&self.buf[..])).unwrap_or_default();
+                return self.conclude(Progress::Ambiguous(candidates));
+            }
+        }
+        Progress::NeedMoreData
+    }
+
+    /// Signals the end of the stream and returns the file format determined from the data fed so
+    /// far.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{FileFormat, IncrementalDetector, Progress};
+    ///
+    /// let mut detector = IncrementalDetector::new();
+    /// assert_eq!(detector.feed(b"\x1F\x8B\x08"), Progress::NeedMoreData);
+    /// assert_eq!(detector.finish(), FileFormat::Gzip);
+    ///```
+    pub fn finish(&self) -> FileFormat {
+        match &self.state {
+            State::Concluded(Progress::Done(fmt)) => *fmt,
+            _ => self.detector.detect_bytes(&self.buf).format,
+        }
+    }
+
+    /// Records the conclusion of the detection and returns it.
+    #[inline]
+    fn conclude(&mut self, progress: Progress) -> Progress {
+        self.state = State::Concluded(progress.clone());
+        progress
+    }
+
+    /// Returns the first signature, in the order in which they are checked, which matches or may
+    /// still match the data fed so far.
+    fn match_signature(&self) -> Match {
+        let probe_size = self.detector.probe_size;
+        let probe = &self.buf[..core::cmp::min(self.buf.len(), probe_size)];
+        for signature in Signature::all() {
+            let mut pending = false;
+            let possible = signature.parts.iter().all(|&(offset, value)| {
+                // Checks the bytes available, which must be within the probe.
+                if offset + value.len() > probe_size {
+                    return false;
+                }
+                let available = probe.get(offset..).unwrap_or_default();
+                let len = core::cmp::min(available.len(), value.len());
+                pending |= len < value.len();
+                available[..len] == value[..len]
+            });
+            if possible {
+                return if pending {
+                    Match::Pending
+                } else {
+                    Match::Matched(*signature)
+                };
+            }
+        }
+        Match::None
+    }
+}
+
+/// Progress of an [`IncrementalDetector`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Progress {
+    /// More data is needed to conclude.
+    NeedMoreData,
+    /// The file format is determined.
+    Done(FileFormat),
+    /// The data retained is not enough to conclude, the candidate file formats are ranked by
+    /// decreasing confidence as with [`FileFormat::candidates_from_bytes`].
+    Ambiguous(Vec<(FileFormat, f32)>),
+}
+
+/// State of an [`IncrementalDetector`].
+#[derive(Clone, Debug)]
+enum State {
+    /// The signatures are being matched.
+    Matching,
+    /// The format reader of the matched signature is being run.
+    Reading {
+        /// Matched signature.
+        signature: Signature,
+        /// Number of bytes from which the reader is run again.
+        next_attempt: usize,
+    },
+    /// The detection has concluded.
+    Concluded(Progress),
+}
+
+impl Default for State {
+    #[inline]
+    fn default() -> Self {
+        Self::Matching
+    }
+}
+
+/// Result of matching the signatures against the data fed so far.
+enum Match {
+    /// A signature may still match with more data.
+    Pending,
+    /// A signature matches.
+    Matched(Signature),
+    /// No signature can match.
+    None,
+}
@@ -0,0 +1,423 @@
+//! I/O primitives used by the readers.
+//!
+//! With the `std` feature, they are the ones of `std::io`. Without it, a minimal replacement
+//! covers what the readers need to work on byte slices.
+
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub(crate) use std::io::{
+    BufRead, BufReader, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom,
+};
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+pub(crate) use shim::*;
+
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
+mod shim {
+    use alloc::{string::String, vec::Vec};
+    use core::fmt::{self, Display, Formatter};
+
+    /// A specialized `Result` type for I/O operations.
+    pub(crate) type Result<T> = core::result::Result<T, Error>;
+
+    /// A list specifying general categories of I/O error.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub(crate) enum ErrorKind {
+        /// Data not valid for the operation were encountered.
+        InvalidData,
+        /// A parameter was incorrect.
+        InvalidInput,
+        /// An operation could not be completed, because it failed to read enough data.
+        UnexpectedEof,
+    }
+
+    /// The error type for I/O operations.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub(crate) struct Error {
+        /// Kind of the error.
+        kind: ErrorKind,
+        /// Description of the error.
+        message: &'static str,
+    }
+
+    impl Error {
+        /// Creates an error from a kind and a description.
+        #[inline]
+        pub(crate) const fn new(kind: ErrorKind, message: &'static str) -> Self {
+            Self { kind, message }
+        }
+
+        /// Returns the kind of the error.
+        #[inline]
+        pub(crate) const fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl Display for Error {
+        #[inline]
+        fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+            formatter.write_str(self.message)
+        }
+    }
+
+    /// Possible ways to seek within a stream.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub(crate) enum SeekFrom {
+        /// Sets the offset to the provided number of bytes.
+        Start(u64),
+        /// Sets the offset to the size of the stream plus the specified number of bytes.
+        End(i64),
+        /// Sets the offset to the current position plus the specified number of bytes.
+        Current(i64),
+    }
+
+    /// A source of bytes.
+    pub(crate) trait Read {
+        /// Pulls some bytes from this source into the buffer, returning how many were read.
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+        /// Reads the exact number of bytes required to fill the buffer.
+        #[inline]
+        fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.read(buf)? {
+                    0 => {
+                        return Err(Error::new(
+                            ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        ))
+                    }
+                    nread => buf = &mut buf[nread..],
+                }
+            }
+            Ok(())
+        }
+
+        /// Creates an adapter which reads at most `limit` bytes from this source.
+        #[inline]
+        fn take(self, limit: u64) -> Take<Self>
+        where
+            Self: Sized,
+        {
+            Take { inner: self, limit }
+        }
+    }
+
+    /// A cursor which can be moved within a stream of bytes.
+    pub(crate) trait Seek {
+        /// Seeks to an offset, in bytes, in the stream.
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64>;
+
+        /// Rewinds to the beginning of the stream.
+        #[inline]
+        fn rewind(&mut self) -> Result<()> {
+            self.seek(SeekFrom::Start(0)).map(|_| ())
+        }
+
+        /// Returns the current seek position from the start of the stream.
+        #[inline]
+        fn stream_position(&mut self) -> Result<u64> {
+            self.seek(SeekFrom::Current(0))
+        }
+    }
+
+    /// A source of bytes which can be read line by line.
+    pub(crate) trait BufRead: Read {
+        /// Returns an iterator over the lines of this source, without their line ending.
+        #[inline]
+        fn lines(self) -> Lines<Self>
+        where
+            Self: Sized,
+        {
+            Lines { inner: self }
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            (**self).read(buf)
+        }
+    }
+
+    impl<S: Seek + ?Sized> Seek for &mut S {
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            (**self).seek(pos)
+        }
+    }
+
+    /// A cursor over an in-memory buffer.
+    #[derive(Clone, Debug)]
+    pub(crate) struct Cursor<T> {
+        /// Underlying buffer.
+        inner: T,
+        /// Current position.
+        pos: u64,
+    }
+
+    impl<T> Cursor<T> {
+        /// Creates a cursor at the beginning of the buffer.
+        #[inline]
+        pub(crate) const fn new(inner: T) -> Self {
+            Self { inner, pos: 0 }
+        }
+    }
+
+    impl<T: AsRef<[u8]>> Read for Cursor<T> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let data = self.inner.as_ref();
+            let start = core::cmp::min(self.pos, data.len() as u64) as usize;
+            let len = core::cmp::min(buf.len(), data.len() - start);
+            buf[..len].copy_from_slice(&data[start..start + len]);
+            self.pos += len as u64;
+            Ok(len)
+        }
+    }
+
+    impl<T: AsRef<[u8]>> Seek for Cursor<T> {
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            let (base, offset) = match pos {
+                SeekFrom::Start(offset) => (offset, 0),
+                SeekFrom::End(offset) => (self.inner.as_ref().len() as u64, offset),
+                SeekFrom::Current(offset) => (self.pos, offset),
+            };
+            let pos = if offset >= 0 {
+                base.checked_add(offset as u64)
+            } else {
+                base.checked_sub(offset.unsigned_abs())
+            };
+            self.pos = pos.ok_or(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))?;
+            Ok(self.pos)
+        }
+    }
+
+    /// A reader adapter, which is a no-op since every reader is an in-memory buffer.
+    #[derive(Debug)]
+    pub(crate) struct BufReader<R> {
+        /// Underlying reader.
+        inner: R,
+    }
+
+    impl<R> BufReader<R> {
+        /// Creates a reader adapter.
+        #[inline]
+        pub(crate) const fn new(inner: R) -> Self {
+            Self { inner }
+        }
+    }
+
+    impl<R: Read> Read for BufReader<R> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl<R: Seek> Seek for BufReader<R> {
+        #[inline]
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    impl<R: Read> BufRead for BufReader<R> {}
+
+    /// A reader adapter which limits the number of bytes read.
+    #[derive(Debug)]
+    pub(crate) struct Take<R> {
+        /// Underlying reader.
+        inner: R,
+        /// Number of bytes which can still be read.
+        limit: u64,
+    }
+
+    impl<R: Read> Read for Take<R> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let len = core::cmp::min(buf.len() as u64, self.limit) as usize;
+            let nread = self.inner.read(&mut buf[..len])?;
+            self.limit -= nread as u64;
+            Ok(nread)
+        }
+    }
+
+    impl<R: BufRead> BufRead for Take<R> {}
+
+    /// An iterator over the lines of a reader.
+    #[derive(Debug)]
+    pub(crate) struct Lines<B> {
+        /// Underlying reader.
+        inner: B,
+    }
+
+    impl<B: BufRead> Iterator for Lines<B> {
+        type Item = Result<String>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            // Reads the bytes up to the next line feed.
+            let mut line = Vec::new();
+            let mut byte = [0; 1];
+            loop {
+                match self.inner.read(&mut byte) {
+                    Ok(0) if line.is_empty() => return None,
+                    Ok(0) => break,
+                    Ok(_) if byte[0] == b'\n' => break,
+                    Ok(_) => line.push(byte[0]),
+                    Err(error) => return Some(Err(error)),
+                }
+            }
+
+            // Removes the carriage return and decodes the line.
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            Some(String::from_utf8(line).map_err(|_| {
+                Error::new(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+            }))
+        }
+    }
+}
+
+/// A reader which can seek, used as a trait object where nested data is detected with the same
+/// readers, so that their instantiations do not recurse.
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+pub(crate) trait ReadSeek: Read + Seek {}
+
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+impl<T: Read + Seek + ?Sized> ReadSeek for T {}
+
+/// A reader whose positions are shifted by a base offset, so that a structure appended to other
+/// data can be read with offsets relative to its start.
+pub(crate) struct Shifted<R> {
+    /// Underlying reader.
+    inner: R,
+    /// Offset of the start of the structure in the underlying reader.
+    base: u64,
+}
+
+impl<R> Shifted<R> {
+    /// Creates a reader whose start is at the specified offset of the underlying reader.
+    #[inline]
+    pub(crate) fn new(inner: R, base: u64) -> Self {
+        Self { inner, base }
+    }
+}
+
+impl<R: Read> Read for Shifted<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for Shifted<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => SeekFrom::Start(self.base + offset),
+            pos => pos,
+        };
+        Ok(self.inner.seek(pos)?.saturating_sub(self.base))
+    }
+}
+
+/// A reader whose end is moved back to an offset of the underlying reader, so that a structure
+/// followed by other data can be read as if it ended the stream.
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+pub(crate) struct Bounded<R> {
+    /// Underlying reader.
+    inner: R,
+    /// Offset of the end of the structure in the underlying reader.
+    end: u64,
+}
+
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+impl<R> Bounded<R> {
+    /// Creates a reader whose end is at the specified offset of the underlying reader.
+    #[inline]
+    pub(crate) fn new(inner: R, end: u64) -> Self {
+        Self { inner, end }
+    }
+}
+
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+impl<R: Read + Seek> Read for Bounded<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let pos = self.inner.stream_position()?;
+        let len = core::cmp::min(buf.len() as u64, self.end.saturating_sub(pos)) as usize;
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+impl<R: Seek> Seek for Bounded<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let pos = match pos {
+            SeekFrom::End(offset) => SeekFrom::Start(
+                if offset >= 0 {
+                    self.end.checked_add(offset as u64)
+                } else {
+                    self.end.checked_sub(offset.unsigned_abs())
+                }
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?,
+            ),
+            pos => pos,
+        };
+        self.inner.seek(pos)
+    }
+}
+
+/// A reader which records whether a read came short of the requested size, so that a result
+/// determined from a prefix of the data can be told apart from one the rest could not change.
+pub(crate) struct Tracked<R> {
+    /// Underlying reader.
+    inner: R,
+    /// `true` once a read has returned fewer bytes than requested.
+    reached_end: bool,
+}
+
+impl<R> Tracked<R> {
+    /// Creates a reader tracking the end of the underlying reader.
+    #[inline]
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            reached_end: false,
+        }
+    }
+
+    /// Returns `true` if a read has returned fewer bytes than requested.
+    #[inline]
+    pub(crate) fn reached_end(&self) -> bool {
+        self.reached_end
+    }
+}
+
+impl<R: Read> Read for Tracked<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let nread = self.inner.read(buf)?;
+        self.reached_end |= nread < buf.len();
+        Ok(nread)
+    }
+}
+
+impl<R: Seek> Seek for Tracked<R> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -0,0 +1,229 @@
+//! Layered detection of compressed file formats.
+
+use crate::{Detector, FileFormat};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Result, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+impl FileFormat {
+    /// Determines the layers of file formats from bytes, from the outermost to the innermost.
+    ///
+    /// See [`FileFormat::layers_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let layers = FileFormat::layers_from_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A");
+    /// assert_eq!(layers, vec![FileFormat::PortableNetworkGraphics]);
+    ///```
+    #[inline]
+    pub fn layers_from_bytes<B: AsRef<[u8]>>(bytes: B) -> Vec<Self> {
+        Detector::default().layers_from_bytes(bytes)
+    }
+
+    /// Determines the layers of file formats from a file, from the outermost to the innermost.
+    ///
+    /// See [`FileFormat::layers_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.gz")?;
+    /// assert_eq!(layers, vec![FileFormat::Gzip, FileFormat::TapeArchive]);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn layers_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        Detector::default().layers_from_file(path)
+    }
+
+    /// Determines the layers of file formats from a reader, from the outermost to the innermost.
+    ///
+    /// While the detected file format is [gzip](`FileFormat::Gzip`),
+    /// [bzip2](`FileFormat::Bzip2`), [XZ](`FileFormat::Xz`), [Zstandard](`FileFormat::Zstandard`)
+    /// or [LZ4](`FileFormat::Lz4`), a bounded prefix of its payload is decompressed and detected in
+    /// turn, so that a `.tar.gz` is reported as `[Gzip, TapeArchive]` and a `.svgz` as
+    /// `[Gzip, ScalableVectorGraphics]`.
+    ///
+    /// To guard against decompression bombs, the number of bytes read and decompressed for each
+    /// layer and the number of layers are bounded by the
+    /// [`DetectorBuilder`](`crate::DetectorBuilder`). The last layer is the compressed file format
+    /// if its payload cannot be decompressed or if the limit of layers is reached.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let layers = FileFormat::layers_from_reader(std::io::empty())?;
+    /// assert_eq!(layers, vec![FileFormat::Empty]);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn layers_from_reader<R: Read + Seek>(reader: R) -> Result<Vec<Self>> {
+        Detector::default().layers_from_reader(reader)
+    }
+}
+
+impl Detector {
+    /// Determines the layers of file formats from bytes, from the outermost to the innermost.
+    ///
+    /// See [`FileFormat::layers_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let layers = Detector::default().layers_from_bytes(b"\x1F\x8B");
+    /// assert_eq!(layers, vec![FileFormat::Gzip]);
+    ///```
+    #[inline]
+    pub fn layers_from_bytes<B: AsRef<[u8]>>(&self, bytes: B) -> Vec<FileFormat> {
+        self.layers(io::Cursor::new(bytes.as_ref()))
+            .unwrap_or_default()
+    }
+
+    /// Determines the layers of file formats from a file, from the outermost to the innermost.
+    ///
+    /// See [`FileFormat::layers_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let layers = Detector::default().layers_from_file("fixtures/layered/sample.tar.zst")?;
+    /// assert_eq!(layers, vec![FileFormat::Zstandard, FileFormat::TapeArchive]);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn layers_from_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<FileFormat>> {
+        self.layers_from_reader(File::open(path)?)
+    }
+
+    /// Determines the layers of file formats from a reader, from the outermost to the innermost.
+    ///
+    /// See [`FileFormat::layers_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{Detector, FileFormat};
+    ///
+    /// let layers = Detector::default().layers_from_reader(std::io::empty())?;
+    /// assert_eq!(layers, vec![FileFormat::Empty]);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[inline]
+    pub fn layers_from_reader<R: Read + Seek>(&self, reader: R) -> Result<Vec<FileFormat>> {
+        self.layers(reader)
+    }
+
+    /// Determines the layers of file formats from a reader, whatever the I/O primitives in use.
+    fn layers<R: Read + Seek>(&self, mut reader: R) -> Result<Vec<FileFormat>> {
+        // Determines the outermost file format.
+        let mut fmt = self.detect(&mut reader)?.format;
+        let mut layers = vec![fmt];
+
+        // Decompresses and detects the payload of each compressed layer, checking the limit before
+        // decompressing the next one.
+        reader.seek(SeekFrom::Start(0))?;
+        let mut payload = if self.layer_limit > 0 {
+            decompress(fmt, reader, self.layer_prefix_size)
+        } else {
+            None
+        };
+        while let Some(data) = payload {
+            fmt = self.detect_bytes(&data).format;
+            layers.push(fmt);
+            payload = if layers.len() <= self.layer_limit {
+                decompress(fmt, &data[..], self.layer_prefix_size)
+            } else {
+                None
+            };
+        }
+        Ok(layers)
+    }
+}
+
+/// Decompresses a prefix of the payload of a compressed file format, reading and producing at
+/// most the specified number of bytes.
+///
+/// Returns `None` if the file format is not supported or if nothing could be decompressed.
+fn decompress<R: Read>(fmt: FileFormat, reader: R, limit: usize) -> Option<Vec<u8>> {
+    let reader = reader.take(limit as u64);
+    let mut prefix = Prefix {
+        data: Vec::new(),
+        limit,
+    };
+    let result = match fmt {
+        FileFormat::Bzip2 => copy(bzip2_rs::DecoderReader::new(reader), &mut prefix),
+        FileFormat::Gzip => copy(flate2::read::MultiGzDecoder::new(reader), &mut prefix),
+        FileFormat::Lz4 => copy(lz4_flex::frame::FrameDecoder::new(reader), &mut prefix),
+        FileFormat::Xz => lzma_rs::xz_decompress(&mut BufReader::new(reader), &mut prefix)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string())),
+        FileFormat::Zstandard => ruzstd::StreamingDecoder::new(reader)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+            .and_then(|decoder| copy(decoder, &mut prefix)),
+        _ => return None,
+    };
+
+    // Keeps the data decompressed before an error, which is expected since the input is bounded.
+    if result.is_err() && prefix.data.is_empty() {
+        None
+    } else {
+        Some(prefix.data)
+    }
+}
+
+/// Copies a decompressed stream into a prefix, until either is exhausted.
+#[inline]
+fn copy<R: Read>(mut reader: R, prefix: &mut Prefix) -> Result<()> {
+    io::copy(&mut reader, prefix).map(|_| ())
+}
+
+/// A writer retaining a bounded prefix of the data written to it.
+struct Prefix {
+    /// Data retained.
+    data: Vec<u8>,
+    /// Maximum number of bytes retained.
+    limit: usize,
+}
+
+impl Write for Prefix {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let len = std::cmp::min(buf.len(), self.limit - self.data.len());
+        if len == 0 && !buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "decompressed prefix is full",
+            ));
+        }
+        self.data.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -41,14 +41,36 @@ assert_eq!(fmt.extension(), "jpg");
 assert_eq!(fmt.kind(), Kind::Image);
 ```
 
+Determines with a configured detector:
+
+```no_run
+use file_format::{Detector, FileFormat, Reader};
+
+let detector = Detector::builder()
+    .pdf_read_limit(65_536)
+    .reader(Reader::Txt, false)
+    .build();
+let fmt = detector.from_file("fixtures/document/sample.pdf")?;
+assert_eq!(fmt, FileFormat::PortableDocumentFormat);
+# Ok::<(), std::io::Error>(())
+```
+
 # Crate features
 
-All features below are disabled by default.
+All features below are disabled by default, except `std`.
+
+## Std feature
+
+- `std` - Enables the detection from [files](`FileFormat::from_file`),
+  [readers](`FileFormat::from_reader`) and [streams](`FileFormat::from_stream`). Without it, the
+  crate is `no_std` and only requires `alloc`: file formats can still be determined from bytes
+  with [`FileFormat::from_bytes`], using every enabled reader.
 
 ## Reader features
 
 These features enable the detection of file formats that require a specific reader for
-identification.
+identification. The ASF, EBML, MP4 and RM readers also collect the duration and the tracks of the
+media, returned by [`FileFormat::media_info_from_reader`] and its variants.
 
 - `reader` - Enables all reader features.
 - `reader-asf` - Enables [Advanced Systems Format (ASF)](`FileFormat::AdvancedSystemsFormat`) based
@@ -93,12 +115,19 @@ identification.
   * [Matroska Subtitles (MKS)](`FileFormat::MatroskaSubtitles`)
   * [Matroska Video (MKV)](`FileFormat::MatroskaVideo`)
   * [WebM](`FileFormat::Webm`)
+- `reader-elf` - Enables the detection of the overlay of
+  [Executable and Linkable Format (ELF)](`FileFormat::ExecutableAndLinkableFormat`) files.
 - `reader-exe` - Enables [MS-DOS Executable (EXE)](`FileFormat::MsDosExecutable`) based file formats
-  detection.
+  detection, along with the detection of their overlay. Mach-O images are not read, so no overlay
+  is reported for them.
+  * [7-Zip Self-Extracting Archive](`FileFormat::SevenZipSelfExtractingArchive`)
+  * [Cabinet Self-Extracting Archive](`FileFormat::CabinetSelfExtractingArchive`)
   * [Dynamic Link Library (DLL)](`FileFormat::DynamicLinkLibrary`)
   * [Linear Executable (LE)](`FileFormat::LinearExecutable`)
   * [New Executable (NE)](`FileFormat::NewExecutable`)
   * [Portable Executable (PE)](`FileFormat::PortableExecutable`)
+  * [Roshal Self-Extracting Archive](`FileFormat::RoshalSelfExtractingArchive`)
+  * [ZIP Self-Extracting Archive](`FileFormat::ZipSelfExtractingArchive`)
 - `reader-mp4` - Enables [MPEG-4 Part 14 (MP4)](`FileFormat::Mpeg4Part14`) based file formats
   detection.
   * [MPEG-4 Part 14 Audio (MP4)](`FileFormat::Mpeg4Part14Audio`)
@@ -165,10 +194,24 @@ identification.
   * [Keyhole Markup Language ZIP (KMZ)](`FileFormat::KeyholeMarkupLanguageZip`)
   * [Microsoft Visual Studio Extension (VSIX)](`FileFormat::MicrosoftVisualStudioExtension`)
   * [MusicXML ZIP (MXL)](`FileFormat::MusicxmlZip`)
+  * [Office Open XML Binary Spreadsheet (XLSB)](`FileFormat::OfficeOpenXmlBinarySpreadsheet`)
   * [Office Open XML Document (DOCX)](`FileFormat::OfficeOpenXmlDocument`)
+  * [Office Open XML Document Template (DOTX)](`FileFormat::OfficeOpenXmlDocumentTemplate`)
   * [Office Open XML Drawing (VSDX)](`FileFormat::OfficeOpenXmlDrawing`)
+  * [Office Open XML Macro-Enabled Document (DOCM)](`FileFormat::OfficeOpenXmlMacroEnabledDocument`)
+  * [Office Open XML Macro-Enabled Document Template (DOTM)](`FileFormat::OfficeOpenXmlMacroEnabledDocumentTemplate`)
+  * [Office Open XML Macro-Enabled Presentation (PPTM)](`FileFormat::OfficeOpenXmlMacroEnabledPresentation`)
+  * [Office Open XML Macro-Enabled Presentation Add-In (PPAM)](`FileFormat::OfficeOpenXmlMacroEnabledPresentationAddIn`)
+  * [Office Open XML Macro-Enabled Presentation Template (POTM)](`FileFormat::OfficeOpenXmlMacroEnabledPresentationTemplate`)
+  * [Office Open XML Macro-Enabled Slideshow (PPSM)](`FileFormat::OfficeOpenXmlMacroEnabledSlideshow`)
+  * [Office Open XML Macro-Enabled Spreadsheet (XLSM)](`FileFormat::OfficeOpenXmlMacroEnabledSpreadsheet`)
+  * [Office Open XML Macro-Enabled Spreadsheet Add-In (XLAM)](`FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetAddIn`)
+  * [Office Open XML Macro-Enabled Spreadsheet Template (XLTM)](`FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetTemplate`)
   * [Office Open XML Presentation (PPTX)](`FileFormat::OfficeOpenXmlPresentation`)
+  * [Office Open XML Presentation Template (POTX)](`FileFormat::OfficeOpenXmlPresentationTemplate`)
+  * [Office Open XML Slideshow (PPSX)](`FileFormat::OfficeOpenXmlSlideshow`)
   * [Office Open XML Spreadsheet (XLSX)](`FileFormat::OfficeOpenXmlSpreadsheet`)
+  * [Office Open XML Spreadsheet Template (XLTX)](`FileFormat::OfficeOpenXmlSpreadsheetTemplate`)
   * [OpenDocument Database (ODB)](`FileFormat::OpendocumentDatabase`)
   * [OpenDocument Formula (ODF)](`FileFormat::OpendocumentFormula`)
   * [OpenDocument Formula Template (OTF)](`FileFormat::OpendocumentFormulaTemplate`)
@@ -203,28 +246,118 @@ identification.
   * [XAP](`FileFormat::Xap`)
   * [XPInstall (XPI)](`FileFormat::Xpinstall`)
   * [iOS App Store Package (IPA)](`FileFormat::IosAppStorePackage`)
+
+## Async feature
+
+- `async` - Enables `FileFormat::from_async_reader`, `FileFormat::detect_async_reader` and
+  `Detector::detect_async`, which determine the file format from a `futures_io::AsyncRead` +
+  `futures_io::AsyncSeek` stream while running the same signature and reader logic as their
+  blocking counterparts on a helper thread.
+
+## Decompress feature
+
+- `decompress` - Enables [`FileFormat::layers_from_reader`] and its variants, which determine the
+  layers of file formats of nested encodings, such as `[Gzip, TapeArchive]` for a `.tar.gz`, by
+  decompressing a bounded prefix of [gzip](`FileFormat::Gzip`), [bzip2](`FileFormat::Bzip2`),
+  [XZ](`FileFormat::Xz`), [Zstandard](`FileFormat::Zstandard`) and [LZ4](`FileFormat::Lz4`)
+  payloads with pure-Rust decoders.
+
+## Image info feature
+
+- `image-info` - Enables [`FileFormat::image_info_from_reader`] and its variants, which read the
+  width, the height, the bit depth and the [`ColorModel`] of an image from its header only, without
+  decoding it, for [APNG](`FileFormat::AnimatedPortableNetworkGraphics`),
+  [AVIF](`FileFormat::Av1ImageFileFormat`), [BMP](`FileFormat::WindowsBitmap`),
+  [GIF](`FileFormat::GraphicsInterchangeFormat`),
+  [JPEG](`FileFormat::JointPhotographicExpertsGroup`), [JPEG XL](`FileFormat::JpegXl`),
+  [OpenEXR](`FileFormat::Openexr`), [PNG](`FileFormat::PortableNetworkGraphics`),
+  [QOI](`FileFormat::QuiteOkImage`), [TIFF](`FileFormat::TagImageFileFormat`) and
+  [WebP](`FileFormat::Webp`).
+
+## Mmap feature
+
+- `mmap` - Enables [`FileFormat::from_file_mmap`] and [`FileFormat::detect_file_mmap`], which
+  determine the file format of a local file by running signature matching and format readers
+  directly on a memory map. This is the only feature relying on `unsafe` code, and both functions
+  are `unsafe` since the file must not be modified while it is mapped.
+
+## Scan feature
+
+- `scan` - Enables [`Scanner`], which walks a directory tree and determines the file format of
+  every regular file in parallel with a bounded pool of threads, with options for following
+  symbolic links, skipping hidden files, limiting the depth and the number of bytes read from
+  each file.
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
+
+extern crate alloc;
 
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "async")]
+mod async_reader;
+mod candidates;
+mod custom;
+mod database;
+mod detection;
+mod detector;
+mod dispatch;
+mod embedded;
+mod encryption;
+mod error;
 mod formats;
+#[cfg(all(feature = "from-extension", feature = "std"))]
+mod hint;
+#[cfg(feature = "image-info")]
+mod image;
+mod incremental;
+mod io;
+#[cfg(feature = "decompress")]
+mod layers;
+mod media;
+mod polyglot;
 mod readers;
+#[cfg(feature = "scan")]
+mod scanner;
 mod signatures;
+#[cfg(feature = "from-extension")]
+mod verify;
+mod version;
 mod extension;
 mod media_type;
 
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "std")]
 use std::{
-    fmt::{self, Display, Formatter},
     fs::File,
-    io::{Cursor, Read, Result, Seek},
+    io::{Chain, Cursor, Read, Result, Seek},
     path::Path,
 };
 
+pub use custom::{CustomFormat, DetectedFormat, Precedence};
+pub use database::DatabaseError;
+pub use detection::Detection;
+pub use detector::{Detector, DetectorBuilder};
+pub use embedded::{EmbeddedScanner, EmbeddedScannerBuilder};
+pub use error::DetectionError;
 pub use formats::FileFormat;
+#[cfg(feature = "image-info")]
+pub use image::{ColorModel, ImageInfo};
+pub use incremental::{IncrementalDetector, Progress};
+pub use media::{MediaInfo, Track, TrackKind};
+pub use readers::Reader;
+#[cfg(feature = "scan")]
+pub use scanner::{Scan, Scanner, ScannerBuilder};
+#[cfg(feature = "from-extension")]
+pub use verify::{Verdict, Verification};
+use dispatch::DISPATCH;
+use signatures::SIGNATURES;
 
 #[cfg(feature = "extended-enums")]
 pub use strum::IntoEnumIterator;
@@ -272,11 +405,46 @@ impl FileFormat {
     /// assert_eq!(fmt, FileFormat::AudioVideoInterleave);
     /// # Ok::<(), std::io::Error>(())
     ///```
+    #[cfg(feature = "std")]
     #[inline]
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         Self::from_reader(File::open(path)?)
     }
 
+    /// Determines file format from a memory-mapped file.
+    ///
+    /// Signature matching and format readers run directly on the map instead of issuing reads and
+    /// seeks on the file, which saves many system calls on large local files, e.g. when the PDF
+    /// reader scans for its markers.
+    ///
+    /// Prefer [`FileFormat::from_file`] for files shared with other processes.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated or removed, by this process or any other, while it
+    /// is being detected, since the map would no longer reflect valid memory and reading it leads
+    /// to undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// // SAFETY: the fixture is not modified during the detection.
+    /// let fmt = unsafe { FileFormat::from_file_mmap("fixtures/document/sample.pdf")? };
+    /// assert_eq!(fmt, FileFormat::PortableDocumentFormat);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(feature = "mmap", allow(unsafe_code))]
+    #[inline]
+    pub unsafe fn from_file_mmap<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // SAFETY: the caller upholds the contract of `detect_file_mmap`, which is the same.
+        unsafe { Self::detect_file_mmap(path) }.map(|detection| detection.format)
+    }
+
     /// Determines file format from a reader.
     ///
     /// # Examples
@@ -290,20 +458,234 @@ impl FileFormat {
     /// assert_eq!(fmt, FileFormat::Empty);
     /// # Ok::<(), std::io::Error>(())
     ///```
-    pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
-        // Creates and fills a buffer.
-        let mut buf = [0; 36_870];
-        let nread = reader.read(&mut buf)?;
-
-        // Determines file format.
-        Ok(if nread == 0 {
-            Self::Empty
-        } else if let Some(fmt) = Self::from_signature(&buf[..nread]) {
-            Self::from_fmt_reader(fmt, &mut reader)
-                .unwrap_or_else(|_| Self::from_generic_reader(&mut reader))
-        } else {
-            Self::from_generic_reader(&mut reader)
-        })
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+        Self::detect_reader(reader).map(|detection| detection.format)
+    }
+
+    /// Determines file format from a stream which cannot seek, such as the standard input, a pipe
+    /// or an HTTP body.
+    ///
+    /// Detection runs over a prefix of the stream, bounded to 1 MB. Readers relying on the end of
+    /// the stream degrade gracefully when it is not available, e.g. the ZIP reader walks the local
+    /// file headers instead of the central directory.
+    ///
+    /// The consumed prefix is returned chained with the rest of the stream, so that no data is
+    /// lost.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    /// use std::io::Read;
+    ///
+    /// let stream: &[u8] = b"\x1F\x8B\x08";
+    /// let (fmt, mut stream) = FileFormat::from_stream(stream)?;
+    /// assert_eq!(fmt, FileFormat::Gzip);
+    ///
+    /// let mut data = Vec::new();
+    /// stream.read_to_end(&mut data)?;
+    /// assert_eq!(data, b"\x1F\x8B\x08");
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_stream<R: Read>(reader: R) -> Result<(Self, Stream<R>)> {
+        Self::detect_stream(reader).map(|(detection, stream)| (detection.format, stream))
+    }
+
+    /// Determines file format from bytes, along with the provenance of the result.
+    ///
+    /// # Examples
+    ///
+    /// Detects from the first bytes of a
+    /// [Portable Network Graphics (PNG)](`FileFormat::PortableNetworkGraphics`) file:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A");
+    /// assert_eq!(detection.format, FileFormat::PortableNetworkGraphics);
+    /// assert_eq!(detection.signature.unwrap().offset(), 0);
+    /// assert_eq!(detection.reader, None);
+    ///```
+    #[inline]
+    pub fn detect_bytes<B: AsRef<[u8]>>(bytes: B) -> Detection {
+        Detector::default().detect_bytes(bytes)
+    }
+
+    /// Determines file format from a file, along with the provenance of the result.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(detection.format, FileFormat::AudioVideoInterleave);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn detect_file<P: AsRef<Path>>(path: P) -> Result<Detection> {
+        Self::detect_reader(File::open(path)?)
+    }
+
+    /// Determines file format from a memory-mapped file, along with the provenance of the result.
+    ///
+    /// See [`FileFormat::from_file_mmap`] for details.
+    ///
+    /// # Safety
+    ///
+    /// See [`FileFormat::from_file_mmap`] for the contract the caller must uphold.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// // SAFETY: the fixture is not modified during the detection.
+    /// let detection = unsafe { FileFormat::detect_file_mmap("fixtures/document/sample.pdf")? };
+    /// assert_eq!(detection.format, FileFormat::PortableDocumentFormat);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(feature = "mmap", allow(unsafe_code))]
+    #[inline]
+    pub unsafe fn detect_file_mmap<P: AsRef<Path>>(path: P) -> Result<Detection> {
+        // SAFETY: the caller upholds the contract of `Detector::detect_file_mmap`, which is the
+        // same.
+        unsafe { Detector::default().detect_file_mmap(path) }
+    }
+
+    /// Determines file format from a reader, along with the provenance of the result.
+    ///
+    /// The returned [`Detection`] records the matched signature, the [`Reader`] which determined
+    /// the file format and the marker it relied on, as well as the error of the format reader if
+    /// the detection fell back to the generic reader.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_reader(std::io::empty())?;
+    /// assert_eq!(detection.format, FileFormat::Empty);
+    /// assert_eq!(detection.signature, None);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn detect_reader<R: Read + Seek>(reader: R) -> Result<Detection> {
+        Detector::default().detect_reader(reader)
+    }
+
+    /// Determines file format from a stream which cannot seek, along with the provenance of the
+    /// result.
+    ///
+    /// See [`FileFormat::from_stream`] for details about the detection.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let (detection, _) = FileFormat::detect_stream(std::io::empty())?;
+    /// assert_eq!(detection.format, FileFormat::Empty);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn detect_stream<R: Read>(reader: R) -> Result<(Detection, Stream<R>)> {
+        Detector::default().detect_stream(reader)
+    }
+
+    /// Determines file format from bytes, surfacing the errors preventing a conclusive detection.
+    ///
+    /// See [`FileFormat::try_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let fmt = FileFormat::try_from_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A")?;
+    /// assert_eq!(fmt, FileFormat::PortableNetworkGraphics);
+    /// # Ok::<(), file_format::DetectionError>(())
+    ///```
+    #[inline]
+    pub fn try_from_bytes<B: AsRef<[u8]>>(bytes: B) -> core::result::Result<Self, DetectionError> {
+        Detector::default()
+            .try_detect_bytes(bytes)
+            .map(|detection| detection.format)
+    }
+
+    /// Determines file format from a file, surfacing the errors preventing a conclusive detection.
+    ///
+    /// See [`FileFormat::try_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let fmt = FileFormat::try_from_file("fixtures/video/sample.avi")?;
+    /// assert_eq!(fmt, FileFormat::AudioVideoInterleave);
+    /// # Ok::<(), file_format::DetectionError>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_from_file<P: AsRef<Path>>(path: P) -> core::result::Result<Self, DetectionError> {
+        Detector::default()
+            .try_detect_file(path)
+            .map(|detection| detection.format)
+    }
+
+    /// Determines file format from a reader, surfacing the errors preventing a conclusive detection.
+    ///
+    /// Unlike [`FileFormat::from_reader`], which falls back to the generic reader when the format
+    /// reader fails, this function returns a [`DetectionError`] telling apart:
+    ///
+    /// - a failure of the underlying reader ([`DetectionError::Io`]);
+    /// - a stream ending before the format reader could conclude, such as a ZIP missing its end of
+    ///   central directory record ([`DetectionError::Truncated`]);
+    /// - inconsistent data, such as an invalid EBML element size
+    ///   ([`DetectionError::Malformed`]);
+    /// - a limit of the [`Detector`] reached before the format reader could conclude
+    ///   ([`DetectionError::LimitExceeded`]).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let fmt = FileFormat::try_from_reader(std::io::empty())?;
+    /// assert_eq!(fmt, FileFormat::Empty);
+    /// # Ok::<(), file_format::DetectionError>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_from_reader<R: Read + Seek>(reader: R) -> core::result::Result<Self, DetectionError> {
+        Detector::default()
+            .try_detect_reader(reader)
+            .map(|detection| detection.format)
     }
 }
 
@@ -326,10 +708,15 @@ impl Display for FileFormat {
 impl From<&[u8]> for FileFormat {
     #[inline]
     fn from(value: &[u8]) -> Self {
-        Self::from_reader(Cursor::new(value)).unwrap_or_default()
+        Self::detect_bytes(value).format
     }
 }
 
+/// A stream returned by [`FileFormat::from_stream`], which yields the prefix consumed by the
+/// detection followed by the rest of the underlying stream.
+#[cfg(feature = "std")]
+pub type Stream<R> = Chain<Cursor<Vec<u8>>, R>;
+
 /// A kind of file format.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -382,3 +769,132 @@ pub enum Kind {
     /// Moving images, possibly with color and coordinated sound.
     Video,
 }
+
+/// A signature of a file format, made of values expected at given offsets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Signature {
+    /// File format identified by the signature.
+    pub(crate) format: FileFormat,
+    /// Offsets and values which must all be matched.
+    pub(crate) parts: &'static [(usize, &'static [u8])],
+}
+
+impl Signature {
+    /// Returns the file format identified by the signature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_bytes(b"\x1F\x8B");
+    /// assert_eq!(detection.signature.unwrap().format(), FileFormat::Gzip);
+    ///```
+    #[inline]
+    pub const fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    /// Returns the offsets and values which must all be matched.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_bytes(b"\x1F\x8B");
+    /// assert_eq!(detection.signature.unwrap().parts(), &[(0, &b"\x1F\x8B"[..])]);
+    ///```
+    #[inline]
+    pub const fn parts(&self) -> &'static [(usize, &'static [u8])] {
+        self.parts
+    }
+
+    /// Returns the total number of bytes checked by the signature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_bytes(b"\x1F\x8B");
+    /// assert_eq!(detection.signature.unwrap().size(), 2);
+    ///```
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.parts.iter().map(|(_, value)| value.len()).sum()
+    }
+
+    /// Returns the offset of the first value of the signature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let detection = FileFormat::detect_bytes(b"\x1F\x8B");
+    /// assert_eq!(detection.signature.unwrap().offset(), 0);
+    ///```
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.parts[0].0
+    }
+
+    /// Returns the known signatures, in the order in which they are checked.
+    ///
+    /// When several signatures match the same bytes, the one listed first takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{FileFormat, Signature};
+    ///
+    /// let signatures = Signature::all();
+    /// assert!(signatures.iter().any(|signature| signature.format() == FileFormat::Gzip));
+    ///```
+    #[inline]
+    pub const fn all() -> &'static [Self] {
+        SIGNATURES
+    }
+
+    /// Returns the first known signature matched by the bytes, if any.
+    ///
+    /// The result is the same as the first signature of [`Signature::all`] matched by the bytes,
+    /// but signatures are indexed by the offset of their first part and the byte it starts with,
+    /// so that only the ones whose first byte is found at this offset of the bytes are checked.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{FileFormat, Signature};
+    ///
+    /// let signature = Signature::find(b"\x1F\x8B\x08").unwrap();
+    /// assert_eq!(signature.format(), FileFormat::Gzip);
+    ///```
+    #[inline]
+    pub fn find(bytes: &[u8]) -> Option<&'static Self> {
+        DISPATCH
+            .candidates(bytes)
+            .find(|signature| signature.matches(bytes))
+    }
+
+    /// Returns `true` if the bytes match all the parts of the signature.
+    #[inline]
+    pub(crate) fn matches(&self, bytes: &[u8]) -> bool {
+        self.parts
+            .iter()
+            .all(|&(offset, value)| bytes.get(offset..offset + value.len()) == Some(value))
+    }
+}
@@ -149,7 +149,7 @@ macro_rules! formats {
     };
 }
 
-/// Generates the [`FileFormat::from_signature`](crate::FileFormat::from_signature) function.
+/// Generates the [`SIGNATURES`](crate::signatures::SIGNATURES) table.
 ///
 /// # Parameters
 ///
@@ -163,23 +163,20 @@ macro_rules! signatures {
             $(value = $($value:literal $(offset = $offset:literal)?),+)+
         )*
     } => {
-        impl crate::FileFormat {
-            /// Determines file format by checking its signature.
-            #[allow(clippy::int_plus_one)]
-            pub(crate) fn from_signature(bytes: &[u8]) -> Option<Self> {
+        /// Known signatures, listed in the order in which they are checked.
+        pub(crate) const SIGNATURES: &[crate::Signature] = &[
+            $(
                 $(
-                    if $($(bytes.len() >= $($offset +)? $value.len()
-                        && &bytes[$($offset)?..$($offset +)? $value.len()] == $value)&&*)||* {
-                        return Some(Self::$format);
-                    }
-                )*
-                None
-            }
-        }
+                    crate::Signature {
+                        format: crate::FileFormat::$format,
+                        parts: &[$((0 $(+ $offset)?, $value)),+],
+                    },
+                )+
+            )*
+        ];
     };
 }
 
-
 macro_rules! media_type_mapping {
     {
         $(
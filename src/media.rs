@@ -0,0 +1,172 @@
+//! Metadata of the tracks of media containers, collected by their format readers.
+
+use crate::FileFormat;
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use crate::io::{Read, Result, Seek};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Metadata of a media container, collected while its format reader walks its structure.
+///
+/// It is only available when the reader of the container is enabled, i.e. `reader-asf` for
+/// [ASF](`FileFormat::AdvancedSystemsFormat`), `reader-ebml` for
+/// [Matroska](`FileFormat::MatroskaVideo`) and [WebM](`FileFormat::Webm`), `reader-mp4` for
+/// [MP4](`FileFormat::Mpeg4Part14`) and `reader-rm` for [RealMedia](`FileFormat::Realmedia`).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{FileFormat, TrackKind};
+///
+/// if let Some(media) = FileFormat::media_info_from_bytes(b"\x1F\x8B") {
+///     println!("{} video track(s)", media.track_count(TrackKind::Video));
+/// }
+///```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct MediaInfo {
+    /// Duration of the presentation, if known.
+    pub duration: Option<Duration>,
+    /// Tracks, in the order in which they are declared by the container.
+    pub tracks: Vec<Track>,
+}
+
+impl MediaInfo {
+    /// Returns the number of tracks of the specified kind.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{MediaInfo, TrackKind};
+    ///
+    /// assert_eq!(MediaInfo::default().track_count(TrackKind::Audio), 0);
+    ///```
+    #[inline]
+    pub fn track_count(&self, kind: TrackKind) -> usize {
+        self.tracks
+            .iter()
+            .filter(|track| track.kind == kind)
+            .count()
+    }
+}
+
+/// A track of a media container.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct Track {
+    /// Kind of the track.
+    pub kind: TrackKind,
+    /// Codec identifier of the track, as declared by the container, if known:
+    ///
+    /// - the stream type GUID of an ASF stream (e.g. `"bc19efc0-5b4d-11cf-a8fd-00805f5c442b"`);
+    /// - the CodecID of a Matroska track (e.g. `"V_MPEG4/ISO/AVC"`);
+    /// - the fourcc of the first sample entry of an MP4 track (e.g. `"avc1"`);
+    /// - the mime type of a RealMedia stream (e.g. `"audio/x-pn-realaudio"`).
+    pub codec: Option<String>,
+}
+
+impl Track {
+    /// Creates a track of the specified kind, whose codec is not known yet.
+    #[cfg(any(
+        feature = "reader-asf",
+        feature = "reader-ebml",
+        feature = "reader-mp4",
+        feature = "reader-rm"
+    ))]
+    #[inline]
+    pub(crate) fn new(kind: TrackKind) -> Self {
+        Self { kind, codec: None }
+    }
+}
+
+/// A kind of track.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "extended-enums",
+    derive(strum::EnumIter, strum::Display, strum::AsRefStr, strum::FromRepr)
+)]
+pub enum TrackKind {
+    /// Audio track.
+    Audio,
+    /// Track which is neither an audio, a subtitle nor a video one (e.g. a hint or a timecode
+    /// track).
+    Other,
+    /// Subtitle track.
+    Subtitle,
+    /// Video track.
+    Video,
+}
+
+impl FileFormat {
+    /// Determines the metadata of a media container from bytes.
+    ///
+    /// See [`MediaInfo`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let media = FileFormat::media_info_from_bytes(b"\x89\x50\x4E\x47\x0D\x0A\x1A\x0A");
+    /// assert_eq!(media, None);
+    ///```
+    #[inline]
+    pub fn media_info_from_bytes<B: AsRef<[u8]>>(bytes: B) -> Option<MediaInfo> {
+        Self::detect_bytes(bytes).media
+    }
+
+    /// Determines the metadata of a media container from a file.
+    ///
+    /// See [`MediaInfo`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::{FileFormat, TrackKind};
+    ///
+    /// if let Some(media) = FileFormat::media_info_from_file("upload.mkv")? {
+    ///     for track in media.tracks.iter().filter(|track| track.kind == TrackKind::Video) {
+    ///         println!("{:?}", track.codec);
+    ///     }
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn media_info_from_file<P: AsRef<Path>>(path: P) -> Result<Option<MediaInfo>> {
+        Ok(Self::detect_file(path)?.media)
+    }
+
+    /// Determines the metadata of a media container from a reader.
+    ///
+    /// See [`MediaInfo`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let media = FileFormat::media_info_from_reader(std::io::empty())?;
+    /// assert_eq!(media, None);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn media_info_from_reader<R: Read + Seek>(reader: R) -> Result<Option<MediaInfo>> {
+        Ok(Self::detect_reader(reader)?.media)
+    }
+}
@@ -153,8 +153,12 @@ media_type_mapping! {
     format = Drawio
 
     media_type = "application/vnd.microsoft.portable-executable"
+    format = CabinetSelfExtractingArchive
     format = DynamicLinkLibrary
     format = PortableExecutable
+    format = RoshalSelfExtractingArchive
+    format = SevenZipSelfExtractingArchive
+    format = ZipSelfExtractingArchive
 
     media_type = "application/vnd.ms-appx"
     format = WindowsAppPackage
@@ -174,6 +178,18 @@ media_type_mapping! {
     media_type = "application/vnd.ms-excel"
     format = MicrosoftExcelSpreadsheet
 
+    media_type = "application/vnd.ms-excel.addin.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledSpreadsheetAddIn
+
+    media_type = "application/vnd.ms-excel.sheet.binary.macroenabled.12"
+    format = OfficeOpenXmlBinarySpreadsheet
+
+    media_type = "application/vnd.ms-excel.sheet.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledSpreadsheet
+
+    media_type = "application/vnd.ms-excel.template.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledSpreadsheetTemplate
+
     media_type = "application/vnd.ms-fontobject"
     format = EmbeddedOpentype
 
@@ -189,6 +205,18 @@ media_type_mapping! {
     media_type = "application/vnd.ms-powerpoint"
     format = MicrosoftPowerpointPresentation
 
+    media_type = "application/vnd.ms-powerpoint.addin.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledPresentationAddIn
+
+    media_type = "application/vnd.ms-powerpoint.presentation.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledPresentation
+
+    media_type = "application/vnd.ms-powerpoint.slideshow.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledSlideshow
+
+    media_type = "application/vnd.ms-powerpoint.template.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledPresentationTemplate
+
     media_type = "application/vnd.ms-project"
     format = MicrosoftProjectPlan
 
@@ -198,6 +226,12 @@ media_type_mapping! {
     media_type = "application/vnd.ms-visio.drawing.main+xml"
     format = OfficeOpenXmlDrawing
 
+    media_type = "application/vnd.ms-word.document.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledDocument
+
+    media_type = "application/vnd.ms-word.template.macroenabled.12"
+    format = OfficeOpenXmlMacroEnabledDocumentTemplate
+
     media_type = "application/vnd.ms-works"
     format = MicrosoftWorks6Spreadsheet
     format = MicrosoftWorksSpreadsheet
@@ -251,12 +285,24 @@ media_type_mapping! {
     media_type = "application/vnd.openxmlformats-officedocument.presentationml.presentation"
     format = OfficeOpenXmlPresentation
 
+    media_type = "application/vnd.openxmlformats-officedocument.presentationml.slideshow"
+    format = OfficeOpenXmlSlideshow
+
+    media_type = "application/vnd.openxmlformats-officedocument.presentationml.template"
+    format = OfficeOpenXmlPresentationTemplate
+
     media_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
     format = OfficeOpenXmlSpreadsheet
 
+    media_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.template"
+    format = OfficeOpenXmlSpreadsheetTemplate
+
     media_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
     format = OfficeOpenXmlDocument
 
+    media_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.template"
+    format = OfficeOpenXmlDocumentTemplate
+
     media_type = "application/vnd.rar"
     format = RoshalArchive
 
@@ -0,0 +1,251 @@
+//! Detection of every file format satisfied by a polyglot.
+
+use crate::{
+    dispatch::DISPATCH,
+    io::{Cursor, Read, Result, Seek, SeekFrom, Shifted},
+    readers::{FindBytes, ReadData},
+    Detection, Detector, FileFormat,
+};
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::{fs::File, path::Path};
+
+impl FileFormat {
+    /// Determines every file format satisfied by bytes.
+    ///
+    /// See [`FileFormat::all_matches_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let matches = FileFormat::all_matches_from_bytes(b"\x1F\x8B");
+    /// assert_eq!(matches, vec![FileFormat::Gzip]);
+    ///```
+    #[inline]
+    pub fn all_matches_from_bytes<B: AsRef<[u8]>>(bytes: B) -> Vec<Self> {
+        Self::all_matches(Cursor::new(bytes.as_ref())).unwrap_or_default()
+    }
+
+    /// Determines every file format satisfied by a file.
+    ///
+    /// See [`FileFormat::all_matches_from_reader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// use file_format::FileFormat;
+    ///
+    /// let matches = FileFormat::all_matches_from_file("upload.gif")?;
+    /// if matches.len() > 1 {
+    ///     println!("polyglot: {:?}", matches);
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn all_matches_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        Self::all_matches_from_reader(File::open(path)?)
+    }
+
+    /// Determines every file format satisfied by a reader.
+    ///
+    /// Unlike [`FileFormat::from_reader`], which stops at the first matching signature, every
+    /// structurally valid interpretation of the stream is reported, so that polyglots such as a
+    /// file being both a PDF and a ZIP, a GIF with an appended JAR or an executable with an
+    /// appended ZIP can be screened:
+    ///
+    /// - the file format determined by [`FileFormat::from_reader`] is listed first;
+    /// - every matching signature follows, each confirmed by its format reader if any, a
+    ///   signature whose format reader fails being discarded;
+    /// - the structures located at the end of the stream are checked last, i.e. a ZIP end of
+    ///   central directory record pointing to a central directory, and a PDF `%%EOF` trailer along
+    ///   with a `%PDF-` header within the first 1024 bytes.
+    ///
+    /// Each file format is listed once.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::FileFormat;
+    ///
+    /// let matches = FileFormat::all_matches_from_reader(std::io::empty())?;
+    /// assert_eq!(matches, vec![FileFormat::Empty]);
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn all_matches_from_reader<R: Read + Seek>(reader: R) -> Result<Vec<Self>> {
+        Self::all_matches(reader)
+    }
+
+    /// Determines every file format satisfied by a reader, whatever the I/O primitives in use.
+    fn all_matches<R: Read + Seek>(mut reader: R) -> Result<Vec<Self>> {
+        // Determines the file format as the regular detection does.
+        let detector = Detector::default();
+        let mut matches = vec![detector.detect(&mut reader)?.format];
+
+        // Creates and fills a buffer.
+        reader.seek(SeekFrom::Start(0))?;
+        let mut buf = vec![0; detector.probe_size];
+        let nread = reader.read(&mut buf)?;
+        let probe = &buf[..nread];
+
+        // Collects the file formats of every matching signature.
+        let mut matched = Vec::new();
+        for signature in DISPATCH.candidates(probe).filter(|sig| sig.matches(probe)) {
+            // Skips the file formats already matched by a longer signature.
+            if matched.contains(&signature.format) {
+                continue;
+            }
+            matched.push(signature.format);
+
+            // Confirms the match with the format reader, if any.
+            let fmt = if detector.reader(signature.format).is_some() {
+                let mut detection = Detection::new(signature.format);
+                match Self::from_fmt_reader(
+                    signature.format,
+                    &mut reader,
+                    &detector,
+                    &mut detection,
+                ) {
+                    Ok(fmt) => fmt,
+                    Err(_) => continue,
+                }
+            } else {
+                signature.format
+            };
+            push(&mut matches, fmt);
+        }
+
+        // Checks the structures located at the end of the stream.
+        if let Some(fmt) = Self::from_zip_tail(&mut reader, &detector)? {
+            push(&mut matches, fmt);
+        }
+        if let Some(fmt) = Self::from_pdf_tail(&mut reader, &detector, probe)? {
+            push(&mut matches, fmt);
+        }
+        Ok(matches)
+    }
+
+    /// Determines the ZIP-based file format of a ZIP located at the end of a reader, if any.
+    fn from_zip_tail<R: Read + Seek>(mut reader: R, detector: &Detector) -> Result<Option<Self>> {
+        // Signature of the end of central directory record.
+        const EOCD_SIGNATURE: &[u8] = b"PK\x05\x06";
+
+        // Signature of the central directory file header.
+        const CDFH_SIGNATURE: &[u8] = b"PK\x01\x02";
+
+        // Maximum size of the end of central directory record.
+        const EOCD_MAX_SIZE: u64 = EOCD_MIN_SIZE + u16::MAX as u64;
+
+        // Minimum size of the end of central directory record.
+        const EOCD_MIN_SIZE: u64 = 22;
+
+        // Searches for the end of central directory record.
+        let len = reader.seek(SeekFrom::End(0))?;
+        if len < EOCD_MIN_SIZE {
+            return Ok(None);
+        }
+        let offset = len.saturating_sub(EOCD_MAX_SIZE);
+        reader.seek(SeekFrom::Start(offset))?;
+        let buf = reader.read_bytes((len - offset) as usize)?;
+        let eocd = match buf.rfind(EOCD_SIGNATURE) {
+            Some(index) if buf.len() - index >= EOCD_MIN_SIZE as usize => &buf[index..],
+            _ => return Ok(None),
+        };
+        let eocd_offset = offset + (buf.len() - eocd.len()) as u64;
+
+        // Reads the number of entries, the size and the offset of the central directory.
+        let number_of_entries = u16::from_le_bytes([eocd[10], eocd[11]]);
+        let cd_size = u32::from_le_bytes([eocd[12], eocd[13], eocd[14], eocd[15]]);
+        let socd_offset = u32::from_le_bytes([eocd[16], eocd[17], eocd[18], eocd[19]]);
+
+        // Checks that the central directory precedes the record, unless it is a ZIP64 one.
+        let mut base = 0;
+        if number_of_entries != u16::MAX && cd_size != u32::MAX && socd_offset != u32::MAX {
+            let cd_offset = match eocd_offset.checked_sub(cd_size as u64) {
+                Some(cd_offset) => cd_offset,
+                None => return Ok(None),
+            };
+            if number_of_entries > 0 {
+                reader.seek(SeekFrom::Start(cd_offset))?;
+                if reader.read_bytes(CDFH_SIGNATURE.len())? != CDFH_SIGNATURE {
+                    return Ok(None);
+                }
+            }
+
+            // Offsets of a ZIP appended to another file may be relative to the start of the ZIP.
+            base = cd_offset.saturating_sub(socd_offset as u64);
+        }
+
+        // Determines the ZIP-based file format, if the reader is available.
+        if detector.reader(Self::Zip).is_none() {
+            return Ok(Some(Self::Zip));
+        }
+        let mut detection = Detection::new(Self::Zip);
+        Ok(Some(
+            Self::from_fmt_reader(
+                Self::Zip,
+                Shifted::new(reader, base),
+                detector,
+                &mut detection,
+            )
+            .unwrap_or(Self::Zip),
+        ))
+    }
+
+    /// Determines the PDF-based file format of a reader ending with a PDF trailer, if any.
+    fn from_pdf_tail<R: Read + Seek>(
+        mut reader: R,
+        detector: &Detector,
+        probe: &[u8],
+    ) -> Result<Option<Self>> {
+        // Number of bytes in which the header and the trailer are searched.
+        const SEARCH_SIZE: u64 = 1024;
+
+        // Checks the header.
+        if !probe[..core::cmp::min(probe.len(), SEARCH_SIZE as usize)].holds(b"%PDF-") {
+            return Ok(None);
+        }
+
+        // Checks the trailer.
+        let len = reader.seek(SeekFrom::End(0))?;
+        let offset = len.saturating_sub(SEARCH_SIZE);
+        reader.seek(SeekFrom::Start(offset))?;
+        if !reader.read_bytes((len - offset) as usize)?.holds(b"%%EOF") {
+            return Ok(None);
+        }
+
+        // Determines the PDF-based file format, if the reader is available.
+        if detector.reader(Self::PortableDocumentFormat).is_none() {
+            return Ok(Some(Self::PortableDocumentFormat));
+        }
+        let mut detection = Detection::new(Self::PortableDocumentFormat);
+        Ok(Some(
+            Self::from_fmt_reader(
+                Self::PortableDocumentFormat,
+                reader,
+                detector,
+                &mut detection,
+            )
+            .unwrap_or(Self::PortableDocumentFormat),
+        ))
+    }
+}
+
+/// Appends a file format to the matches, unless it is already listed.
+#[inline]
+fn push(matches: &mut Vec<FileFormat>, fmt: FileFormat) {
+    if !matches.contains(&fmt) {
+        matches.push(fmt);
+    }
+}
@@ -1,57 +1,165 @@
 //! Readers for specific file formats.
 
-use std::io::*;
+use crate::{io::*, Detection, Detector};
+#[cfg(any(
+    feature = "reader-asf",
+    feature = "reader-ebml",
+    feature = "reader-mp4",
+    feature = "reader-rm"
+))]
+use crate::{MediaInfo, Track, TrackKind};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+use core::ops::Range;
+#[cfg(any(
+    feature = "reader-asf",
+    feature = "reader-ebml",
+    feature = "reader-mp4",
+    feature = "reader-rm"
+))]
+use core::time::Duration;
+
+/// Maximum size of a `[Content_Types].xml` ZIP entry that can be processed by the reader.
+#[cfg(feature = "reader-zip")]
+const CONTENT_TYPES_LIMIT: usize = 65536;
+
+/// A reader used to determine a file format more accurately than its signature.
+///
+/// Format readers are only available when their respective `reader-*` feature is enabled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "extended-enums",
+    derive(strum::EnumIter, strum::Display, strum::AsRefStr, strum::FromRepr)
+)]
+pub enum Reader {
+    /// [Advanced Systems Format (ASF)](`crate::FileFormat::AdvancedSystemsFormat`) reader.
+    Asf,
+    /// [Compound File Binary (CFB)](`crate::FileFormat::CompoundFileBinary`) reader.
+    Cfb,
+    /// [Extensible Binary Meta Language (EBML)](`crate::FileFormat::ExtensibleBinaryMetaLanguage`)
+    /// reader.
+    Ebml,
+    /// [Executable and Linkable Format (ELF)](`crate::FileFormat::ExecutableAndLinkableFormat`)
+    /// reader.
+    Elf,
+    /// [MS-DOS Executable (EXE)](`crate::FileFormat::MsDosExecutable`) reader.
+    Exe,
+    /// [MPEG-4 Part 14 (MP4)](`crate::FileFormat::Mpeg4Part14`) reader.
+    Mp4,
+    /// [Portable Document Format (PDF)](`crate::FileFormat::PortableDocumentFormat`) reader.
+    Pdf,
+    /// [RealMedia (RM)](`crate::FileFormat::Realmedia`) reader.
+    Rm,
+    /// [SQLite 3](`crate::FileFormat::Sqlite3`) reader.
+    Sqlite3,
+    /// [Plain Text (TXT)](`crate::FileFormat::PlainText`) reader, used as the generic reader.
+    Txt,
+    /// [Extensible Markup Language (XML)](`crate::FileFormat::ExtensibleMarkupLanguage`) reader.
+    Xml,
+    /// [ZIP](`crate::FileFormat::Zip`) reader.
+    Zip,
+}
+
+impl Reader {
+    /// Returns the format reader available for the specified file format, if any.
+    #[inline]
+    pub(crate) fn from_format(fmt: crate::FileFormat) -> Option<Self> {
+        match fmt {
+            #[cfg(feature = "reader-asf")]
+            crate::FileFormat::AdvancedSystemsFormat => Some(Self::Asf),
+            #[cfg(feature = "reader-cfb")]
+            crate::FileFormat::CompoundFileBinary => Some(Self::Cfb),
+            #[cfg(feature = "reader-ebml")]
+            crate::FileFormat::ExtensibleBinaryMetaLanguage => Some(Self::Ebml),
+            #[cfg(feature = "reader-elf")]
+            crate::FileFormat::ExecutableAndLinkableFormat => Some(Self::Elf),
+            #[cfg(feature = "reader-exe")]
+            crate::FileFormat::MsDosExecutable => Some(Self::Exe),
+            #[cfg(feature = "reader-mp4")]
+            crate::FileFormat::Mpeg4Part14 => Some(Self::Mp4),
+            #[cfg(feature = "reader-pdf")]
+            crate::FileFormat::PortableDocumentFormat => Some(Self::Pdf),
+            #[cfg(feature = "reader-rm")]
+            crate::FileFormat::Realmedia => Some(Self::Rm),
+            #[cfg(feature = "reader-sqlite3")]
+            crate::FileFormat::Sqlite3 => Some(Self::Sqlite3),
+            #[cfg(feature = "reader-xml")]
+            crate::FileFormat::ExtensibleMarkupLanguage => Some(Self::Xml),
+            #[cfg(feature = "reader-zip")]
+            crate::FileFormat::Zip => Some(Self::Zip),
+            _ => None,
+        }
+    }
+}
 
 impl crate::FileFormat {
-    /// Determines file format from the specified format reader, if any.
+    /// Determines file format from the specified format reader, if it is available and enabled.
     #[inline]
     pub(crate) fn from_fmt_reader<R: Read + Seek>(
         fmt: Self,
         #[allow(unused_variables)] reader: R,
+        #[allow(unused_variables)] detector: &Detector,
+        detection: &mut Detection,
     ) -> Result<Self> {
-        Ok(match fmt {
+        detection.reader = detector.reader(fmt);
+        Ok(match detection.reader {
             #[cfg(feature = "reader-asf")]
-            Self::AdvancedSystemsFormat => Self::from_asf_reader(reader)?,
+            Some(Reader::Asf) => Self::from_asf_reader(reader, detection)?,
             #[cfg(feature = "reader-cfb")]
-            Self::CompoundFileBinary => Self::from_cfb_reader(reader)?,
+            Some(Reader::Cfb) => Self::from_cfb_reader(reader, detection)?,
             #[cfg(feature = "reader-ebml")]
-            Self::ExtensibleBinaryMetaLanguage => Self::from_ebml_reader(reader)?,
+            Some(Reader::Ebml) => Self::from_ebml_reader(reader, detector, detection)?,
+            #[cfg(feature = "reader-elf")]
+            Some(Reader::Elf) => Self::from_elf_reader(reader, detector, detection)?,
             #[cfg(feature = "reader-exe")]
-            Self::MsDosExecutable => Self::from_exe_reader(reader)?,
+            Some(Reader::Exe) => Self::from_exe_reader(reader, detector, detection)?,
             #[cfg(feature = "reader-mp4")]
-            Self::Mpeg4Part14 => Self::from_mp4_reader(reader)?,
+            Some(Reader::Mp4) => Self::from_mp4_reader(reader, detector, detection)?,
             #[cfg(feature = "reader-pdf")]
-            Self::PortableDocumentFormat => Self::from_pdf_reader(reader)?,
+            Some(Reader::Pdf) => Self::from_pdf_reader(reader, detector, detection)?,
             #[cfg(feature = "reader-rm")]
-            Self::Realmedia => Self::from_rm_reader(reader)?,
+            Some(Reader::Rm) => Self::from_rm_reader(reader, detection)?,
             #[cfg(feature = "reader-sqlite3")]
-            Self::Sqlite3 => Self::from_sqlite3_reader(reader)?,
+            Some(Reader::Sqlite3) => Self::from_sqlite3_reader(reader, detection)?,
             #[cfg(feature = "reader-xml")]
-            Self::ExtensibleMarkupLanguage => Self::from_xml_reader(reader)?,
+            Some(Reader::Xml) => Self::from_xml_reader(reader, detection)?,
             #[cfg(feature = "reader-zip")]
-            Self::Zip => Self::from_zip_reader(reader)?,
+            Some(Reader::Zip) => Self::from_zip_reader(reader, detector, detection)?,
             _ => fmt,
         })
     }
 
-    /// Determines file format from a generic reader.
+    /// Determines file format from the generic reader, if it is available and enabled.
     #[inline]
     pub(crate) fn from_generic_reader<R: Read + Seek>(
         #[allow(unused_variables)] reader: R,
+        #[allow(unused_variables)] detector: &Detector,
+        #[allow(unused_variables)] detection: &mut Detection,
     ) -> Self {
         #[cfg(feature = "reader-txt")]
-        {
-            Self::from_txt_reader(reader).unwrap_or_default()
-        }
-        #[cfg(not(feature = "reader-txt"))]
-        {
-            Self::default()
+        if detector.is_enabled(Reader::Txt) {
+            return Self::from_txt_reader(reader, detector)
+                .map(|fmt| {
+                    detection.reader = Some(Reader::Txt);
+                    fmt
+                })
+                .unwrap_or_default();
         }
+        Self::default()
     }
 
     /// Determines file format from an ASF reader.
     #[cfg(feature = "reader-asf")]
-    pub(crate) fn from_asf_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+    pub(crate) fn from_asf_reader<R: Read + Seek>(
+        reader: R,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Maximum number of descriptors that can be processed by the reader.
         const DESCRIPTOR_LIMIT: usize = 32;
 
@@ -65,6 +173,9 @@ impl crate::FileFormat {
         const EXTENDED_CONTENT_DESCRIPTION_OBJECT_GUID: &str =
             "d2d0a440-e307-11d2-97f0-00a0c95ea850";
 
+        // GUID for file properties object.
+        const FILE_PROPERTIES_OBJECT_GUID: &str = "8cabdca1-a947-11cf-8ee4-00c00c205365";
+
         // GUID for stream properties object.
         const STREAM_PROPERTIES_OBJECT_GUID: &str = "b7dc0791-a9b7-11cf-8ee6-00c00c205365";
 
@@ -88,8 +199,11 @@ impl crate::FileFormat {
         let mut audio_stream = false;
         let mut video_stream = false;
 
+        // Duration and streams of the media.
+        let mut media = MediaInfo::default();
+
         // Iterates through the header objects.
-        for _ in 0..std::cmp::min(OBJECT_LIMIT, number_of_header_objects as usize) {
+        for _ in 0..core::cmp::min(OBJECT_LIMIT, number_of_header_objects as usize) {
             // Reads the object GUID.
             let guid = reader.read_guid()?;
 
@@ -103,15 +217,44 @@ impl crate::FileFormat {
                     let stream_type = reader.read_guid()?;
 
                     // Checks the stream type.
-                    match stream_type.as_str() {
-                        AUDIO_MEDIA_GUID => audio_stream = true,
-                        VIDEO_MEDIA_GUID => video_stream = true,
-                        _ => {}
-                    }
+                    let kind = match stream_type.as_str() {
+                        AUDIO_MEDIA_GUID => {
+                            audio_stream = true;
+                            TrackKind::Audio
+                        }
+                        VIDEO_MEDIA_GUID => {
+                            video_stream = true;
+                            TrackKind::Video
+                        }
+                        _ => TrackKind::Other,
+                    };
+                    media.tracks.push(Track {
+                        codec: Some(stream_type),
+                        ..Track::new(kind)
+                    });
 
                     // Seeks to the next object.
                     reader.seek(SeekFrom::Current(size as i64 - 40))?;
                 }
+                FILE_PROPERTIES_OBJECT_GUID => {
+                    // Reads the play duration, the preroll and the flags.
+                    reader.seek(SeekFrom::Current(40))?;
+                    let play_duration = reader.read_u64_le()?;
+                    reader.seek(SeekFrom::Current(8))?;
+                    let preroll = reader.read_u64_le()?;
+                    let flags = reader.read_u32_le()?;
+
+                    // Computes the duration, unless the file is being broadcast.
+                    if flags & 0x01 == 0 {
+                        media.duration = Some(
+                            Duration::from_nanos(play_duration.saturating_mul(100))
+                                .saturating_sub(Duration::from_millis(preroll)),
+                        );
+                    }
+
+                    // Seeks to the next object.
+                    reader.seek(SeekFrom::Current(size as i64 - 92))?;
+                }
                 EXTENDED_CONTENT_DESCRIPTION_OBJECT_GUID => {
                     // Reads the content descriptors count.
                     let count = reader.read_u16_le()?;
@@ -120,17 +263,18 @@ impl crate::FileFormat {
                     let offset = reader.stream_position()?;
 
                     // Iterates through the content descriptors.
-                    for _ in 0..std::cmp::min(DESCRIPTOR_LIMIT, count as usize) {
+                    for _ in 0..core::cmp::min(DESCRIPTOR_LIMIT, count as usize) {
                         // Reads the descriptor name length.
                         let len = reader.read_u16_le()?;
 
                         // Reads the descriptor name.
                         let name = reader
-                            .read_bytes(std::cmp::min(DESCRIPTOR_NAME_LIMIT, len as usize))?;
+                            .read_bytes(core::cmp::min(DESCRIPTOR_NAME_LIMIT, len as usize))?;
 
                         // Checks the descriptor name.
                         if name.starts_with(b"D\0V\0R\0 \0F\0i\0l\0e\0 \0V\0e\0r\0s\0i\0o\0n\0") {
-                            return Ok(Self::MicrosoftDigitalVideoRecording);
+                            return Ok(detection
+                                .mark("DVR File Version", Self::MicrosoftDigitalVideoRecording));
                         }
 
                         // Calculates the remaining length.
@@ -155,10 +299,11 @@ impl crate::FileFormat {
         }
 
         // Determines the file format based on the identified streams.
+        detection.media = Some(media);
         Ok(if video_stream {
-            Self::WindowsMediaVideo
+            detection.mark(VIDEO_MEDIA_GUID, Self::WindowsMediaVideo)
         } else if audio_stream {
-            Self::WindowsMediaAudio
+            detection.mark(AUDIO_MEDIA_GUID, Self::WindowsMediaAudio)
         } else {
             Self::AdvancedSystemsFormat
         })
@@ -166,7 +311,10 @@ impl crate::FileFormat {
 
     /// Determines file format from a CFB reader.
     #[cfg(feature = "reader-cfb")]
-    pub(crate) fn from_cfb_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+    pub(crate) fn from_cfb_reader<R: Read + Seek>(
+        mut reader: R,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Reads the major version.
         reader.seek(SeekFrom::Start(26))?;
         let major_version = reader.read_u16_le()?;
@@ -178,6 +326,15 @@ impl crate::FileFormat {
         reader.seek(SeekFrom::Current(20))?;
         let first_directory_sector_location = reader.read_u32_le()?;
 
+        // Checks whether it holds a VBA project, a broken chain of sectors not preventing the
+        // detection.
+        detection.has_macros = Self::has_cfb_macros(
+            &mut reader,
+            directory_sector_size,
+            first_directory_sector_location,
+        )
+        .unwrap_or(false);
+
         // Seeks to the root entry CLSID.
         let offset = directory_sector_size * (1 + first_directory_sector_location as u64) + 80;
         reader.seek(SeekFrom::Start(offset))?;
@@ -186,7 +343,7 @@ impl crate::FileFormat {
         let clsid = reader.read_guid()?;
 
         // Determines the file format based on the CLSID.
-        Ok(match clsid.as_str() {
+        let fmt = match clsid.as_str() {
             "e60f81e1-49b3-11d0-93c3-7e0706000000" => Self::AutodeskInventorAssembly,
             "bbf9fdf1-52dc-11d0-8c04-0800090be8ec" => Self::AutodeskInventorDrawing,
             "4d29b490-49b2-11d0-93c3-7e0706000000" => Self::AutodeskInventorPart,
@@ -240,7 +397,7 @@ impl crate::FileFormat {
 
                 // Checks the second directory entry name.
                 if directory_entry_name.starts_with(b"M\0a\0t\0O\0S\0T\0") {
-                    return Ok(Self::MicrosoftWorksWordProcessor);
+                    return Ok(detection.mark("MatOST", Self::MicrosoftWorksWordProcessor));
                 }
 
                 // Reads the third directory entry name.
@@ -249,21 +406,135 @@ impl crate::FileFormat {
 
                 // Checks the third directory entry name.
                 if directory_entry_name.starts_with(b"W\0k\0s\0S\0S\0W\0o\0r\0k\0B\0o\0o\0k\0") {
-                    return Ok(Self::MicrosoftWorks6Spreadsheet);
+                    return Ok(detection.mark("WksSSWorkBook", Self::MicrosoftWorks6Spreadsheet));
                 }
 
+                // Checks whether it is an encrypted Office document, a broken chain of sectors
+                // not preventing the detection.
+                detection.encrypted = Self::is_cfb_encrypted(
+                    &mut reader,
+                    directory_sector_size,
+                    first_directory_sector_location,
+                )
+                .unwrap_or(false);
+
                 // Returns the default value.
-                Self::CompoundFileBinary
+                return Ok(Self::CompoundFileBinary);
             }
-        })
+        };
+
+        // Returns the file format identified by the CLSID.
+        Ok(detection.mark(clsid, fmt))
+    }
+
+    /// Returns `true` if a CFB holds the streams of an Office document encrypted with the ECMA-376
+    /// encryption, i.e. `EncryptionInfo` and `EncryptedPackage`.
+    #[cfg(feature = "reader-cfb")]
+    fn is_cfb_encrypted<R: Read + Seek>(
+        reader: R,
+        sector_size: u64,
+        first_directory_sector_location: u32,
+    ) -> Result<bool> {
+        // Names of the streams of an encrypted Office document.
+        const ENCRYPTED_PACKAGE_NAME: &[u8] =
+            b"E\0n\0c\0r\0y\0p\0t\0e\0d\0P\0a\0c\0k\0a\0g\0e\0\0\0";
+        const ENCRYPTION_INFO_NAME: &[u8] = b"E\0n\0c\0r\0y\0p\0t\0i\0o\0n\0I\0n\0f\0o\0\0\0";
+
+        // Checks the names of the directory entries.
+        let mut encrypted_package = false;
+        let mut encryption_info = false;
+        Self::browse_cfb_directory(
+            reader,
+            sector_size,
+            first_directory_sector_location,
+            |name| {
+                encrypted_package |= name.starts_with(ENCRYPTED_PACKAGE_NAME);
+                encryption_info |= name.starts_with(ENCRYPTION_INFO_NAME);
+                encrypted_package && encryption_info
+            },
+        )
+    }
+
+    /// Returns `true` if a CFB holds the storage of the VBA project of an Office document, i.e.
+    /// `Macros` for a Word document or `_VBA_PROJECT_CUR` for an Excel workbook.
+    #[cfg(feature = "reader-cfb")]
+    fn has_cfb_macros<R: Read + Seek>(
+        reader: R,
+        sector_size: u64,
+        first_directory_sector_location: u32,
+    ) -> Result<bool> {
+        // Names of the storages of a VBA project.
+        const MACROS_NAME: &[u8] = b"M\0a\0c\0r\0o\0s\0\0\0";
+        const VBA_PROJECT_CUR_NAME: &[u8] = b"_\0V\0B\0A\0_\0P\0R\0O\0J\0E\0C\0T\0_\0C\0U\0R\0\0\0";
+
+        // Checks the names of the directory entries.
+        Self::browse_cfb_directory(
+            reader,
+            sector_size,
+            first_directory_sector_location,
+            |name| name.starts_with(MACROS_NAME) || name.starts_with(VBA_PROJECT_CUR_NAME),
+        )
+    }
+
+    /// Browses the names of the directory entries of a CFB, following the chain of directory
+    /// sectors, until the callback returns `true`, in which case `true` is returned.
+    #[cfg(feature = "reader-cfb")]
+    fn browse_cfb_directory<R: Read + Seek, F: FnMut(&[u8]) -> bool>(
+        mut reader: R,
+        sector_size: u64,
+        first_directory_sector_location: u32,
+        mut callback: F,
+    ) -> Result<bool> {
+        // Maximum number of directory sectors that can be processed by the reader.
+        const DIRECTORY_SECTOR_LIMIT: usize = 16;
+
+        // Size of a directory entry.
+        const DIRECTORY_ENTRY_SIZE: u64 = 128;
+
+        // Location marking the end of a chain of sectors.
+        const END_OF_CHAIN: u32 = 0xFFFFFFFE;
+
+        // Iterates through the chain of directory sectors.
+        let mut location = first_directory_sector_location;
+        for _ in 0..DIRECTORY_SECTOR_LIMIT {
+            // Checks the names of the directory entries of the sector.
+            let offset = sector_size * (1 + location as u64);
+            for index in 0..sector_size / DIRECTORY_ENTRY_SIZE {
+                reader.seek(SeekFrom::Start(offset + index * DIRECTORY_ENTRY_SIZE))?;
+                if callback(&reader.read_bytes(64)?) {
+                    return Ok(true);
+                }
+            }
+
+            // Reads the location of the FAT sector holding the next location, from the header.
+            let entries_per_sector = (sector_size / 4) as u32;
+            let fat_sector_index = location / entries_per_sector;
+            if fat_sector_index >= 109 {
+                break;
+            }
+            reader.seek(SeekFrom::Start(76 + 4 * fat_sector_index as u64))?;
+            let fat_sector_location = reader.read_u32_le()?;
+
+            // Reads the location of the next directory sector.
+            reader.seek(SeekFrom::Start(
+                sector_size * (1 + fat_sector_location as u64)
+                    + 4 * (location % entries_per_sector) as u64,
+            ))?;
+            location = reader.read_u32_le()?;
+            if location == END_OF_CHAIN {
+                break;
+            }
+        }
+        Ok(false)
     }
 
     /// Determines file format from an EBML reader.
     #[cfg(feature = "reader-ebml")]
-    pub(crate) fn from_ebml_reader<R: Read + Seek>(reader: R) -> Result<Self> {
-        // Maximum number of EBML elements that can be processed by the reader.
-        const ELEMENT_LIMIT: usize = 256;
-
+    pub(crate) fn from_ebml_reader<R: Read + Seek>(
+        reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Maximum size of a Codec ID that can be processed by the reader.
         const CODEC_ID_LIMIT: usize = 64;
 
@@ -273,6 +544,9 @@ impl crate::FileFormat {
         // DocType element ID.
         const DOC_TYPE_ELEMENT_ID: u32 = 0x4282;
 
+        // Duration element ID.
+        const DURATION_ELEMENT_ID: u32 = 0x4489;
+
         // EBML element ID.
         const EBML_ELEMENT_ID: u32 = 0x1A45DFA3;
 
@@ -282,12 +556,18 @@ impl crate::FileFormat {
         // CodecID element ID.
         const CODEC_ID_ELEMENT_ID: u32 = 0x86;
 
+        // Info element ID.
+        const INFO_ELEMENT_ID: u32 = 0x1549A966;
+
         // Segment element ID.
         const SEGMENT_ELEMENT_ID: u32 = 0x18538067;
 
         // StereoMode element ID.
         const STEREO_MODE_ELEMENT_ID: u32 = 0x53B8;
 
+        // TimestampScale element ID.
+        const TIMESTAMP_SCALE_ELEMENT_ID: u32 = 0x2AD7B1;
+
         // Tracks element ID.
         const TRACKS_ELEMENT_ID: u32 = 0x1654AE6B;
 
@@ -306,14 +586,23 @@ impl crate::FileFormat {
         // Rewinds to the beginning of the stream.
         reader.rewind()?;
 
-        // Flags indicating the presence of audio, video and subtitle tracks.
-        let mut audio_track = false;
-        let mut video_track = false;
-        let mut subtitle_track = false;
+        // Codec IDs of the first audio, video and subtitle tracks.
+        let mut audio_track = None;
+        let mut video_track = None;
+        let mut subtitle_track = None;
+
+        // DocType, if it is a WebM one, and whether the video is stereoscopic.
+        let mut webm = None;
+        let mut stereoscopic = false;
+
+        // Duration, in units of the timestamp scale, and tracks of the media.
+        let mut duration = None;
+        let mut timestamp_scale = 1_000_000;
+        let mut media = MediaInfo::default();
 
         // Iterates through the EBML elements.
         let mut element_count = 0;
-        while element_count < ELEMENT_LIMIT && reader.stream_position()? < len {
+        while element_count < detector.ebml_element_limit && reader.stream_position()? < len {
             // Reads the first byte of the element ID.
             let first_byte = reader.read_u8()?;
 
@@ -348,19 +637,17 @@ impl crate::FileFormat {
 
             // Checks the element ID.
             match id {
-                EBML_ELEMENT_ID
-                | SEGMENT_ELEMENT_ID
-                | TRACKS_ELEMENT_ID
-                | TRACK_ENTRY_ELEMENT_ID
+                EBML_ELEMENT_ID | INFO_ELEMENT_ID | SEGMENT_ELEMENT_ID | TRACKS_ELEMENT_ID
                 | VIDEO_ELEMENT_ID => {}
+                TRACK_ENTRY_ELEMENT_ID => media.tracks.push(Track::new(TrackKind::Other)),
                 DOC_TYPE_ELEMENT_ID => {
                     // Reads the DocType.
                     let doc_type =
-                        reader.read_bytes(std::cmp::min(DOC_TYPE_LIMIT, size as usize))?;
+                        reader.read_bytes(core::cmp::min(DOC_TYPE_LIMIT, size as usize))?;
 
                     // Checks the DocType.
                     if doc_type.starts_with(b"webm") {
-                        return Ok(Self::Webm);
+                        webm = Some(doc_type);
                     } else if !doc_type.starts_with(b"matroska") {
                         return Ok(Self::ExtensibleBinaryMetaLanguage);
                     }
@@ -372,15 +659,28 @@ impl crate::FileFormat {
                 CODEC_ID_ELEMENT_ID => {
                     // Reads the Codec ID.
                     let codec_id =
-                        reader.read_bytes(std::cmp::min(CODEC_ID_LIMIT, size as usize))?;
+                        reader.read_bytes(core::cmp::min(CODEC_ID_LIMIT, size as usize))?;
+
+                    // Records the Codec ID of the current track.
+                    if let Some(track) = media.tracks.last_mut() {
+                        track.codec = Some(String::from_utf8_lossy(&codec_id).into_owned());
+                    }
 
                     // Checks the Codec ID.
-                    if codec_id.starts_with(b"A_") {
-                        audio_track = true;
+                    let kind = if codec_id.starts_with(b"A_") {
+                        audio_track.get_or_insert(codec_id);
+                        TrackKind::Audio
                     } else if codec_id.starts_with(b"V_") {
-                        video_track = true;
+                        video_track.get_or_insert(codec_id);
+                        TrackKind::Video
                     } else if codec_id.starts_with(b"S_") {
-                        subtitle_track = true;
+                        subtitle_track.get_or_insert(codec_id);
+                        TrackKind::Subtitle
+                    } else {
+                        TrackKind::Other
+                    };
+                    if let Some(track) = media.tracks.last_mut() {
+                        track.kind = kind;
                     }
 
                     // Skips the remaining size.
@@ -393,9 +693,24 @@ impl crate::FileFormat {
 
                     // Checks the StereoMode.
                     if stereo_mode > 0 {
-                        return Ok(Self::Matroska3dVideo);
+                        stereoscopic = true;
                     }
                 }
+                TIMESTAMP_SCALE_ELEMENT_ID if size <= 8 => {
+                    // Reads the TimestampScale, in nanoseconds.
+                    timestamp_scale = reader
+                        .read_bytes(size as usize)?
+                        .iter()
+                        .fold(0, |value, &byte| value << 8 | byte as u64);
+                }
+                DURATION_ELEMENT_ID if size == 4 || size == 8 => {
+                    // Reads the Duration, stored as a floating-point number.
+                    duration = Some(if size == 4 {
+                        f32::from_bits(reader.read_u32_be()?) as f64
+                    } else {
+                        f64::from_bits(reader.read_u64_be()?)
+                    });
+                }
                 CLUSTER_ELEMENT_ID => break,
                 _ => {
                     // Seeks to the next element.
@@ -407,21 +722,63 @@ impl crate::FileFormat {
             element_count += 1;
         }
 
+        // Computes the duration, in nanoseconds.
+        media.duration = duration
+            .map(|duration| duration * timestamp_scale as f64)
+            .filter(|nanos| (0.0..u64::MAX as f64).contains(nanos))
+            .map(|nanos| Duration::from_nanos(nanos as u64));
+        detection.media = Some(media);
+
+        // Checks the DocType and the StereoMode.
+        if let Some(doc_type) = webm {
+            return Ok(detection.mark(doc_type, Self::Webm));
+        } else if stereoscopic {
+            return Ok(detection.mark("StereoMode", Self::Matroska3dVideo));
+        }
+
+        // Records whether the elements were not all browsed before finding a video track.
+        if video_track.is_none()
+            && element_count == detector.ebml_element_limit
+            && reader.stream_position()? < len
+        {
+            detection.limit_exceeded = Some("ebml_element_limit");
+        }
+
         // Determines the file format based on the identified tracks.
-        Ok(if video_track {
-            Self::MatroskaVideo
-        } else if audio_track {
-            Self::MatroskaAudio
-        } else if subtitle_track {
-            Self::MatroskaSubtitles
+        Ok(if let Some(codec_id) = video_track {
+            detection.mark(codec_id, Self::MatroskaVideo)
+        } else if let Some(codec_id) = audio_track {
+            detection.mark(codec_id, Self::MatroskaAudio)
+        } else if let Some(codec_id) = subtitle_track {
+            detection.mark(codec_id, Self::MatroskaSubtitles)
         } else {
             Self::ExtensibleBinaryMetaLanguage
         })
     }
 
+    /// Determines file format from an ELF reader.
+    #[cfg(feature = "reader-elf")]
+    pub(crate) fn from_elf_reader<R: Read + Seek>(
+        mut reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
+        // Detects the overlay, if the headers can be read.
+        if let Ok(end) = Self::elf_image_end(&mut reader) {
+            detection.overlay = Self::from_overlay(&mut reader, detector, end..u64::MAX)?;
+        }
+
+        // Returns the default value.
+        Ok(Self::ExecutableAndLinkableFormat)
+    }
+
     /// Determines file format from an EXE reader.
     #[cfg(feature = "reader-exe")]
-    pub(crate) fn from_exe_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+    pub(crate) fn from_exe_reader<R: Read + Seek>(
+        mut reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Retrieves the stream length.
         let len = reader.seek(SeekFrom::End(0))?;
 
@@ -443,16 +800,34 @@ impl crate::FileFormat {
                 reader.seek(SeekFrom::Current(18))?;
                 let characteristics = reader.read_u16_le()?;
 
+                // Detects the overlay, if the headers can be read.
+                if let Ok(overlay) = Self::pe_overlay_bounds(&mut reader, offset as u64) {
+                    detection.overlay = Self::from_overlay(&mut reader, detector, overlay)?;
+                }
+
                 // Checks the characteristics
-                return Ok(if characteristics & 0x2000 == 0x2000 {
-                    Self::DynamicLinkLibrary
-                } else {
-                    Self::PortableExecutable
+                if characteristics & 0x2000 == 0x2000 {
+                    return Ok(detection.mark("IMAGE_FILE_DLL", Self::DynamicLinkLibrary));
+                }
+
+                // Checks the archive appended to the image, if any.
+                return Ok(match detection.overlay {
+                    Some((_, Self::Cabinet)) => {
+                        detection.mark("PE", Self::CabinetSelfExtractingArchive)
+                    }
+                    Some((_, Self::RoshalArchive)) => {
+                        detection.mark("PE", Self::RoshalSelfExtractingArchive)
+                    }
+                    Some((_, Self::SevenZip)) => {
+                        detection.mark("PE", Self::SevenZipSelfExtractingArchive)
+                    }
+                    Some((_, Self::Zip)) => detection.mark("PE", Self::ZipSelfExtractingArchive),
+                    _ => detection.mark("PE", Self::PortableExecutable),
                 });
             } else if &signature[..2] == b"LE" || &signature[..2] == b"LX" {
-                return Ok(Self::LinearExecutable);
+                return Ok(detection.mark(&signature[..2], Self::LinearExecutable));
             } else if &signature[..2] == b"NE" {
-                return Ok(Self::NewExecutable);
+                return Ok(detection.mark("NE", Self::NewExecutable));
             }
         }
 
@@ -462,10 +837,11 @@ impl crate::FileFormat {
 
     /// Determines file format from a MP4 reader.
     #[cfg(feature = "reader-mp4")]
-    pub(crate) fn from_mp4_reader<R: Read + Seek>(reader: R) -> Result<Self> {
-        // Maximum number of boxes that can be processed by the reader.
-        const BOX_LIMIT: usize = 256;
-
+    pub(crate) fn from_mp4_reader<R: Read + Seek>(
+        reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Creates a buffered reader.
         let mut reader = BufReader::new(reader);
 
@@ -475,14 +851,20 @@ impl crate::FileFormat {
         // Rewinds to the beginning of the stream.
         reader.rewind()?;
 
-        // Flags indicating the presence of audio, video and subtitle tracks.
-        let mut audio_track = false;
-        let mut video_track = false;
-        let mut subtitle_track = false;
+        // Handler types of the first audio, video and subtitle tracks.
+        let mut audio_track = None;
+        let mut video_track = None;
+        let mut subtitle_track = None;
+
+        // Duration and tracks of the media, along with whether the handler of the last track is
+        // yet to be read and whether any track box was found.
+        let mut media = MediaInfo::default();
+        let mut pending_handler = false;
+        let mut track_found = false;
 
         // Iterates through boxes.
         let mut box_count = 0;
-        while box_count < BOX_LIMIT && reader.stream_position()? < len {
+        while box_count < detector.mp4_box_limit && reader.stream_position()? < len {
             // Reads the box size.
             let size = reader.read_u32_be()?;
 
@@ -498,26 +880,126 @@ impl crate::FileFormat {
 
             // Checks the box type.
             match box_type.as_slice() {
-                b"moov" | b"trak" | b"mdia" => {}
+                b"moov" | b"mdia" | b"minf" | b"stbl" => {}
+                b"trak" => {
+                    media.tracks.push(Track::new(TrackKind::Other));
+                    pending_handler = true;
+                    track_found = true;
+                }
                 b"hdlr" => {
                     // Reads the handler type.
                     reader.seek(SeekFrom::Current(8))?;
                     let handler_type = reader.read_bytes(4)?;
 
                     // Checks the handler type.
-                    match handler_type.as_slice() {
-                        b"vide" => video_track = true,
-                        b"soun" => audio_track = true,
-                        b"sbtl" | b"subt" | b"text" => subtitle_track = true,
-                        _ => {}
+                    let kind = match handler_type.as_slice() {
+                        b"vide" => {
+                            video_track.get_or_insert(handler_type);
+                            TrackKind::Video
+                        }
+                        b"soun" => {
+                            audio_track.get_or_insert(handler_type);
+                            TrackKind::Audio
+                        }
+                        b"sbtl" | b"subt" | b"text" => {
+                            subtitle_track.get_or_insert(handler_type);
+                            TrackKind::Subtitle
+                        }
+                        _ => TrackKind::Other,
+                    };
+
+                    // Records the kind of the current track, the handler of a media information
+                    // box being a data handler.
+                    if pending_handler {
+                        if let Some(track) = media.tracks.last_mut() {
+                            track.kind = kind;
+                        }
+                        pending_handler = false;
+                    } else if !track_found {
+                        media.tracks.push(Track::new(kind));
                     }
 
-                    // Seeks to the next box.
-                    reader.seek(SeekFrom::Current(size as i64 - 20))?;
+                    // Seeks to the next box, unless the box extends to the end of the stream or its size is
+                    // invalid.
+                    match size {
+                        0 => break,
+                        1..=19 if detector.strict => {
+                            return Err(Error::new(ErrorKind::InvalidData, "invalid MP4 box size"))
+                        }
+                        1..=19 => break,
+                        _ => reader.seek(SeekFrom::Current(size as i64 - 20))?,
+                    };
+                }
+                b"mvhd" => {
+                    // Reads the time scale and the duration.
+                    let version = reader.read_u8()?;
+                    let (time_scale, duration, header_size) = if version == 1 {
+                        reader.seek(SeekFrom::Current(19))?;
+                        let time_scale = reader.read_u32_be()?;
+                        (time_scale, reader.read_u64_be()?, 40)
+                    } else {
+                        reader.seek(SeekFrom::Current(11))?;
+                        let time_scale = reader.read_u32_be()?;
+                        (time_scale, reader.read_u32_be()? as u64, 28)
+                    };
+
+                    // Computes the duration, unless it is unknown.
+                    if time_scale > 0 && duration != u64::MAX && duration != u32::MAX as u64 {
+                        let time_scale = time_scale as u64;
+                        media.duration = Some(
+                            Duration::from_secs(duration / time_scale)
+                                + Duration::from_nanos(
+                                    duration % time_scale * 1_000_000_000 / time_scale,
+                                ),
+                        );
+                    }
+
+                    // Seeks to the next box, unless the box extends to the end of the stream or its size is
+                    // invalid.
+                    match size {
+                        0 => break,
+                        size if size < header_size && detector.strict => {
+                            return Err(Error::new(ErrorKind::InvalidData, "invalid MP4 box size"))
+                        }
+                        size if size < header_size => break,
+                        _ => reader.seek(SeekFrom::Current((size - header_size) as i64))?,
+                    };
+                }
+                b"stsd" => {
+                    // Reads the type of the first sample entry, if any.
+                    reader.seek(SeekFrom::Current(4))?;
+                    if reader.read_u32_be()? > 0 {
+                        reader.seek(SeekFrom::Current(4))?;
+                        let codec = reader.read_string(4)?;
+                        if let Some(track) = media.tracks.last_mut() {
+                            track.codec.get_or_insert(codec);
+                        }
+                    } else {
+                        reader.seek(SeekFrom::Current(8))?;
+                    }
+
+                    // Seeks to the next box, unless the box extends to the end of the stream or its size is
+                    // invalid.
+                    match size {
+                        0 => break,
+                        1..=23 if detector.strict => {
+                            return Err(Error::new(ErrorKind::InvalidData, "invalid MP4 box size"))
+                        }
+                        1..=23 => break,
+                        _ => reader.seek(SeekFrom::Current(size as i64 - 24))?,
+                    };
                 }
                 _ => {
-                    // Seeks to the next box.
-                    reader.seek(SeekFrom::Current(size as i64 - 8))?;
+                    // Seeks to the next box, unless the box extends to the end of the stream or its size is
+                    // invalid.
+                    match size {
+                        0 => break,
+                        1..=7 if detector.strict => {
+                            return Err(Error::new(ErrorKind::InvalidData, "invalid MP4 box size"))
+                        }
+                        1..=7 => break,
+                        _ => reader.seek(SeekFrom::Current(size as i64 - 8))?,
+                    };
                 }
             }
 
@@ -525,13 +1007,22 @@ impl crate::FileFormat {
             box_count += 1;
         }
 
+        // Records whether the boxes were not all browsed before finding a video track.
+        if video_track.is_none()
+            && box_count == detector.mp4_box_limit
+            && reader.stream_position()? < len
+        {
+            detection.limit_exceeded = Some("mp4_box_limit");
+        }
+
         // Determines the file format based on the identified tracks.
-        Ok(if video_track {
-            Self::Mpeg4Part14Video
-        } else if audio_track {
-            Self::Mpeg4Part14Audio
-        } else if subtitle_track {
-            Self::Mpeg4Part14Subtitles
+        detection.media = Some(media);
+        Ok(if let Some(handler_type) = video_track {
+            detection.mark(handler_type, Self::Mpeg4Part14Video)
+        } else if let Some(handler_type) = audio_track {
+            detection.mark(handler_type, Self::Mpeg4Part14Audio)
+        } else if let Some(handler_type) = subtitle_track {
+            detection.mark(handler_type, Self::Mpeg4Part14Subtitles)
         } else {
             Self::Mpeg4Part14
         })
@@ -539,10 +1030,11 @@ impl crate::FileFormat {
 
     /// Determines file format from a PDF reader.
     #[cfg(feature = "reader-pdf")]
-    pub(crate) fn from_pdf_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
-        // Maximum number of bytes that can be processed by the reader (32 MB).
-        const READ_LIMIT: usize = 33_554_432;
-
+    pub(crate) fn from_pdf_reader<R: Read + Seek>(
+        mut reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Size of each chunk to read (32 KB).
         const CHUNK_SIZE: usize = 32_768;
 
@@ -552,6 +1044,18 @@ impl crate::FileFormat {
         // Marker for the AI file format.
         const AI_MARKER: &[u8] = b"AIPrivateData";
 
+        // Number of bytes at the end of the stream in which the trailer is searched.
+        const TRAILER_SEARCH_SIZE: u64 = 4096;
+
+        // Checks whether the trailer, or the cross-reference stream, references an encryption
+        // dictionary.
+        let len = reader.seek(SeekFrom::End(0))?;
+        let offset = len.saturating_sub(TRAILER_SEARCH_SIZE);
+        reader.seek(SeekFrom::Start(offset))?;
+        if Self::has_pdf_encrypt_entry(&reader.read_bytes((len - offset) as usize)?) {
+            detection.encrypted = true;
+        }
+
         // Rewinds to the beginning of the stream plus the size of the PDF file format signature.
         reader.seek(SeekFrom::Start(5))?;
 
@@ -560,7 +1064,7 @@ impl crate::FileFormat {
 
         // Reads the data from the stream in chunks.
         let mut total_nread = 0;
-        while total_nread < READ_LIMIT {
+        while total_nread < detector.pdf_read_limit {
             // Reads a chunk of the stream into the buffer.
             let nread = reader.read(&mut buf[OVERLAP_SIZE..])?;
             if nread == 0 {
@@ -570,9 +1074,15 @@ impl crate::FileFormat {
             // Determines the start index for searching the buffer.
             let start = if total_nread == 0 { OVERLAP_SIZE } else { 0 };
 
+            // Checks if the first chunk holds the first-page trailer of a linearized PDF
+            // referencing an encryption dictionary.
+            if total_nread == 0 && Self::has_pdf_encrypt_entry(&buf[start..OVERLAP_SIZE + nread]) {
+                detection.encrypted = true;
+            }
+
             // Checks if the buffer holds the AI file format marker.
             if buf[start..OVERLAP_SIZE + nread].holds(AI_MARKER) {
-                return Ok(Self::AdobeIllustratorArtwork);
+                return Ok(detection.mark(AI_MARKER, Self::AdobeIllustratorArtwork));
             }
 
             // Rotates the buffer to the right by the overlap size.
@@ -582,13 +1092,220 @@ impl crate::FileFormat {
             total_nread += nread;
         }
 
+        // Records whether the stream was not entirely read.
+        if total_nread >= detector.pdf_read_limit && reader.read(&mut buf[..1])? > 0 {
+            detection.limit_exceeded = Some("pdf_read_limit");
+        }
+
         // Returns the default value.
         Ok(Self::PortableDocumentFormat)
     }
 
+    /// Checks whether the data holds a trailer dictionary, or a cross-reference stream dictionary,
+    /// with an `/Encrypt` entry.
+    ///
+    /// The data is tokenized in a single forward pass, each dictionary following the `trailer` or
+    /// `obj` keyword being checked once closed. A byte which cannot start a valid object, such as
+    /// one of a binary stream, is skipped along with the arrays and dictionaries enclosing it.
+    #[cfg(feature = "reader-pdf")]
+    fn has_pdf_encrypt_entry(data: &[u8]) -> bool {
+        // Maximum nesting depth of arrays and dictionaries that can be processed by the reader.
+        const DEPTH_LIMIT: usize = 32;
+
+        /// An array or a dictionary which is not closed yet.
+        struct Container<'a> {
+            /// Index of the opening delimiter.
+            start: usize,
+            /// `true` for a dictionary, `false` for an array.
+            dictionary: bool,
+            /// Keyword preceding the dictionary, if any, along with its top-level objects.
+            keyword: Option<(&'a [u8], Vec<&'a [u8]>)>,
+        }
+
+        // Ends of the literal strings, by start index.
+        let string_ends = Self::pdf_string_ends(data);
+
+        // Browses the objects of the data.
+        let mut containers: Vec<Container> = Vec::new();
+        let mut keyword = None;
+        let mut index = Self::skip_pdf_whitespace(data, 0);
+        while let Some(&byte) = data.get(index) {
+            // Determines the range of the object starting at the index, if it is valid.
+            let object = match byte {
+                b'<' if data.get(index + 1) == Some(&b'<') => {
+                    // Opens a dictionary, the outermost container being dropped beyond the depth
+                    // limit.
+                    if containers.len() == DEPTH_LIMIT {
+                        containers.remove(0);
+                    }
+                    containers.push(Container {
+                        start: index,
+                        dictionary: true,
+                        keyword: keyword.take().map(|keyword| (keyword, Vec::new())),
+                    });
+                    index = Self::skip_pdf_whitespace(data, index + 2);
+                    continue;
+                }
+                b'[' => {
+                    // Opens an array, the outermost container being dropped beyond the depth
+                    // limit.
+                    if containers.len() == DEPTH_LIMIT {
+                        containers.remove(0);
+                    }
+                    containers.push(Container {
+                        start: index,
+                        dictionary: false,
+                        keyword: None,
+                    });
+                    keyword = None;
+                    index = Self::skip_pdf_whitespace(data, index + 1);
+                    continue;
+                }
+                b'>' | b']' => {
+                    // Closes the innermost container, if it has the same type.
+                    let dictionary = byte == b'>';
+                    match containers.pop() {
+                        Some(container)
+                            if container.dictionary == dictionary
+                                && (!dictionary || data.get(index + 1) == Some(&b'>')) =>
+                        {
+                            // Checks a dictionary following a keyword.
+                            if let Some((keyword, objects)) = container.keyword {
+                                if Self::is_pdf_encrypted_trailer(keyword, &objects) {
+                                    return true;
+                                }
+                            }
+                            Some(container.start..index + if dictionary { 2 } else { 1 })
+                        }
+                        _ => None,
+                    }
+                }
+                b'<' => data[index + 1..]
+                    .iter()
+                    .position(|byte| !byte.is_ascii_hexdigit() && !b"\0\t\n\x0C\r ".contains(byte))
+                    .filter(|&offset| data[index + 1 + offset] == b'>')
+                    .map(|offset| index..index + offset + 2),
+                b'(' => string_ends
+                    .binary_search_by_key(&index, |&(start, _)| start)
+                    .ok()
+                    .map(|position| index..string_ends[position].1),
+                b'/' => Some(index..Self::pdf_token_end(data, index + 1)),
+                b')' => None,
+                b'{' | b'}' => Some(index..index + 1),
+                _ => Some(index..Self::pdf_token_end(data, index)),
+            };
+
+            // Records the object, or skips the byte along with the enclosing containers.
+            match object {
+                Some(object) => {
+                    let end = object.end;
+                    let object = &data[object];
+                    if let Some(Container {
+                        keyword: Some((_, objects)),
+                        ..
+                    }) = containers.last_mut()
+                    {
+                        objects.push(object);
+                    }
+                    keyword =
+                        Some(object).filter(|&object| object == b"trailer" || object == b"obj");
+                    index = Self::skip_pdf_whitespace(data, end);
+                }
+                None => {
+                    containers.clear();
+                    keyword = None;
+                    index = Self::skip_pdf_whitespace(data, index + 1);
+                }
+            }
+        }
+        false
+    }
+
+    /// Checks whether the top-level objects of a dictionary following the specified keyword are
+    /// the ones of a trailer, or of a cross-reference stream, with an `/Encrypt` entry.
+    #[cfg(feature = "reader-pdf")]
+    fn is_pdf_encrypted_trailer(keyword: &[u8], objects: &[&[u8]]) -> bool {
+        // Pairs the keys with their values, an indirect reference spanning three objects.
+        let is_integer = |object: &[u8]| object.iter().all(u8::is_ascii_digit);
+        let mut entries = Vec::new();
+        let mut index = 0;
+        while index + 1 < objects.len() {
+            entries.push((objects[index], objects[index + 1]));
+            index += match objects.get(index + 1..index + 4) {
+                Some([number, generation, b"R"])
+                    if is_integer(number) && is_integer(generation) =>
+                {
+                    4
+                }
+                _ => 2,
+            };
+        }
+
+        // Checks the entries.
+        let is_trailer = keyword == b"trailer" || entries.contains(&(&b"/Type"[..], &b"/XRef"[..]));
+        is_trailer && entries.iter().any(|&(key, _)| key == b"/Encrypt")
+    }
+
+    /// Returns the start and end indexes of the PDF literal strings of the data, sorted by start
+    /// index, each opening parenthesis being paired with its closing one.
+    #[cfg(feature = "reader-pdf")]
+    fn pdf_string_ends(data: &[u8]) -> Vec<(usize, usize)> {
+        let mut starts = Vec::new();
+        let mut ends = Vec::new();
+        let mut escaped = false;
+        for (index, &byte) in data.iter().enumerate() {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'(' => starts.push(index),
+                b')' => {
+                    if let Some(start) = starts.pop() {
+                        ends.push((start, index + 1));
+                    }
+                }
+                _ => {}
+            }
+        }
+        ends.sort_unstable();
+        ends
+    }
+
+    /// Returns the index following the PDF regular characters starting at the specified index of
+    /// the data.
+    #[cfg(feature = "reader-pdf")]
+    fn pdf_token_end(data: &[u8], index: usize) -> usize {
+        index
+            + data[index..]
+                .iter()
+                .position(|byte| b"\0\t\n\x0C\r ()<>[]{}/%".contains(byte))
+                .unwrap_or(data.len() - index)
+    }
+
+    /// Returns the index following the PDF white-space characters and comments starting at the
+    /// specified index of the data.
+    #[cfg(feature = "reader-pdf")]
+    fn skip_pdf_whitespace(data: &[u8], mut index: usize) -> usize {
+        while let Some(&byte) = data.get(index) {
+            match byte {
+                b'\0' | b'\t' | b'\n' | b'\x0C' | b'\r' | b' ' => index += 1,
+                b'%' => {
+                    index += data[index..]
+                        .iter()
+                        .position(|&byte| byte == b'\n' || byte == b'\r')
+                        .unwrap_or(data.len() - index)
+                }
+                _ => break,
+            }
+        }
+        index
+    }
+
     /// Determines file format from a RM reader.
     #[cfg(feature = "reader-rm")]
-    pub(crate) fn from_rm_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+    pub(crate) fn from_rm_reader<R: Read + Seek>(
+        reader: R,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Maximum number of chunks that can be processed by the reader.
         const CHUNK_LIMIT: usize = 64;
 
@@ -599,12 +1316,15 @@ impl crate::FileFormat {
         reader.seek(SeekFrom::Start(14))?;
         let number_of_headers = reader.read_u32_be()?;
 
-        // Flags indicating the presence of audio and video streams.
-        let mut audio_stream = false;
-        let mut video_stream = false;
+        // Mime types of the first audio and video streams.
+        let mut audio_stream = None;
+        let mut video_stream = None;
+
+        // Duration and streams of the media.
+        let mut media = MediaInfo::default();
 
         // Iterates through the chunks.
-        for _ in 0..std::cmp::min(CHUNK_LIMIT, number_of_headers.saturating_sub(1) as usize) {
+        for _ in 0..core::cmp::min(CHUNK_LIMIT, number_of_headers.saturating_sub(1) as usize) {
             // Reads the chunk type.
             let chunk_type = reader.read_bytes(4)?;
 
@@ -612,7 +1332,12 @@ impl crate::FileFormat {
             let chunk_size = reader.read_u32_be()?;
 
             // Checks the chunk type.
-            if &chunk_type == b"MDPR" {
+            if &chunk_type == b"PROP" {
+                // Reads the duration, in milliseconds.
+                reader.seek(SeekFrom::Current(22))?;
+                media.duration = Some(Duration::from_millis(reader.read_u32_be()? as u64));
+                reader.seek(SeekFrom::Current(-26))?;
+            } else if &chunk_type == b"MDPR" {
                 // Calculates the offset of the media properties.
                 let offset = reader.stream_position()?;
 
@@ -630,11 +1355,20 @@ impl crate::FileFormat {
                 let mime_type = reader.read_bytes(mime_type_size as usize)?;
 
                 // Checks the mime type.
-                if mime_type.starts_with(b"audio/") {
-                    audio_stream = true;
+                let codec = String::from_utf8_lossy(&mime_type).into_owned();
+                let kind = if mime_type.starts_with(b"audio/") {
+                    audio_stream.get_or_insert(mime_type);
+                    TrackKind::Audio
                 } else if mime_type.starts_with(b"video/") {
-                    video_stream = true;
-                }
+                    video_stream.get_or_insert(mime_type);
+                    TrackKind::Video
+                } else {
+                    TrackKind::Other
+                };
+                media.tracks.push(Track {
+                    codec: Some(codec),
+                    ..Track::new(kind)
+                });
 
                 // Rewinds to the offset of the media properties.
                 reader.seek(SeekFrom::Start(offset))?;
@@ -645,10 +1379,11 @@ impl crate::FileFormat {
         }
 
         // Determines the file format based on the identified streams.
-        Ok(if video_stream {
-            Self::Realvideo
-        } else if audio_stream {
-            Self::Realaudio
+        detection.media = Some(media);
+        Ok(if let Some(mime_type) = video_stream {
+            detection.mark(mime_type, Self::Realvideo)
+        } else if let Some(mime_type) = audio_stream {
+            detection.mark(mime_type, Self::Realaudio)
         } else {
             Self::Realmedia
         })
@@ -656,7 +1391,10 @@ impl crate::FileFormat {
 
     /// Determines file format from a SQLite 3 reader.
     #[cfg(feature = "reader-sqlite3")]
-    pub(crate) fn from_sqlite3_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+    pub(crate) fn from_sqlite3_reader<R: Read + Seek>(
+        mut reader: R,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Marker for the Sketch file format.
         const SKETCH_MARKER: &[u8] = b"com.bohemiancoding.sketch3";
 
@@ -669,7 +1407,7 @@ impl crate::FileFormat {
 
         // Checks if the buffer holds the Sketch file format marker.
         if buf[..nread].holds(SKETCH_MARKER) {
-            return Ok(Self::Sketch);
+            return Ok(detection.mark(SKETCH_MARKER, Self::Sketch));
         }
 
         // Returns the default value.
@@ -678,10 +1416,7 @@ impl crate::FileFormat {
 
     /// Determines file format from a TXT reader.
     #[cfg(feature = "reader-txt")]
-    pub(crate) fn from_txt_reader<R: Read + Seek>(reader: R) -> Result<Self> {
-        // Maximum number of lines that can be processed by the reader.
-        const LINE_LIMIT: usize = 16;
-
+    pub(crate) fn from_txt_reader<R: Read + Seek>(reader: R, detector: &Detector) -> Result<Self> {
         // Maximum number of bytes that can be processed by the reader (64 KB).
         const READ_LIMIT: u64 = 65_536;
 
@@ -696,7 +1431,7 @@ impl crate::FileFormat {
         reader
             .take(READ_LIMIT)
             .lines()
-            .take(LINE_LIMIT)
+            .take(detector.txt_line_limit)
             .try_for_each(|line| {
                 line?
                     .chars()
@@ -709,7 +1444,10 @@ impl crate::FileFormat {
 
     /// Determines file format from a XML reader.
     #[cfg(feature = "reader-xml")]
-    pub(crate) fn from_xml_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+    pub(crate) fn from_xml_reader<R: Read + Seek>(
+        mut reader: R,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Rewinds to the beginning of the stream plus the size of the XML file format signature.
         reader.seek(SeekFrom::Start(5))?;
 
@@ -718,76 +1456,69 @@ impl crate::FileFormat {
         let nread = reader.read(&mut buf)?;
         let buf = &buf[..nread];
 
-        // Checks if the buffer holds markers indicating the presence of various file formats.
-        Ok(if buf.holds("<abiword template=\"false\"") {
-            Self::Abiword
-        } else if buf.holds("<abiword template=\"true\"") {
-            Self::AbiwordTemplate
-        } else if buf.holds("<amf") {
-            Self::AdditiveManufacturingFormat
-        } else if buf.holds("<ASX") || buf.holds("<asx") {
-            Self::AdvancedStreamRedirector
-        } else if buf.holds("<feed") {
-            Self::Atom
-        } else if buf.holds("<COLLADA") {
-            Self::CollaborativeDesignActivity
-        } else if buf.holds("<mxfile") {
-            Self::Drawio
-        } else if buf.holds("<X3D") {
-            Self::Extensible3d
-        } else if buf.holds("<xsl") {
-            Self::ExtensibleStylesheetLanguageTransformations
-        } else if buf.holds("<FictionBook") {
-            Self::Fictionbook
-        } else if buf.holds("<gml") {
-            Self::GeographyMarkupLanguage
-        } else if buf.holds("<gpx") {
-            Self::GpsExchangeFormat
-        } else if buf.holds("<kml") {
-            Self::KeyholeMarkupLanguage
-        } else if buf.holds("<math") {
-            Self::MathematicalMarkupLanguage
-        } else if buf.holds("<MPD") {
-            Self::MpegDashMpd
-        } else if buf.holds("<score-partwise") {
-            Self::Musicxml
-        } else if buf.holds("<rss") {
-            Self::ReallySimpleSyndication
-        } else if buf.holds("<SVG") || buf.holds("<svg") {
-            Self::ScalableVectorGraphics
-        } else if buf.holds("<soap") {
-            Self::SimpleObjectAccessProtocol
-        } else if buf.holds("<map") {
-            Self::TiledMapXml
-        } else if buf.holds("<tileset") {
-            Self::TiledTilesetXml
-        } else if buf.holds("<tt") && buf.holds("xmlns=\"http://www.w3.org/ns/ttml\"") {
-            Self::TimedTextMarkupLanguage
-        } else if buf.holds("<TrainingCenterDatabase") {
-            Self::TrainingCenterXml
-        } else if buf.holds("<uof:UOF") && buf.holds("uof:mimetype=\"vnd.uof.presentation\"") {
-            Self::UniformOfficeFormatPresentation
-        } else if buf.holds("<uof:UOF") && buf.holds("uof:mimetype=\"vnd.uof.spreadsheet\"") {
-            Self::UniformOfficeFormatSpreadsheet
-        } else if buf.holds("<uof:UOF") && buf.holds("uof:mimetype=\"vnd.uof.text\"") {
-            Self::UniformOfficeFormatText
-        } else if buf.holds("<USFSubtitles") {
-            Self::UniversalSubtitleFormat
-        } else if buf.holds("<xliff") {
-            Self::XmlLocalizationInterchangeFileFormat
-        } else if buf.holds("<playlist") {
-            Self::XmlShareablePlaylistFormat
-        } else {
-            Self::ExtensibleMarkupLanguage
-        })
+        // Markers indicating the presence of various file formats, all of which must be held.
+        let markers: &[(&[&str], Self)] = &[
+            (&["<abiword template=\"false\""], Self::Abiword),
+            (&["<abiword template=\"true\""], Self::AbiwordTemplate),
+            (&["<amf"], Self::AdditiveManufacturingFormat),
+            (&["<ASX"], Self::AdvancedStreamRedirector),
+            (&["<asx"], Self::AdvancedStreamRedirector),
+            (&["<feed"], Self::Atom),
+            (&["<COLLADA"], Self::CollaborativeDesignActivity),
+            (&["<mxfile"], Self::Drawio),
+            (&["<X3D"], Self::Extensible3d),
+            (&["<xsl"], Self::ExtensibleStylesheetLanguageTransformations),
+            (&["<FictionBook"], Self::Fictionbook),
+            (&["<gml"], Self::GeographyMarkupLanguage),
+            (&["<gpx"], Self::GpsExchangeFormat),
+            (&["<kml"], Self::KeyholeMarkupLanguage),
+            (&["<math"], Self::MathematicalMarkupLanguage),
+            (&["<MPD"], Self::MpegDashMpd),
+            (&["<score-partwise"], Self::Musicxml),
+            (&["<rss"], Self::ReallySimpleSyndication),
+            (&["<SVG"], Self::ScalableVectorGraphics),
+            (&["<svg"], Self::ScalableVectorGraphics),
+            (&["<soap"], Self::SimpleObjectAccessProtocol),
+            (&["<map"], Self::TiledMapXml),
+            (&["<tileset"], Self::TiledTilesetXml),
+            (
+                &["<tt", "xmlns=\"http://www.w3.org/ns/ttml\""],
+                Self::TimedTextMarkupLanguage,
+            ),
+            (&["<TrainingCenterDatabase"], Self::TrainingCenterXml),
+            (
+                &["<uof:UOF", "uof:mimetype=\"vnd.uof.presentation\""],
+                Self::UniformOfficeFormatPresentation,
+            ),
+            (
+                &["<uof:UOF", "uof:mimetype=\"vnd.uof.spreadsheet\""],
+                Self::UniformOfficeFormatSpreadsheet,
+            ),
+            (
+                &["<uof:UOF", "uof:mimetype=\"vnd.uof.text\""],
+                Self::UniformOfficeFormatText,
+            ),
+            (&["<USFSubtitles"], Self::UniversalSubtitleFormat),
+            (&["<xliff"], Self::XmlLocalizationInterchangeFileFormat),
+            (&["<playlist"], Self::XmlShareablePlaylistFormat),
+        ];
+
+        // Checks if the buffer holds markers indicating the presence of various file formats, the
+        // last marker of each file format being the most specific one.
+        Ok(markers
+            .iter()
+            .find(|(markers, _)| markers.iter().all(|marker| buf.holds(marker)))
+            .map(|&(markers, fmt)| detection.mark(markers[markers.len() - 1], fmt))
+            .unwrap_or(Self::ExtensibleMarkupLanguage))
     }
 
     /// Determines file format from a ZIP reader.
     #[cfg(feature = "reader-zip")]
-    pub(crate) fn from_zip_reader<R: Read + Seek>(reader: R) -> Result<Self> {
-        // Maximum number of entries that can be processed by the reader.
-        const ENTRY_LIMIT: usize = 1024;
-
+    pub(crate) fn from_zip_reader<R: Read + Seek>(
+        reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
         // Signature of the ZIP64 end of central directory locator.
         const EOCD64_LOCATOR_SIGNATURE: &[u8] = b"PK\x06\x07";
 
@@ -812,10 +1543,16 @@ impl crate::FileFormat {
         // Searches for the end of central directory record.
         let offset = len.saturating_sub(EOCD_MAX_SIZE as u64);
         reader.seek(SeekFrom::Start(offset))?;
-        let buf_index = reader
+        let buf_index = match reader
             .read_bytes((len as usize).clamp(EOCD_MIN_SIZE, EOCD_MAX_SIZE))?
             .rfind(EOCD_SIGNATURE)
-            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "cannot find the EOCD"))?;
+        {
+            Some(buf_index) => buf_index,
+            None if detector.stream => {
+                return Self::from_zip_local_reader(reader, detector, detection)
+            }
+            None => return Err(Error::new(ErrorKind::UnexpectedEof, "cannot find the EOCD")),
+        };
         let eocd_offset = offset + buf_index as u64;
 
         // Checks for ZIP64 end of central directory locator.
@@ -868,10 +1605,24 @@ impl crate::FileFormat {
         // Sets the default value.
         let mut fmt = Self::Zip;
 
+        // File format identified by an entry, the remaining entries only being browsed for
+        // encrypted entries and VBA projects.
+        let mut found = None;
+
+        // Office Open XML file format identified by the name of an entry, refined by the other
+        // entries when the content types of the package have not been read yet.
+        let mut ooxml = None;
+        let mut binary_workbook = false;
+
         // Browses central directory headers.
-        for _ in 0..std::cmp::min(ENTRY_LIMIT, number_of_entries) {
+        for _ in 0..core::cmp::min(detector.zip_entry_limit, number_of_entries) {
+            // Reads the general purpose bit flag and the compression method.
+            reader.seek(SeekFrom::Current(8))?;
+            let flags = reader.read_u16_le()?;
+            let compression_method = reader.read_u16_le()?;
+
             // Reads the compressed size.
-            reader.seek(SeekFrom::Current(20))?;
+            reader.seek(SeekFrom::Current(8))?;
             let compressed_size = reader.read_u32_le()?;
 
             // Reads the uncompressed size.
@@ -893,23 +1644,34 @@ impl crate::FileFormat {
             // Reads the filename.
             let filename = reader.read_string(filename_len as usize)?;
 
+            // Checks whether the entry is encrypted.
+            let extra_field = reader.read_bytes(extra_field_len as usize)?;
+            if Self::is_zip_entry_encrypted(flags, compression_method, &extra_field) {
+                detection.encrypted = true;
+            }
+
+            // Records the parts of macro-enabled and binary Office Open XML packages.
+            if Self::is_zip_vba_project(&filename) {
+                detection.has_macros = true;
+            }
+            binary_workbook |= filename == "xl/workbook.bin";
+
+            // Skips to the next central directory entry once the file format is identified.
+            if found.is_some() {
+                reader.seek(SeekFrom::Current(file_comment_len as i64))?;
+                continue;
+            }
+
             // Checks the filename.
-            match filename.as_str() {
-                "AndroidManifest.xml" => return Ok(Self::AndroidPackage),
-                "AppManifest.xaml" => return Ok(Self::Xap),
-                "AppxManifest.xml" => return Ok(Self::WindowsAppPackage),
-                "AppxMetadata/AppxBundleManifest.xml" => return Ok(Self::WindowsAppBundle),
-                "BundleConfig.pb" => return Ok(Self::AndroidAppBundle),
-                "DOMDocument.xml" => return Ok(Self::FlashCs5Project),
-                "META-INF/AIR/application.xml" => return Ok(Self::AdobeIntegratedRuntime),
-                "META-INF/MANIFEST.MF" => fmt = Self::JavaArchive,
-                "META-INF/application.xml" => return Ok(Self::EnterpriseApplicationArchive),
-                "META-INF/mozilla.rsa" => return Ok(Self::Xpinstall),
-                "WEB-INF/web.xml" => return Ok(Self::WebApplicationArchive),
-                "doc.kml" => return Ok(Self::KeyholeMarkupLanguageZip),
-                "document.json" => return Ok(Self::Sketch43),
-                "extension.vsixmanifest" => return Ok(Self::MicrosoftVisualStudioExtension),
+            let entry_fmt = match filename.as_str() {
+                "META-INF/MANIFEST.MF" => {
+                    fmt = detection.mark(&filename, Self::JavaArchive);
+                    None
+                }
                 "mimetype" if compressed_size == uncompressed_size => {
+                    // Saves the position of the next central directory entry.
+                    let position = reader.stream_position()?;
+
                     // Seeks to the filename of the local file header.
                     reader.seek(SeekFrom::Start(offset as u64 + 26))?;
 
@@ -927,110 +1689,679 @@ impl crate::FileFormat {
                     // Reads the data.
                     let data = reader.read_string(compressed_size as usize)?;
 
-                    // Checks the trimmed data.
-                    return Ok(match data.trim() {
-                        "application/epub+zip" => Self::ElectronicPublication,
-                        "application/vnd.adobe.indesign-idml-package" => {
-                            Self::IndesignMarkupLanguage
-                        }
-                        "application/vnd.oasis.opendocument.base"
-                        | "application/vnd.oasis.opendocument.database" => {
-                            Self::OpendocumentDatabase
-                        }
-                        "application/vnd.oasis.opendocument.formula" => Self::OpendocumentFormula,
-                        "application/vnd.oasis.opendocument.formula-template" => {
-                            Self::OpendocumentFormulaTemplate
-                        }
-                        "application/vnd.oasis.opendocument.graphics" => Self::OpendocumentGraphics,
-                        "application/vnd.oasis.opendocument.graphics-template" => {
-                            Self::OpendocumentGraphicsTemplate
-                        }
-                        "application/vnd.oasis.opendocument.presentation" => {
-                            Self::OpendocumentPresentation
-                        }
-                        "application/vnd.oasis.opendocument.presentation-template" => {
-                            Self::OpendocumentPresentationTemplate
-                        }
-                        "application/vnd.oasis.opendocument.spreadsheet" => {
-                            Self::OpendocumentSpreadsheet
-                        }
-                        "application/vnd.oasis.opendocument.spreadsheet-template" => {
-                            Self::OpendocumentSpreadsheetTemplate
-                        }
-                        "application/vnd.oasis.opendocument.text" => Self::OpendocumentText,
-                        "application/vnd.oasis.opendocument.text-master" => {
-                            Self::OpendocumentTextMaster
-                        }
-                        "application/vnd.oasis.opendocument.text-master-template" => {
-                            Self::OpendocumentTextMasterTemplate
-                        }
-                        "application/vnd.oasis.opendocument.text-template" => {
-                            Self::OpendocumentTextTemplate
-                        }
-                        "application/vnd.recordare.musicxml" => Self::MusicxmlZip,
-                        "application/vnd.sun.xml.calc" => Self::SunXmlCalc,
-                        "application/vnd.sun.xml.calc.template" => Self::SunXmlCalcTemplate,
-                        "application/vnd.sun.xml.draw" => Self::SunXmlDraw,
-                        "application/vnd.sun.xml.draw.template" => Self::SunXmlDrawTemplate,
-                        "application/vnd.sun.xml.impress" => Self::SunXmlImpress,
-                        "application/vnd.sun.xml.impress.template" => Self::SunXmlImpressTemplate,
-                        "application/vnd.sun.xml.math" => Self::SunXmlMath,
-                        "application/vnd.sun.xml.writer" => Self::SunXmlWriter,
-                        "application/vnd.sun.xml.writer.global" => Self::SunXmlWriterGlobal,
-                        "application/vnd.sun.xml.writer.template" => Self::SunXmlWriterTemplate,
-                        "image/openraster" => Self::Openraster,
-                        _ => Self::Zip,
-                    });
+                    // Checks the data.
+                    found = Some(Self::from_zip_mimetype(&data, detection));
+
+                    // Seeks back to the next central directory entry.
+                    reader.seek(SeekFrom::Start(position))?;
+                    None
                 }
-                _ => {
-                    if filename.starts_with("Fusion[Active]/") {
-                        return Ok(Self::Autodesk123d);
-                    } else if filename.starts_with("circuitdiagram/") {
-                        return Ok(Self::CircuitDiagramDocument);
-                    } else if filename.starts_with("dwf/") {
-                        return Ok(Self::DesignWebFormatXps);
-                    } else if filename.ends_with(".fb2") && !filename.contains('/') {
-                        return Ok(Self::FictionbookZip);
-                    } else if filename.starts_with("FusionAssetName[Active]/") {
-                        return Ok(Self::Fusion360);
-                    } else if filename.starts_with("Payload/") && filename.contains(".app/") {
-                        return Ok(Self::IosAppStorePackage);
-                    } else if filename.starts_with("word/") {
-                        return Ok(Self::OfficeOpenXmlDocument);
-                    } else if filename.starts_with("visio/") {
-                        return Ok(Self::OfficeOpenXmlDrawing);
-                    } else if filename.starts_with("ppt/") {
-                        return Ok(Self::OfficeOpenXmlPresentation);
-                    } else if filename.starts_with("xl/") {
-                        return Ok(Self::OfficeOpenXmlSpreadsheet);
-                    } else if filename.starts_with("Documents/") && filename.ends_with(".fpage") {
-                        return Ok(Self::Openxps);
-                    } else if filename.starts_with("SpaceClaim/") {
-                        return Ok(Self::SpaceclaimDocument);
-                    } else if filename.starts_with("3D/") && filename.ends_with(".model") {
-                        return Ok(Self::ThreeDimensionalManufacturingFormat);
-                    } else if (filename.ends_with(".usd")
-                        || filename.ends_with(".usda")
-                        || filename.ends_with(".usdc"))
-                        && !filename.contains('/')
-                    {
-                        return Ok(Self::UniversalSceneDescriptionZip);
-                    }
+                "[Content_Types].xml" => {
+                    // Saves the position of the next central directory entry.
+                    let position = reader.stream_position()?;
+
+                    // Seeks to the filename of the local file header.
+                    reader.seek(SeekFrom::Start(offset as u64 + 26))?;
+
+                    // Reads the filename length.
+                    let filename_len = reader.read_u16_le()?;
+
+                    // Reads the extra field length.
+                    let extra_field_len = reader.read_u16_le()?;
+
+                    // Seeks to the data.
+                    reader.seek(SeekFrom::Current(
+                        filename_len as i64 + extra_field_len as i64,
+                    ))?;
+
+                    // Reads the data.
+                    let data = reader.read_bytes(core::cmp::min(
+                        compressed_size as usize,
+                        CONTENT_TYPES_LIMIT,
+                    ))?;
+
+                    // Checks the data.
+                    found = Self::from_zip_content_types(&data, compression_method, detection);
+
+                    // Seeks back to the next central directory entry.
+                    reader.seek(SeekFrom::Start(position))?;
+                    None
                 }
+                _ => Self::from_zip_filename(&filename),
+            };
+
+            // Records the file format identified by the filename, if any, an Office Open XML one
+            // being kept until the other entries are browsed.
+            match entry_fmt {
+                Some(_) if ooxml.is_some() => {}
+                Some(
+                    entry_fmt @ (Self::OfficeOpenXmlDocument
+                    | Self::OfficeOpenXmlPresentation
+                    | Self::OfficeOpenXmlSpreadsheet),
+                ) => ooxml = Some((filename, entry_fmt)),
+                Some(entry_fmt) => found = Some(detection.mark(filename, entry_fmt)),
+                None => {}
             }
 
             // Seeks to the next central directory entry.
-            reader.seek(SeekFrom::Current(
-                extra_field_len as i64 + file_comment_len as i64,
-            ))?;
+            reader.seek(SeekFrom::Current(file_comment_len as i64))?;
+        }
+
+        // Returns the file format identified by an entry, if any.
+        if let Some(fmt) = found {
+            return Ok(fmt);
+        }
+
+        // Records whether the entries were not all browsed.
+        if number_of_entries > detector.zip_entry_limit {
+            detection.limit_exceeded = Some("zip_entry_limit");
+        }
+
+        // Returns the Office Open XML file format identified by the filenames, if any.
+        if let Some((filename, ooxml_fmt)) = ooxml {
+            let ooxml_fmt = match ooxml_fmt {
+                Self::OfficeOpenXmlSpreadsheet if binary_workbook => {
+                    Self::OfficeOpenXmlBinarySpreadsheet
+                }
+                Self::OfficeOpenXmlDocument if detection.has_macros => {
+                    Self::OfficeOpenXmlMacroEnabledDocument
+                }
+                Self::OfficeOpenXmlPresentation if detection.has_macros => {
+                    Self::OfficeOpenXmlMacroEnabledPresentation
+                }
+                Self::OfficeOpenXmlSpreadsheet if detection.has_macros => {
+                    Self::OfficeOpenXmlMacroEnabledSpreadsheet
+                }
+                _ => ooxml_fmt,
+            };
+            return Ok(detection.mark(filename, ooxml_fmt));
         }
         Ok(fmt)
     }
+
+    /// Determines file format from the local file headers of a ZIP reader.
+    ///
+    /// The headers are walked from the beginning of the stream until the available data runs out,
+    /// which makes it usable on the prefix of a stream which cannot seek, where the end of central
+    /// directory record is usually out of reach.
+    #[cfg(feature = "reader-zip")]
+    fn from_zip_local_reader<R: Read + Seek>(
+        mut reader: R,
+        detector: &Detector,
+        detection: &mut Detection,
+    ) -> Result<Self> {
+        // Signature of the local file header.
+        const LOCAL_FILE_HEADER_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+        // Size of the local file header, excluding the variable fields.
+        const LOCAL_FILE_HEADER_SIZE: u64 = 30;
+
+        // Flag indicating that the sizes are stored in a data descriptor following the data.
+        const DATA_DESCRIPTOR_FLAG: u16 = 0x0008;
+
+        // Retrieves the stream length.
+        let len = reader.seek(SeekFrom::End(0))?;
+
+        // Sets the default value.
+        let mut fmt = Self::Zip;
+
+        // File format identified by an entry, the remaining entries only being browsed for
+        // encrypted entries and VBA projects.
+        let mut found = None;
+
+        // Browses local file headers.
+        let mut offset = 0;
+        let mut entry_count = 0;
+        while entry_count < detector.zip_entry_limit {
+            // Stops at the end of the available data.
+            if offset + LOCAL_FILE_HEADER_SIZE > len {
+                break;
+            }
+
+            // Reads the signature.
+            reader.seek(SeekFrom::Start(offset))?;
+            if reader.read_bytes(4)? != LOCAL_FILE_HEADER_SIGNATURE {
+                break;
+            }
+
+            // Reads the general purpose bit flag and the compression method.
+            reader.seek(SeekFrom::Current(2))?;
+            let flags = reader.read_u16_le()?;
+            let compression_method = reader.read_u16_le()?;
+
+            // Reads the compressed size.
+            reader.seek(SeekFrom::Current(8))?;
+            let compressed_size = reader.read_u32_le()?;
+
+            // Reads the uncompressed size.
+            let uncompressed_size = reader.read_u32_le()?;
+
+            // Reads the filename length.
+            let filename_len = reader.read_u16_le()?;
+
+            // Reads the extra field length.
+            let extra_field_len = reader.read_u16_le()?;
+
+            // Calculates the data offset.
+            let data_offset =
+                offset + LOCAL_FILE_HEADER_SIZE + filename_len as u64 + extra_field_len as u64;
+            if data_offset > len {
+                break;
+            }
+
+            // Reads the filename.
+            let filename = reader.read_string(filename_len as usize)?;
+
+            // Checks whether the entry is encrypted.
+            let extra_field = reader.read_bytes(extra_field_len as usize)?;
+            if Self::is_zip_entry_encrypted(flags, compression_method, &extra_field) {
+                detection.encrypted = true;
+            }
+
+            // Records the VBA project of a macro-enabled Office Open XML package.
+            if Self::is_zip_vba_project(&filename) {
+                detection.has_macros = true;
+            }
+
+            // Checks the filename, unless the file format is already identified.
+            let entry_fmt = match filename.as_str() {
+                _ if found.is_some() => None,
+                "META-INF/MANIFEST.MF" => {
+                    fmt = detection.mark(&filename, Self::JavaArchive);
+                    None
+                }
+                "mimetype"
+                    if compressed_size == uncompressed_size
+                        && data_offset + compressed_size as u64 <= len =>
+                {
+                    // Reads the data.
+                    reader.seek(SeekFrom::Start(data_offset))?;
+                    let data = reader.read_string(compressed_size as usize)?;
+
+                    // Checks the data.
+                    found = Some(Self::from_zip_mimetype(&data, detection));
+                    None
+                }
+                "[Content_Types].xml" => {
+                    // Reads the available data.
+                    reader.seek(SeekFrom::Start(data_offset))?;
+                    let data = reader.read_bytes(core::cmp::min(
+                        core::cmp::min(compressed_size as u64, len - data_offset) as usize,
+                        CONTENT_TYPES_LIMIT,
+                    ))?;
+
+                    // Checks the data.
+                    found = Self::from_zip_content_types(&data, compression_method, detection);
+                    None
+                }
+                _ => Self::from_zip_filename(&filename),
+            };
+
+            // Records the file format identified by the filename, if any.
+            if let Some(entry_fmt) = entry_fmt {
+                found = Some(detection.mark(filename, entry_fmt));
+            }
+
+            // Stops when the size of the data is unknown.
+            if flags & DATA_DESCRIPTOR_FLAG != 0 {
+                break;
+            }
+
+            // Skips to the next local file header.
+            offset = data_offset + compressed_size as u64;
+
+            // Increments the entry count.
+            entry_count += 1;
+        }
+
+        // Returns the file format identified by an entry, if any.
+        if let Some(fmt) = found {
+            return Ok(fmt);
+        }
+
+        // Records that the file format was determined without the central directory, and whether
+        // the entries were not all browsed.
+        detection.truncated = true;
+        if entry_count == detector.zip_entry_limit {
+            detection.limit_exceeded = Some("zip_entry_limit");
+        }
+        Ok(fmt)
+    }
+
+    /// Returns `true` if a ZIP entry is encrypted, either with the traditional PKWARE encryption
+    /// or with AES, whose extra field replaces the actual compression method.
+    #[cfg(feature = "reader-zip")]
+    fn is_zip_entry_encrypted(flags: u16, compression_method: u16, extra_field: &[u8]) -> bool {
+        // Flag indicating that the entry is encrypted.
+        const ENCRYPTED_FLAG: u16 = 0x0001;
+
+        // Compression method of an entry encrypted with AES.
+        const AES_COMPRESSION_METHOD: u16 = 99;
+
+        // Header ID of the AES extra field.
+        const AES_EXTRA_FIELD_ID: u16 = 0x9901;
+
+        // Checks the flag and the compression method.
+        if flags & ENCRYPTED_FLAG != 0 || compression_method == AES_COMPRESSION_METHOD {
+            return true;
+        }
+
+        // Iterates through the extra fields.
+        let mut extra_field = extra_field;
+        while extra_field.len() >= 4 {
+            let id = u16::from_le_bytes([extra_field[0], extra_field[1]]);
+            let size = u16::from_le_bytes([extra_field[2], extra_field[3]]) as usize;
+            if id == AES_EXTRA_FIELD_ID {
+                return true;
+            }
+            extra_field = extra_field.get(4 + size..).unwrap_or_default();
+        }
+        false
+    }
+
+    /// Returns `true` if a ZIP entry is the VBA project of a macro-enabled Office Open XML
+    /// package.
+    #[cfg(feature = "reader-zip")]
+    fn is_zip_vba_project(filename: &str) -> bool {
+        filename == "vbaProject.bin" || filename.ends_with("/vbaProject.bin")
+    }
+
+    /// Determines file format from the data of a `[Content_Types].xml` ZIP entry, stored or
+    /// deflated, i.e. from the content type of the main part of an Office Open XML package, if it
+    /// identifies one.
+    #[cfg(feature = "reader-zip")]
+    fn from_zip_content_types(
+        data: &[u8],
+        compression_method: u16,
+        detection: &mut Detection,
+    ) -> Option<Self> {
+        // Compression methods of the entry.
+        const STORED: u16 = 0;
+        const DEFLATED: u16 = 8;
+
+        // Content type of the VBA project part.
+        const VBA_PROJECT_CONTENT_TYPE: &str = "application/vnd.ms-office.vbaProject";
+
+        // Content types of the main parts, along with the file formats they identify.
+        let main_content_types: &[(&str, Self)] = &[
+            (
+                "application/vnd.ms-excel.addin.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledSpreadsheetAddIn,
+            ),
+            (
+                "application/vnd.ms-excel.sheet.binary.macroEnabled.main",
+                Self::OfficeOpenXmlBinarySpreadsheet,
+            ),
+            (
+                "application/vnd.ms-excel.sheet.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledSpreadsheet,
+            ),
+            (
+                "application/vnd.ms-excel.template.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledSpreadsheetTemplate,
+            ),
+            (
+                "application/vnd.ms-powerpoint.addin.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledPresentationAddIn,
+            ),
+            (
+                "application/vnd.ms-powerpoint.presentation.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledPresentation,
+            ),
+            (
+                "application/vnd.ms-powerpoint.slideshow.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledSlideshow,
+            ),
+            (
+                "application/vnd.ms-powerpoint.template.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledPresentationTemplate,
+            ),
+            ("application/vnd.ms-visio.drawing.main+xml", Self::OfficeOpenXmlDrawing),
+            (
+                "application/vnd.ms-word.document.macroEnabled.main+xml",
+                Self::OfficeOpenXmlMacroEnabledDocument,
+            ),
+            (
+                "application/vnd.ms-word.template.macroEnabledTemplate.main+xml",
+                Self::OfficeOpenXmlMacroEnabledDocumentTemplate,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml",
+                Self::OfficeOpenXmlPresentation,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.presentationml.slideshow.main+xml",
+                Self::OfficeOpenXmlSlideshow,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.presentationml.template.main+xml",
+                Self::OfficeOpenXmlPresentationTemplate,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml",
+                Self::OfficeOpenXmlSpreadsheet,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.template.main+xml",
+                Self::OfficeOpenXmlSpreadsheetTemplate,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml",
+                Self::OfficeOpenXmlDocument,
+            ),
+            (
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.template.main+xml",
+                Self::OfficeOpenXmlDocumentTemplate,
+            ),
+        ];
+
+        // Inflates the data if needed, a truncated stream still yielding the data inflated so
+        // far.
+        let inflated;
+        let data = match compression_method {
+            STORED => data,
+            DEFLATED => {
+                inflated =
+                    miniz_oxide::inflate::decompress_to_vec_with_limit(data, CONTENT_TYPES_LIMIT)
+                        .unwrap_or_else(|error| error.output);
+                &inflated
+            }
+            _ => return None,
+        };
+
+        // Checks whether a VBA project part is declared.
+        if data.holds(VBA_PROJECT_CONTENT_TYPE) {
+            detection.has_macros = true;
+        }
+
+        // Checks the content types of the main parts.
+        main_content_types
+            .iter()
+            .find(|(content_type, _)| data.holds(content_type))
+            .map(|&(content_type, fmt)| detection.mark(content_type, fmt))
+    }
+
+    /// Determines file format from the name of a ZIP entry, if it identifies one.
+    #[cfg(feature = "reader-zip")]
+    fn from_zip_filename(filename: &str) -> Option<Self> {
+        match filename {
+            "AndroidManifest.xml" => Some(Self::AndroidPackage),
+            "AppManifest.xaml" => Some(Self::Xap),
+            "AppxManifest.xml" => Some(Self::WindowsAppPackage),
+            "AppxMetadata/AppxBundleManifest.xml" => Some(Self::WindowsAppBundle),
+            "BundleConfig.pb" => Some(Self::AndroidAppBundle),
+            "DOMDocument.xml" => Some(Self::FlashCs5Project),
+            "META-INF/AIR/application.xml" => Some(Self::AdobeIntegratedRuntime),
+            "META-INF/application.xml" => Some(Self::EnterpriseApplicationArchive),
+            "META-INF/mozilla.rsa" => Some(Self::Xpinstall),
+            "WEB-INF/web.xml" => Some(Self::WebApplicationArchive),
+            "doc.kml" => Some(Self::KeyholeMarkupLanguageZip),
+            "document.json" => Some(Self::Sketch43),
+            "extension.vsixmanifest" => Some(Self::MicrosoftVisualStudioExtension),
+            _ => {
+                if filename.starts_with("Fusion[Active]/") {
+                    Some(Self::Autodesk123d)
+                } else if filename.starts_with("circuitdiagram/") {
+                    Some(Self::CircuitDiagramDocument)
+                } else if filename.starts_with("dwf/") {
+                    Some(Self::DesignWebFormatXps)
+                } else if filename.ends_with(".fb2") && !filename.contains('/') {
+                    Some(Self::FictionbookZip)
+                } else if filename.starts_with("FusionAssetName[Active]/") {
+                    Some(Self::Fusion360)
+                } else if filename.starts_with("Payload/") && filename.contains(".app/") {
+                    Some(Self::IosAppStorePackage)
+                } else if filename.starts_with("word/") {
+                    Some(Self::OfficeOpenXmlDocument)
+                } else if filename.starts_with("visio/") {
+                    Some(Self::OfficeOpenXmlDrawing)
+                } else if filename.starts_with("ppt/") {
+                    Some(Self::OfficeOpenXmlPresentation)
+                } else if filename.starts_with("xl/") {
+                    Some(Self::OfficeOpenXmlSpreadsheet)
+                } else if filename.starts_with("Documents/") && filename.ends_with(".fpage") {
+                    Some(Self::Openxps)
+                } else if filename.starts_with("SpaceClaim/") {
+                    Some(Self::SpaceclaimDocument)
+                } else if filename.starts_with("3D/") && filename.ends_with(".model") {
+                    Some(Self::ThreeDimensionalManufacturingFormat)
+                } else if (filename.ends_with(".usd")
+                    || filename.ends_with(".usda")
+                    || filename.ends_with(".usdc"))
+                    && !filename.contains('/')
+                {
+                    Some(Self::UniversalSceneDescriptionZip)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Determines file format from the data of a stored `mimetype` ZIP entry.
+    #[cfg(feature = "reader-zip")]
+    fn from_zip_mimetype(data: &str, detection: &mut Detection) -> Self {
+        // Checks the trimmed data.
+        let data = data.trim();
+        let fmt = match data {
+            "application/epub+zip" => Self::ElectronicPublication,
+            "application/vnd.adobe.indesign-idml-package" => Self::IndesignMarkupLanguage,
+            "application/vnd.oasis.opendocument.base"
+            | "application/vnd.oasis.opendocument.database" => Self::OpendocumentDatabase,
+            "application/vnd.oasis.opendocument.formula" => Self::OpendocumentFormula,
+            "application/vnd.oasis.opendocument.formula-template" => {
+                Self::OpendocumentFormulaTemplate
+            }
+            "application/vnd.oasis.opendocument.graphics" => Self::OpendocumentGraphics,
+            "application/vnd.oasis.opendocument.graphics-template" => {
+                Self::OpendocumentGraphicsTemplate
+            }
+            "application/vnd.oasis.opendocument.presentation" => Self::OpendocumentPresentation,
+            "application/vnd.oasis.opendocument.presentation-template" => {
+                Self::OpendocumentPresentationTemplate
+            }
+            "application/vnd.oasis.opendocument.spreadsheet" => Self::OpendocumentSpreadsheet,
+            "application/vnd.oasis.opendocument.spreadsheet-template" => {
+                Self::OpendocumentSpreadsheetTemplate
+            }
+            "application/vnd.oasis.opendocument.text" => Self::OpendocumentText,
+            "application/vnd.oasis.opendocument.text-master" => Self::OpendocumentTextMaster,
+            "application/vnd.oasis.opendocument.text-master-template" => {
+                Self::OpendocumentTextMasterTemplate
+            }
+            "application/vnd.oasis.opendocument.text-template" => Self::OpendocumentTextTemplate,
+            "application/vnd.recordare.musicxml" => Self::MusicxmlZip,
+            "application/vnd.sun.xml.calc" => Self::SunXmlCalc,
+            "application/vnd.sun.xml.calc.template" => Self::SunXmlCalcTemplate,
+            "application/vnd.sun.xml.draw" => Self::SunXmlDraw,
+            "application/vnd.sun.xml.draw.template" => Self::SunXmlDrawTemplate,
+            "application/vnd.sun.xml.impress" => Self::SunXmlImpress,
+            "application/vnd.sun.xml.impress.template" => Self::SunXmlImpressTemplate,
+            "application/vnd.sun.xml.math" => Self::SunXmlMath,
+            "application/vnd.sun.xml.writer" => Self::SunXmlWriter,
+            "application/vnd.sun.xml.writer.global" => Self::SunXmlWriterGlobal,
+            "application/vnd.sun.xml.writer.template" => Self::SunXmlWriterTemplate,
+            "image/openraster" => Self::Openraster,
+            _ => return Self::Zip,
+        };
+        detection.mark(data, fmt)
+    }
+
+    /// Computes the offset at which the image of an ELF reader ends, from its program headers and
+    /// section headers.
+    #[cfg(feature = "reader-elf")]
+    fn elf_image_end<R: Read + Seek>(mut reader: R) -> Result<u64> {
+        // Type of the sections occupying no space in the file.
+        const SHT_NOBITS: u64 = 8;
+
+        // Reads the identification, then the rest of the header according to the class.
+        reader.seek(SeekFrom::Start(0))?;
+        let mut header = reader.read_bytes(16)?;
+        let width = match header[4] {
+            1 => 4,
+            2 => 8,
+            _ => return Err(Error::new(ErrorKind::InvalidData, "invalid ELF class")),
+        };
+        header.extend(reader.read_bytes(24 + 3 * width)?);
+
+        // Decodes an unsigned integer in the byte order of the file.
+        let big_endian = header[5] == 2;
+        let uint = |bytes: &[u8]| {
+            let fold = |value: u64, &byte: &u8| value << 8 | byte as u64;
+            if big_endian {
+                bytes.iter().fold(0, fold)
+            } else {
+                bytes.iter().rev().fold(0, fold)
+            }
+        };
+
+        // Reads the offsets, the entry sizes and the numbers of program and section headers.
+        let ph_offset = uint(&header[24 + width..24 + 2 * width]);
+        let sh_offset = uint(&header[24 + 2 * width..24 + 3 * width]);
+        let ph_entry_size = uint(&header[30 + 3 * width..32 + 3 * width]);
+        let ph_number = uint(&header[32 + 3 * width..34 + 3 * width]);
+        let sh_entry_size = uint(&header[34 + 3 * width..36 + 3 * width]);
+        let sh_number = uint(&header[36 + 3 * width..38 + 3 * width]);
+        let mut end = [
+            header.len() as u64,
+            ph_offset.saturating_add(ph_entry_size * ph_number),
+            sh_offset.saturating_add(sh_entry_size * sh_number),
+        ]
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default();
+
+        // Iterates through the program headers, extending the end to their segments.
+        if ph_number > 0 {
+            if ph_entry_size < 5 * width as u64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "invalid program header size",
+                ));
+            }
+            for index in 0..ph_number {
+                reader.seek(SeekFrom::Start(
+                    ph_offset.saturating_add(index * ph_entry_size),
+                ))?;
+                let entry = reader.read_bytes(5 * width)?;
+                let offset = uint(&entry[width..2 * width]);
+                let size = uint(&entry[4 * width..5 * width]);
+                end = end.max(offset.saturating_add(size));
+            }
+        }
+
+        // Iterates through the section headers, extending the end to the sections stored.
+        if sh_number > 0 {
+            if sh_entry_size < 8 + 4 * width as u64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "invalid section header size",
+                ));
+            }
+            for index in 0..sh_number {
+                reader.seek(SeekFrom::Start(
+                    sh_offset.saturating_add(index * sh_entry_size),
+                ))?;
+                let entry = reader.read_bytes(8 + 4 * width)?;
+                if uint(&entry[4..8]) != SHT_NOBITS {
+                    let offset = uint(&entry[8 + 2 * width..8 + 3 * width]);
+                    let size = uint(&entry[8 + 3 * width..8 + 4 * width]);
+                    end = end.max(offset.saturating_add(size));
+                }
+            }
+        }
+        Ok(end)
+    }
+
+    /// Determines the file format of the data appended to an executable image, lying within the
+    /// specified bounds, if any.
+    #[cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+    fn from_overlay<R: Read + Seek>(
+        mut reader: R,
+        detector: &Detector,
+        bounds: Range<u64>,
+    ) -> Result<Option<(u64, Self)>> {
+        // Maximum nesting depth of overlays that can be processed by the reader.
+        const DEPTH_LIMIT: usize = 4;
+
+        // Checks that the image is not nested too deep in the overlays of other images.
+        if detector.overlay_depth >= DEPTH_LIMIT {
+            return Ok(None);
+        }
+
+        // Retrieves the stream length.
+        let len = reader.seek(SeekFrom::End(0))?;
+        let end = bounds.end.min(len);
+        if bounds.start >= end {
+            return Ok(None);
+        }
+
+        // Detects the file format of the overlay, read as a stream of its own.
+        let mut detector = detector.clone();
+        detector.overlay_depth += 1;
+        let mut overlay = Shifted::new(
+            Bounded::new(&mut reader as &mut dyn ReadSeek, end),
+            bounds.start,
+        );
+        overlay.seek(SeekFrom::Start(0))?;
+        Ok(Some((bounds.start, detector.detect(overlay)?.format)))
+    }
+
+    /// Computes the bounds of the overlay of a PE reader, from the end of the raw data of its
+    /// sections to the certificate table of a signed image, which is appended after the overlay.
+    #[cfg(feature = "reader-exe")]
+    fn pe_overlay_bounds<R: Read + Seek>(mut reader: R, offset: u64) -> Result<Range<u64>> {
+        // Size of a section header.
+        const SECTION_HEADER_SIZE: u64 = 40;
+
+        // Reads the number of sections and the size of the optional header.
+        reader.seek(SeekFrom::Start(offset + 6))?;
+        let number_of_sections = reader.read_u16_le()? as u64;
+        reader.seek(SeekFrom::Current(12))?;
+        let optional_header_size = reader.read_u16_le()? as u64;
+
+        // Iterates through the section headers, extending the end to the raw data of the sections.
+        let optional_header_offset = offset + 24;
+        let section_table_offset = optional_header_offset + optional_header_size;
+        let mut end = section_table_offset + number_of_sections * SECTION_HEADER_SIZE;
+        reader.seek(SeekFrom::Start(section_table_offset))?;
+        for _ in 0..number_of_sections {
+            reader.seek(SeekFrom::Current(16))?;
+            let size_of_raw_data = reader.read_u32_le()? as u64;
+            let pointer_to_raw_data = reader.read_u32_le()? as u64;
+            reader.seek(SeekFrom::Current(16))?;
+            if size_of_raw_data > 0 {
+                end = end.max(pointer_to_raw_data + size_of_raw_data);
+            }
+        }
+
+        // Locates the certificate table, which is appended after the overlay of a signed image.
+        if optional_header_size >= 2 {
+            reader.seek(SeekFrom::Start(optional_header_offset))?;
+            let data_directories_offset = match reader.read_u16_le()? {
+                0x10B => 96,
+                0x20B => 112,
+                _ => return Ok(end..u64::MAX),
+            };
+            if optional_header_size >= data_directories_offset + 40 {
+                reader.seek(SeekFrom::Start(
+                    optional_header_offset + data_directories_offset + 32,
+                ))?;
+                let certificate_table_offset = reader.read_u32_le()? as u64;
+                let certificate_table_size = reader.read_u32_le()? as u64;
+                if certificate_table_size > 0 {
+                    return Ok(end..certificate_table_offset);
+                }
+            }
+        }
+        Ok(end..u64::MAX)
+    }
 }
 
 /// A trait for convenient data reading.
 #[allow(dead_code)]
-trait ReadData: Read {
+pub(crate) trait ReadData: Read {
     /// Reads a specified number of bytes into a `Vec<u8>`.
     #[inline]
     fn read_bytes(&mut self, size: usize) -> Result<Vec<u8>> {
@@ -1069,6 +2400,14 @@ trait ReadData: Read {
         Ok(buf[0])
     }
 
+    /// Reads a `u16` value in big-endian byte order.
+    #[inline]
+    fn read_u16_be(&mut self) -> Result<u16> {
+        let mut buf = [0; 2];
+        self.read_exact(&mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
     /// Reads a `u16` value in little-endian byte order.
     #[inline]
     fn read_u16_le(&mut self) -> Result<u16> {
@@ -1116,7 +2455,7 @@ impl<R: Read> ReadData for R {}
 
 /// A trait for finding a byte pattern within data.
 #[allow(dead_code)]
-trait FindBytes: AsRef<[u8]> {
+pub(crate) trait FindBytes: AsRef<[u8]> {
     /// Searches for the specified byte pattern and returns the index of the first occurrence.
     fn find<P: AsRef<[u8]>>(&self, pat: P) -> Option<usize> {
         // Retrieves references to data and pattern.
@@ -0,0 +1,367 @@
+//! Parallel detection over a directory tree.
+
+use crate::{Detector, FileFormat};
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::{Read, Result, Seek, SeekFrom},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// A recursive directory scanner, determining the file format of every regular file of a tree
+/// with a bounded pool of threads.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// use file_format::Scanner;
+///
+/// let scanner = Scanner::builder()
+///     .threads(4)
+///     .skip_hidden(true)
+///     .max_depth(2)
+///     .build();
+///
+/// for (path, result) in scanner.scan("fixtures") {
+///     match result {
+///         Ok(fmt) => println!("{}: {}", path.display(), fmt),
+///         Err(error) => eprintln!("{}: {}", path.display(), error),
+///     }
+/// }
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Scanner {
+    /// Detector used on every file.
+    detector: Detector,
+    /// Number of threads detecting the files.
+    threads: usize,
+    /// Whether symbolic links are followed.
+    follow_symlinks: bool,
+    /// Whether hidden files and directories are skipped.
+    skip_hidden: bool,
+    /// Maximum depth of the files, relative to the root, if any.
+    max_depth: Option<usize>,
+    /// Maximum number of bytes read from each file, if any.
+    read_limit: Option<u64>,
+}
+
+impl Scanner {
+    /// Returns a builder of scanner, initialized with the default options.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::Scanner;
+    ///
+    /// let scanner = Scanner::builder().build();
+    /// assert_eq!(scanner, Scanner::default());
+    ///```
+    #[inline]
+    pub fn builder() -> ScannerBuilder {
+        ScannerBuilder(Self::default())
+    }
+
+    /// Walks the tree rooted at the specified path and determines the file format of every regular
+    /// file it contains.
+    ///
+    /// The files are detected in parallel, hence the results are yielded in no particular order.
+    /// Errors raised while walking the tree are yielded along with the path of the directory or
+    /// entry which could not be read. Dropping the returned iterator stops the scan.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use file_format::{FileFormat, Scanner};
+    ///
+    /// let results: Vec<_> = Scanner::default().scan("fixtures/image").collect();
+    /// assert!(results
+    ///     .iter()
+    ///     .any(|(_, result)| matches!(result, Ok(FileFormat::PortableNetworkGraphics))));
+    ///```
+    pub fn scan<P: AsRef<Path>>(&self, root: P) -> Scan {
+        let (path_sender, path_receiver) = mpsc::sync_channel::<PathBuf>(self.threads);
+        let (result_sender, result_receiver) = mpsc::sync_channel(self.threads);
+
+        // Spawns the threads detecting the files.
+        let path_receiver = Arc::new(Mutex::new(path_receiver));
+        let detector = Arc::new(self.detector.clone());
+        for _ in 0..self.threads {
+            let path_receiver = Arc::clone(&path_receiver);
+            let result_sender = result_sender.clone();
+            let detector = Arc::clone(&detector);
+            let read_limit = self.read_limit;
+            thread::spawn(move || loop {
+                let path = match path_receiver.lock() {
+                    Ok(receiver) => match receiver.recv() {
+                        Ok(path) => path,
+                        Err(_) => break,
+                    },
+                    Err(_) => break,
+                };
+                let result = detect(&detector, &path, read_limit);
+                if result_sender.send((path, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Spawns the thread walking the tree.
+        let walker = Walker {
+            follow_symlinks: self.follow_symlinks,
+            skip_hidden: self.skip_hidden,
+            max_depth: self.max_depth,
+            visited: HashSet::new(),
+            paths: path_sender,
+            results: result_sender,
+        };
+        let root = root.as_ref().to_path_buf();
+        thread::spawn(move || walker.walk(root));
+
+        Scan {
+            results: result_receiver,
+        }
+    }
+}
+
+impl Default for Scanner {
+    /// Returns a scanner using the [default](`Detector::default`) detector and as many threads as
+    /// the available parallelism, which neither follows symbolic links nor skips hidden files, and
+    /// reads files entirely at any depth.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            detector: Detector::default(),
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            follow_symlinks: false,
+            skip_hidden: false,
+            max_depth: None,
+            read_limit: None,
+        }
+    }
+}
+
+/// A builder of [`Scanner`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use file_format::{Detector, Scanner};
+///
+/// let scanner = Scanner::builder()
+///     .detector(Detector::builder().probe_size(1024).build())
+///     .follow_symlinks(true)
+///     .read_limit(1 << 20)
+///     .build();
+///```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ScannerBuilder(Scanner);
+
+impl ScannerBuilder {
+    /// Sets the detector used on every file (the [default](`Detector::default`) one by default).
+    #[inline]
+    #[must_use]
+    pub fn detector(mut self, detector: Detector) -> Self {
+        self.0.detector = detector;
+        self
+    }
+
+    /// Sets the number of threads detecting the files (the available parallelism by default).
+    ///
+    /// A value of 0 is treated as 1.
+    #[inline]
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.0.threads = threads.max(1);
+        self
+    }
+
+    /// Sets whether symbolic links are followed (disabled by default).
+    ///
+    /// When disabled, symbolic links are skipped. When enabled, each directory is only walked
+    /// once, which prevents cycles.
+    #[inline]
+    #[must_use]
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.0.follow_symlinks = follow;
+        self
+    }
+
+    /// Sets whether hidden files and directories, whose name starts with a dot, are skipped
+    /// (disabled by default).
+    #[inline]
+    #[must_use]
+    pub fn skip_hidden(mut self, skip: bool) -> Self {
+        self.0.skip_hidden = skip;
+        self
+    }
+
+    /// Sets the maximum depth of the files, relative to the root (unlimited by default).
+    ///
+    /// A depth of 1 only scans the files directly contained in the root.
+    #[inline]
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.0.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets the maximum number of bytes read from each file (unlimited by default).
+    ///
+    /// Beyond this budget, a file is detected as if it ended, which may prevent a format reader
+    /// from concluding.
+    #[inline]
+    #[must_use]
+    pub fn read_limit(mut self, limit: u64) -> Self {
+        self.0.read_limit = Some(limit);
+        self
+    }
+
+    /// Builds the scanner.
+    #[inline]
+    pub fn build(self) -> Scanner {
+        self.0
+    }
+}
+
+/// An iterator over the results of a [`Scanner`], yielding each path along with its file format.
+///
+/// Dropping it stops the scan.
+#[derive(Debug)]
+pub struct Scan {
+    /// Results sent by the threads.
+    results: Receiver<(PathBuf, Result<FileFormat>)>,
+}
+
+impl Iterator for Scan {
+    type Item = (PathBuf, Result<FileFormat>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.results.recv().ok()
+    }
+}
+
+/// Walker of a directory tree, sending the paths of the regular files to the detecting threads.
+struct Walker {
+    /// Whether symbolic links are followed.
+    follow_symlinks: bool,
+    /// Whether hidden files and directories are skipped.
+    skip_hidden: bool,
+    /// Maximum depth of the files, relative to the root, if any.
+    max_depth: Option<usize>,
+    /// Canonical paths of the directories already walked.
+    visited: HashSet<PathBuf>,
+    /// Sender of the paths to detect.
+    paths: SyncSender<PathBuf>,
+    /// Sender of the errors raised while walking.
+    results: SyncSender<(PathBuf, Result<FileFormat>)>,
+}
+
+impl Walker {
+    /// Walks the tree rooted at the specified path, until it is exhausted or the scan is dropped.
+    fn walk(mut self, root: PathBuf) {
+        let mut stack = vec![(root, 0)];
+        while let Some((path, depth)) = stack.pop() {
+            // Retrieves the metadata, following the symbolic link only if enabled.
+            let metadata = if self.follow_symlinks || depth == 0 {
+                fs::metadata(&path)
+            } else {
+                fs::symlink_metadata(&path)
+            };
+            let metadata = match metadata {
+                Ok(metadata) => metadata,
+                Err(error) => {
+                    if self.results.send((path, Err(error))).is_err() {
+                        return;
+                    }
+                    continue;
+                }
+            };
+
+            if metadata.is_file() {
+                if self.paths.send(path).is_err() {
+                    return;
+                }
+            } else if metadata.is_dir() && self.max_depth.map_or(true, |max| depth < max) {
+                // Skips the directories already walked through a symbolic link.
+                if self.follow_symlinks {
+                    if let Ok(canonical) = fs::canonicalize(&path) {
+                        if !self.visited.insert(canonical) {
+                            continue;
+                        }
+                    }
+                }
+
+                // Reads the entries of the directory.
+                let entries =
+                    match fs::read_dir(&path).and_then(Iterator::collect::<Result<Vec<_>>>) {
+                        Ok(entries) => entries,
+                        Err(error) => {
+                            if self.results.send((path, Err(error))).is_err() {
+                                return;
+                            }
+                            continue;
+                        }
+                    };
+
+                // Pushes the entries in reverse order, so that they are walked in order.
+                for entry in entries.into_iter().rev() {
+                    if self.skip_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                        continue;
+                    }
+                    stack.push((entry.path(), depth + 1));
+                }
+            }
+        }
+    }
+}
+
+/// Determines the file format of a file, reading at most the specified number of bytes.
+fn detect(detector: &Detector, path: &Path, read_limit: Option<u64>) -> Result<FileFormat> {
+    let file = File::open(path)?;
+    match read_limit {
+        Some(remaining) => detector.from_reader(Budget {
+            inner: file,
+            remaining,
+        }),
+        None => detector.from_reader(file),
+    }
+}
+
+/// A reader which reports the end of the stream once a number of bytes have been read.
+struct Budget<R> {
+    /// Underlying reader.
+    inner: R,
+    /// Number of bytes which may still be read.
+    remaining: u64,
+}
+
+impl<R: Read> Read for Budget<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let len = std::cmp::min(buf.len() as u64, self.remaining) as usize;
+        let nread = self.inner.read(&mut buf[..len])?;
+        self.remaining -= nread as u64;
+        Ok(nread)
+    }
+}
+
+impl<R: Seek> Seek for Budget<R> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -0,0 +1,295 @@
+//! Verification of file extensions against the detected file format.
+
+use crate::FileFormat;
+
+#[cfg(feature = "std")]
+use std::{io::Result, path::Path};
+
+/// A verification of the extension of a file against the file format determined from its
+/// content.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// use file_format::{FileFormat, Verdict};
+///
+/// let verification = FileFormat::verify_path("fixtures/image/sample.png")?;
+/// assert_eq!(verification.detected, FileFormat::PortableNetworkGraphics);
+/// assert_eq!(verification.expected, &[FileFormat::PortableNetworkGraphics]);
+/// assert_eq!(verification.verdict, Verdict::Match);
+/// # Ok::<(), std::io::Error>(())
+///```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct Verification {
+    /// File format determined from the content.
+    pub detected: FileFormat,
+    /// File formats to which the extension maps, empty if the extension is missing or unknown.
+    pub expected: &'static [FileFormat],
+    /// Agreement between the extension and the content.
+    pub verdict: Verdict,
+}
+
+impl Verification {
+    /// Verifies an extension against the file format determined from the content.
+    pub(crate) fn new(detected: FileFormat, extension: Option<&str>) -> Self {
+        // Retrieves the file formats to which the extension maps.
+        let expected = extension.map_or(&[][..], extension_formats);
+
+        // Compares the file formats.
+        let verdict = if expected.is_empty() {
+            Verdict::Unknown
+        } else if expected.contains(&detected) {
+            Verdict::Match
+        } else if expected
+            .iter()
+            .any(|&fmt| base_format(fmt) == Some(detected) || base_format(detected) == Some(fmt))
+        {
+            Verdict::Compatible
+        } else {
+            Verdict::Mismatch
+        };
+        Self {
+            detected,
+            expected,
+            verdict,
+        }
+    }
+}
+
+/// Agreement between the extension of a file and its content.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "extended-enums",
+    derive(strum::EnumIter, strum::Display, strum::AsRefStr, strum::FromRepr)
+)]
+pub enum Verdict {
+    /// The detected file format is one to which the extension maps.
+    Match,
+    /// The detected file format is the container of one to which the extension maps, or the
+    /// other way around (e.g. `.jar` for a detected [ZIP](`FileFormat::Zip`) without the
+    /// `reader-zip` feature, or `.zip` for a detected [JAR](`FileFormat::JavaArchive`)).
+    Compatible,
+    /// The detected file format is unrelated to the ones to which the extension maps (e.g. `.jpg`
+    /// for a detected [EXE](`FileFormat::MsDosExecutable`)).
+    Mismatch,
+    /// The extension is missing or does not map to any file format.
+    Unknown,
+}
+
+impl FileFormat {
+    /// Verifies an extension against the file format determined from bytes.
+    ///
+    /// See [`FileFormat::verify_path`] for details.
+    ///
+    /// # Examples
+    ///
+    /// Flags an ELF executable uploaded with a `.jpg` extension:
+    ///
+    /// ```
+    /// use file_format::{FileFormat, Verdict};
+    ///
+    /// let verification = FileFormat::verify_bytes(b"\x7FELF\x02\x01\x01", "jpg");
+    /// assert_eq!(verification.detected, FileFormat::ExecutableAndLinkableFormat);
+    /// assert_eq!(verification.expected, &[FileFormat::JointPhotographicExpertsGroup]);
+    /// assert_eq!(verification.verdict, Verdict::Mismatch);
+    ///```
+    #[inline]
+    pub fn verify_bytes<B: AsRef<[u8]>>(bytes: B, extension: &str) -> Verification {
+        Verification::new(Self::from_bytes(bytes), Some(extension))
+    }
+
+    /// Verifies the extension of a file against the file format determined from its content.
+    ///
+    /// The extension is looked up with [`FileFormat::from_extension`], ignoring its case if it is
+    /// not found as is. A file format determined by a format reader is considered compatible with
+    /// the container it is based on, so that a verification remains meaningful when the
+    /// corresponding `reader-*` feature is disabled.
+    ///
+    /// # Examples
+    ///
+    /// Flags an executable renamed with a `.jpg` extension:
+    ///
+    /// ```no_run
+    /// use file_format::{FileFormat, Verdict};
+    ///
+    /// let verification = FileFormat::verify_path("upload.jpg")?;
+    /// if verification.verdict == Verdict::Mismatch {
+    ///     println!("{} disguised as {:?}", verification.detected, verification.expected);
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    ///```
+    #[cfg(feature = "std")]
+    pub fn verify_path<P: AsRef<Path>>(path: P) -> Result<Verification> {
+        let path = path.as_ref();
+        let detected = Self::from_file(path)?;
+        Ok(Verification::new(
+            detected,
+            path.extension().and_then(|extension| extension.to_str()),
+        ))
+    }
+}
+
+/// Returns the file formats to which the extension maps, ignoring its case if it is not found as
+/// is.
+pub(crate) fn extension_formats(extension: &str) -> &'static [FileFormat] {
+    FileFormat::from_extension(extension)
+        .or_else(|| FileFormat::from_extension(extension.to_ascii_lowercase()))
+        .unwrap_or_default()
+}
+
+/// Returns the container on which the specified file format is based, if it is determined by a
+/// format reader.
+pub(crate) const fn base_format(fmt: FileFormat) -> Option<FileFormat> {
+    use FileFormat::*;
+    Some(match fmt {
+        MicrosoftDigitalVideoRecording | WindowsMediaAudio | WindowsMediaVideo => {
+            AdvancedSystemsFormat
+        }
+        ThreeDimensionalStudioMax
+        | AutodeskInventorAssembly
+        | AutodeskInventorDrawing
+        | AutodeskInventorPart
+        | AutodeskInventorPresentation
+        | CorelPresentations7
+        | FlashProject
+        | MicrosoftExcelSpreadsheet
+        | MicrosoftPowerpointPresentation
+        | MicrosoftProjectPlan
+        | MicrosoftPublisherDocument
+        | MicrosoftSoftwareInstaller
+        | MicrosoftVisioDrawing
+        | MicrosoftWordDocument
+        | MicrosoftWorks6Spreadsheet
+        | MicrosoftWorksDatabase
+        | MicrosoftWorksWordProcessor
+        | SolidworksAssembly
+        | SolidworksDrawing
+        | SolidworksPart
+        | Starcalc
+        | Starchart
+        | Stardraw
+        | Starimpress
+        | Starmath
+        | Starwriter
+        | WordperfectDocument
+        | WordperfectGraphics => CompoundFileBinary,
+        Matroska3dVideo | MatroskaAudio | MatroskaSubtitles | MatroskaVideo | Webm => {
+            ExtensibleBinaryMetaLanguage
+        }
+        CabinetSelfExtractingArchive
+        | DynamicLinkLibrary
+        | LinearExecutable
+        | NewExecutable
+        | PortableExecutable
+        | RoshalSelfExtractingArchive
+        | SevenZipSelfExtractingArchive
+        | ZipSelfExtractingArchive => MsDosExecutable,
+        Mpeg4Part14Audio | Mpeg4Part14Subtitles | Mpeg4Part14Video => Mpeg4Part14,
+        AdobeIllustratorArtwork => PortableDocumentFormat,
+        Realaudio | Realvideo => Realmedia,
+        Sketch => Sqlite3,
+        Abiword
+        | AbiwordTemplate
+        | AdditiveManufacturingFormat
+        | AdvancedStreamRedirector
+        | Atom
+        | CollaborativeDesignActivity
+        | Extensible3d
+        | ExtensibleStylesheetLanguageTransformations
+        | Fictionbook
+        | GpsExchangeFormat
+        | GeographyMarkupLanguage
+        | KeyholeMarkupLanguage
+        | MpegDashMpd
+        | MathematicalMarkupLanguage
+        | Musicxml
+        | ReallySimpleSyndication
+        | ScalableVectorGraphics
+        | SimpleObjectAccessProtocol
+        | TiledMapXml
+        | TiledTilesetXml
+        | TimedTextMarkupLanguage
+        | TrainingCenterXml
+        | UniformOfficeFormatPresentation
+        | UniformOfficeFormatSpreadsheet
+        | UniformOfficeFormatText
+        | UniversalSubtitleFormat
+        | XmlLocalizationInterchangeFileFormat
+        | XmlShareablePlaylistFormat
+        | Drawio => ExtensibleMarkupLanguage,
+        ThreeDimensionalManufacturingFormat
+        | AdobeIntegratedRuntime
+        | AndroidAppBundle
+        | AndroidPackage
+        | Autodesk123d
+        | CircuitDiagramDocument
+        | DesignWebFormatXps
+        | ElectronicPublication
+        | EnterpriseApplicationArchive
+        | FictionbookZip
+        | FlashCs5Project
+        | Fusion360
+        | IndesignMarkupLanguage
+        | JavaArchive
+        | KeyholeMarkupLanguageZip
+        | MicrosoftVisualStudioExtension
+        | MusicxmlZip
+        | OfficeOpenXmlBinarySpreadsheet
+        | OfficeOpenXmlDocument
+        | OfficeOpenXmlDocumentTemplate
+        | OfficeOpenXmlDrawing
+        | OfficeOpenXmlMacroEnabledDocument
+        | OfficeOpenXmlMacroEnabledDocumentTemplate
+        | OfficeOpenXmlMacroEnabledPresentation
+        | OfficeOpenXmlMacroEnabledPresentationAddIn
+        | OfficeOpenXmlMacroEnabledPresentationTemplate
+        | OfficeOpenXmlMacroEnabledSlideshow
+        | OfficeOpenXmlMacroEnabledSpreadsheet
+        | OfficeOpenXmlMacroEnabledSpreadsheetAddIn
+        | OfficeOpenXmlMacroEnabledSpreadsheetTemplate
+        | OfficeOpenXmlPresentation
+        | OfficeOpenXmlPresentationTemplate
+        | OfficeOpenXmlSlideshow
+        | OfficeOpenXmlSpreadsheet
+        | OfficeOpenXmlSpreadsheetTemplate
+        | OpendocumentDatabase
+        | OpendocumentFormula
+        | OpendocumentFormulaTemplate
+        | OpendocumentGraphics
+        | OpendocumentGraphicsTemplate
+        | OpendocumentPresentation
+        | OpendocumentPresentationTemplate
+        | OpendocumentSpreadsheet
+        | OpendocumentSpreadsheetTemplate
+        | OpendocumentText
+        | OpendocumentTextMaster
+        | OpendocumentTextMasterTemplate
+        | OpendocumentTextTemplate
+        | Openraster
+        | Openxps
+        | Sketch43
+        | SpaceclaimDocument
+        | SunXmlCalc
+        | SunXmlCalcTemplate
+        | SunXmlDraw
+        | SunXmlDrawTemplate
+        | SunXmlImpress
+        | SunXmlImpressTemplate
+        | SunXmlMath
+        | SunXmlWriter
+        | SunXmlWriterGlobal
+        | SunXmlWriterTemplate
+        | UniversalSceneDescriptionZip
+        | WebApplicationArchive
+        | WindowsAppBundle
+        | WindowsAppPackage
+        | Xap
+        | Xpinstall
+        | IosAppStorePackage => Zip,
+        _ => return None,
+    })
+}
@@ -0,0 +1,170 @@
+//! Versions of file formats, read from their headers.
+
+use crate::FileFormat;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+/// Returns the version of a file format, read from the header held by the first bytes, if known.
+pub(crate) fn from_probe(fmt: FileFormat, probe: &[u8]) -> Option<String> {
+    match fmt {
+        FileFormat::ExecutableAndLinkableFormat => match probe.get(4)? {
+            1 => Some("ELF32".to_string()),
+            2 => Some("ELF64".to_string()),
+            _ => None,
+        },
+        FileFormat::ExtensibleBinaryMetaLanguage => from_ebml_header(probe),
+        FileFormat::GraphicsInterchangeFormat => {
+            Some(String::from_utf8_lossy(probe.get(3..6)?).into_owned())
+        }
+        FileFormat::AnimatedPortableNetworkGraphics | FileFormat::PortableNetworkGraphics => {
+            from_png_chunks(probe)
+        }
+        FileFormat::JavaClass => {
+            let minor = u16::from_be_bytes([*probe.get(4)?, *probe.get(5)?]);
+            let major = u16::from_be_bytes([*probe.get(6)?, *probe.get(7)?]);
+            Some(format!("{}.{}", major, minor))
+        }
+        FileFormat::Opentype | FileFormat::Truetype => from_sfnt_tables(probe),
+        FileFormat::PortableDocumentFormat => {
+            let version = probe.get(5..)?;
+            let len = version
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit() || **byte == b'.')
+                .count();
+            if len == 0 {
+                return None;
+            }
+            Some(String::from_utf8_lossy(&version[..len]).into_owned())
+        }
+        FileFormat::RoshalArchive => match probe.get(6)? {
+            0 => Some("1.5".to_string()),
+            1 => Some("5.0".to_string()),
+            _ => None,
+        },
+        FileFormat::Sqlite3 => {
+            let number = u32::from_be_bytes(probe.get(96..100)?.try_into().ok()?);
+            if number == 0 {
+                return None;
+            }
+            Some(format!(
+                "{}.{}.{}",
+                number / 1_000_000,
+                number / 1000 % 1000,
+                number % 1000
+            ))
+        }
+        FileFormat::TagImageFileFormat => {
+            let number = if probe.starts_with(b"II") {
+                *probe.get(2)?
+            } else {
+                *probe.get(3)?
+            };
+            match number {
+                42 => Some("TIFF".to_string()),
+                43 => Some("BigTIFF".to_string()),
+                _ => None,
+            }
+        }
+        FileFormat::Zip if probe.starts_with(b"PK\x03\x04") => {
+            let number = u16::from_le_bytes([*probe.get(4)?, *probe.get(5)?]);
+            Some(format!("{}.{}", number / 10, number % 10))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the DocTypeVersion of an EBML header, if any.
+fn from_ebml_header(probe: &[u8]) -> Option<String> {
+    // DocTypeVersion element ID.
+    const DOC_TYPE_VERSION_ELEMENT_ID: u64 = 0x4287;
+
+    // EBML element ID.
+    const EBML_ELEMENT_ID: u64 = 0x1A45DFA3;
+
+    // Reads the EBML element.
+    let (id, id_len) = read_vint(probe, true)?;
+    if id != EBML_ELEMENT_ID {
+        return None;
+    }
+    let (size, size_len) = read_vint(probe.get(id_len..)?, false)?;
+    let start = id_len + size_len;
+    let end = core::cmp::min(
+        probe.len(),
+        start.saturating_add(usize::try_from(size).ok()?),
+    );
+
+    // Iterates through the child elements.
+    let mut header = probe.get(start..end)?;
+    while !header.is_empty() {
+        let (id, id_len) = read_vint(header, true)?;
+        let (size, size_len) = read_vint(header.get(id_len..)?, false)?;
+        let size = usize::try_from(size).ok()?;
+        let data = header.get(id_len + size_len..)?.get(..size)?;
+        if id == DOC_TYPE_VERSION_ELEMENT_ID && size <= 8 {
+            let version = data
+                .iter()
+                .fold(0u64, |value, &byte| value << 8 | byte as u64);
+            return Some(version.to_string());
+        }
+        header = &header[id_len + size_len + size..];
+    }
+    None
+}
+
+/// Returns the variant of a PNG (`"APNG"` if an animation control chunk precedes the image data,
+/// `"PNG"` otherwise), if the chunks up to the image data are held by the first bytes.
+fn from_png_chunks(probe: &[u8]) -> Option<String> {
+    // Iterates through the chunks following the signature.
+    let mut chunks = probe.get(8..)?;
+    loop {
+        let len = u32::from_be_bytes(chunks.get(..4)?.try_into().ok()?) as usize;
+        match chunks.get(4..8)? {
+            b"acTL" => return Some("APNG".to_string()),
+            b"IDAT" | b"IEND" => return Some("PNG".to_string()),
+            _ => chunks = chunks.get(len.checked_add(12)?..)?,
+        }
+    }
+}
+
+/// Returns the outlines of an OpenType or TrueType font (`"CFF"`, `"CFF2"` or `"TrueType"`), read
+/// from its table directory, if held by the first bytes.
+fn from_sfnt_tables(probe: &[u8]) -> Option<String> {
+    // Size of the table directory header and of a table record.
+    const TABLE_DIRECTORY_SIZE: usize = 12;
+    const TABLE_RECORD_SIZE: usize = 16;
+
+    // Iterates through the table records.
+    let number_of_tables = u16::from_be_bytes(probe.get(4..6)?.try_into().ok()?) as usize;
+    let records = probe.get(TABLE_DIRECTORY_SIZE..)?;
+    records
+        .chunks_exact(TABLE_RECORD_SIZE)
+        .take(number_of_tables)
+        .find_map(|record| match &record[..4] {
+            b"CFF " => Some("CFF".to_string()),
+            b"CFF2" => Some("CFF2".to_string()),
+            b"glyf" => Some("TrueType".to_string()),
+            _ => None,
+        })
+}
+
+/// Reads an EBML variable-size integer, along with the number of bytes representing it.
+///
+/// The length marker is kept for element IDs and removed for element sizes.
+fn read_vint(bytes: &[u8], keep_marker: bool) -> Option<(u64, usize)> {
+    let first_byte = *bytes.first()?;
+    let len = first_byte.leading_zeros() as usize + 1;
+    if len > 8 {
+        return None;
+    }
+    let mut value = if keep_marker {
+        first_byte as u64
+    } else {
+        first_byte as u64 & (0xFF >> len)
+    };
+    for &byte in bytes.get(1..len)? {
+        value = value << 8 | byte as u64;
+    }
+    Some((value, len))
+}
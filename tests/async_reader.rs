@@ -0,0 +1,126 @@
+#![cfg(feature = "async")]
+
+use file_format::{Detector, FileFormat, Reader};
+use futures::{
+    executor::block_on,
+    io::{AllowStdIo, AsyncRead, AsyncSeek, Cursor},
+};
+use std::{
+    fs::File,
+    future::Future,
+    io::{Result, SeekFrom},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An asynchronous reader counting the bytes read from the inner reader.
+struct Counting<R> {
+    inner: R,
+    nread: usize,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for Counting<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = poll {
+            self.nread += n;
+        }
+        poll
+    }
+}
+
+impl<R: AsyncSeek + Unpin> AsyncSeek for Counting<R> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<Result<u64>> {
+        Pin::new(&mut self.inner).poll_seek(cx, pos)
+    }
+}
+
+fn from_async_file<P: AsRef<Path>>(path: P) -> FileFormat {
+    let file = AllowStdIo::new(File::open(path).unwrap());
+    block_on(FileFormat::from_async_reader(file)).unwrap()
+}
+
+#[test]
+fn test_async_empty() {
+    let fmt = block_on(FileFormat::from_async_reader(Cursor::new([]))).unwrap();
+    assert_eq!(fmt, FileFormat::Empty);
+}
+
+#[test]
+fn test_async_signature() {
+    let fmt = from_async_file("fixtures/image/sample.png");
+    assert_eq!(fmt, FileFormat::PortableNetworkGraphics);
+}
+
+#[test]
+fn test_async_matches_blocking() {
+    for kind in Path::new("fixtures").read_dir().unwrap() {
+        for fixture in kind.unwrap().path().read_dir().unwrap() {
+            let path = fixture.unwrap().path();
+            let fmt = FileFormat::from_file(&path).unwrap();
+            assert_eq!(from_async_file(&path), fmt, "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn test_async_detector() {
+    let detector = Detector::builder().reader(Reader::Zip, false).build();
+    let file = AllowStdIo::new(File::open("fixtures/document/sample.docx").unwrap());
+    let detection = block_on(detector.detect_async(file)).unwrap();
+    assert_eq!(detection.format, FileFormat::Zip);
+    assert_eq!(detection.reader, None);
+}
+
+#[test]
+fn test_async_fetches_blocks_once() {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    data.resize(2_097_152, b' ');
+    data.extend_from_slice(b"trailer\n<< /Size 1 >>\n%%EOF\n");
+    let len = data.len();
+    let mut reader = Counting {
+        inner: Cursor::new(data),
+        nread: 0,
+    };
+    let detection = block_on(FileFormat::detect_async_reader(&mut reader)).unwrap();
+    assert_eq!(detection.format, FileFormat::PortableDocumentFormat);
+    assert!(reader.nread <= len);
+}
+
+#[test]
+fn test_async_beyond_cache() {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    data.resize(8_388_608, b' ');
+    data.extend_from_slice(b"AIPrivateData1\ntrailer\n<< /Size 1 >>\n%%EOF\n");
+    let len = data.len();
+    let mut reader = Counting {
+        inner: Cursor::new(data),
+        nread: 0,
+    };
+    let detection = block_on(FileFormat::detect_async_reader(&mut reader)).unwrap();
+    assert_eq!(detection.format, FileFormat::AdobeIllustratorArtwork);
+    assert!(reader.nread <= len + 65_536);
+}
+
+#[test]
+fn test_async_interleaved() {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    data.resize(2_097_152, b' ');
+    let mut first = Box::pin(FileFormat::from_async_reader(Cursor::new(data)));
+    let waker = futures::task::noop_waker();
+    let _ = first.as_mut().poll(&mut Context::from_waker(&waker));
+    assert_eq!(
+        from_async_file("fixtures/image/sample.png"),
+        FileFormat::PortableNetworkGraphics
+    );
+    assert_eq!(block_on(first).unwrap(), FileFormat::PortableDocumentFormat);
+}
@@ -0,0 +1,110 @@
+//! Detection from bytes, which is also available without the `std` feature.
+
+use file_format::{FileFormat, Kind};
+
+#[test]
+fn test_metadata() {
+    let fmt = FileFormat::from_bytes(b"\x1F\x8B\x08");
+    assert_eq!(fmt, FileFormat::Gzip);
+    assert_eq!(fmt.name(), "gzip");
+    assert_eq!(fmt.media_type(), "application/gzip");
+    assert_eq!(fmt.extension(), "gz");
+    assert_eq!(fmt.kind(), Kind::Compressed);
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!(FileFormat::from_bytes([]), FileFormat::Empty);
+}
+
+#[test]
+fn test_arbitrary_binary_data() {
+    assert_eq!(
+        FileFormat::from_bytes([0; 1000]),
+        FileFormat::ArbitraryBinaryData
+    );
+}
+
+#[cfg(feature = "reader-asf")]
+#[test]
+fn test_windows_media_video() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/video/sample.wmv").unwrap());
+    assert_eq!(fmt, FileFormat::WindowsMediaVideo);
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_microsoft_word_document() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/document/sample.doc").unwrap());
+    assert_eq!(fmt, FileFormat::MicrosoftWordDocument);
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_matroska_video() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/video/sample.mkv").unwrap());
+    assert_eq!(fmt, FileFormat::MatroskaVideo);
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_dynamic_link_library() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/executable/sample.dll").unwrap());
+    assert_eq!(fmt, FileFormat::DynamicLinkLibrary);
+}
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_mpeg4_part14_video() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/video/sample.mp4").unwrap());
+    assert_eq!(fmt, FileFormat::Mpeg4Part14Video);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_adobe_illustrator_artwork() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/image/sample.ai").unwrap());
+    assert_eq!(fmt, FileFormat::AdobeIllustratorArtwork);
+}
+
+#[cfg(feature = "reader-rm")]
+#[test]
+fn test_realvideo() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/video/sample.rv").unwrap());
+    assert_eq!(fmt, FileFormat::Realvideo);
+}
+
+#[cfg(feature = "reader-sqlite3")]
+#[test]
+fn test_sqlite3() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/database/sample.sqlite").unwrap());
+    assert_eq!(fmt, FileFormat::Sqlite3);
+}
+
+#[cfg(feature = "reader-txt")]
+#[test]
+fn test_plain_text() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/other/sample.txt").unwrap());
+    assert_eq!(fmt, FileFormat::PlainText);
+}
+
+#[cfg(feature = "reader-txt")]
+#[test]
+fn test_plain_text_crlf() {
+    let fmt = FileFormat::from_bytes(b"first line\r\nsecond line\r\n");
+    assert_eq!(fmt, FileFormat::PlainText);
+}
+
+#[cfg(feature = "reader-xml")]
+#[test]
+fn test_scalable_vector_graphics() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/image/sample1.svg").unwrap());
+    assert_eq!(fmt, FileFormat::ScalableVectorGraphics);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_office_open_xml_document() {
+    let fmt = FileFormat::from_bytes(std::fs::read("fixtures/document/sample.docx").unwrap());
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlDocument);
+}
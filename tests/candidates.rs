@@ -0,0 +1,48 @@
+use file_format::FileFormat;
+
+#[test]
+fn test_candidates_empty() {
+    let candidates = FileFormat::candidates_from_bytes([]);
+    assert_eq!(candidates, vec![(FileFormat::Empty, 1.0)]);
+}
+
+#[test]
+fn test_candidates_unknown() {
+    let candidates = FileFormat::candidates_from_bytes([0; 1000]);
+    assert_eq!(candidates, vec![(FileFormat::ArbitraryBinaryData, 0.0)]);
+}
+
+#[test]
+fn test_candidates_ranking() {
+    let candidates = FileFormat::candidates_from_file("fixtures/audio/sample.oga").unwrap();
+    let formats: Vec<_> = candidates.iter().map(|&(fmt, _)| fmt).collect();
+    assert_eq!(
+        formats,
+        vec![
+            FileFormat::OggFlac,
+            FileFormat::OggMultiplexedMedia,
+            FileFormat::ArbitraryBinaryData
+        ]
+    );
+    assert!(candidates.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+}
+
+#[test]
+fn test_candidates_first_matches_from_file() {
+    let fmt = FileFormat::from_file("fixtures/presentation/sample1.shw").unwrap();
+    let candidates = FileFormat::candidates_from_file("fixtures/presentation/sample1.shw").unwrap();
+    assert_eq!(candidates[0].0, fmt);
+    assert!(candidates
+        .iter()
+        .any(|&(fmt, _)| fmt == FileFormat::WordperfectDocument));
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_candidates_confirmed_by_reader() {
+    let candidates = FileFormat::candidates_from_file("fixtures/document/sample.docx").unwrap();
+    assert_eq!(candidates[0].0, FileFormat::OfficeOpenXmlDocument);
+    let unconfirmed = FileFormat::candidates_from_bytes(b"PK\x03\x04");
+    assert_eq!(unconfirmed[0].0, FileFormat::Zip);
+    assert!(candidates[0].1 > unconfirmed[0].1);
+}
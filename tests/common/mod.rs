@@ -0,0 +1,108 @@
+//! Builders of the container files shared by the integration tests.
+
+#![allow(dead_code)]
+
+/// An entry of a ZIP archive built by [`zip`].
+#[derive(Clone, Copy, Default)]
+pub struct ZipEntry<'a> {
+    pub filename: &'a str,
+    pub flags: u16,
+    pub compression_method: u16,
+    pub extra_field: &'a [u8],
+    pub data: &'a [u8],
+}
+
+impl<'a> ZipEntry<'a> {
+    /// Returns an entry without flags nor extra field.
+    pub fn new(filename: &'a str, compression_method: u16, data: &'a [u8]) -> Self {
+        Self {
+            filename,
+            compression_method,
+            data,
+            ..Self::default()
+        }
+    }
+}
+
+/// Wraps the data into a single stored DEFLATE block, as found in a ZIP entry compressed with
+/// method 8.
+pub fn deflate_stored_block(data: &[u8]) -> Vec<u8> {
+    let len = data.len() as u16;
+    let mut block = vec![0x01];
+    block.extend_from_slice(&len.to_le_bytes());
+    block.extend_from_slice(&(!len).to_le_bytes());
+    block.extend_from_slice(data);
+    block
+}
+
+/// Builds the local file header of a ZIP entry, followed by its data.
+pub fn zip_local_file_header(entry: &ZipEntry) -> Vec<u8> {
+    let mut header = b"PK\x03\x04\x14\0".to_vec();
+    header.extend_from_slice(&entry.flags.to_le_bytes());
+    header.extend_from_slice(&entry.compression_method.to_le_bytes());
+    header.extend_from_slice(&[0; 8]);
+    header.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+    header.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+    header.extend_from_slice(&(entry.filename.len() as u16).to_le_bytes());
+    header.extend_from_slice(&(entry.extra_field.len() as u16).to_le_bytes());
+    header.extend_from_slice(entry.filename.as_bytes());
+    header.extend_from_slice(entry.extra_field);
+    header.extend_from_slice(entry.data);
+    header
+}
+
+/// Builds a ZIP archive holding the specified entries, along with its central directory.
+pub fn zip(entries: &[ZipEntry]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut central_directory = Vec::new();
+    for entry in entries {
+        let offset = data.len() as u32;
+        data.extend(zip_local_file_header(entry));
+        central_directory.extend_from_slice(b"PK\x01\x02\x14\0\x14\0");
+        central_directory.extend_from_slice(&entry.flags.to_le_bytes());
+        central_directory.extend_from_slice(&entry.compression_method.to_le_bytes());
+        central_directory.extend_from_slice(&[0; 8]);
+        central_directory.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(entry.filename.len() as u16).to_le_bytes());
+        central_directory.extend_from_slice(&(entry.extra_field.len() as u16).to_le_bytes());
+        central_directory.extend_from_slice(&[0; 10]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(entry.filename.as_bytes());
+        central_directory.extend_from_slice(entry.extra_field);
+    }
+    let cd_offset = data.len() as u32;
+    data.extend_from_slice(&central_directory);
+    data.extend_from_slice(b"PK\x05\x06\0\0\0\0");
+    data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    data.extend_from_slice(&cd_offset.to_le_bytes());
+    data.extend_from_slice(&[0; 2]);
+    data
+}
+
+/// Builds a CFB file with a 512-byte sector, whose root entry has the specified CLSID and is
+/// followed by entries of the specified names.
+pub fn cfb(clsid: &[u8], names: &[&str]) -> Vec<u8> {
+    let mut data = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1".to_vec();
+    data.resize(512, 0);
+    data[24..34].copy_from_slice(b"\x3E\0\x03\0\xFE\xFF\x09\0\x06\0");
+    data[44..52].copy_from_slice(b"\x01\0\0\0\x01\0\0\0");
+    data[76..80].copy_from_slice(&[0; 4]);
+    data.extend_from_slice(b"\xFD\xFF\xFF\xFF\xFE\xFF\xFF\xFF");
+    data.resize(1024, 0xFF);
+    for name in ["Root Entry"].iter().chain(names) {
+        let mut entry: Vec<u8> = name.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        entry.resize(64, 0);
+        entry.extend_from_slice(&(2 * name.len() as u16 + 2).to_le_bytes());
+        entry.resize(80, 0);
+        if *name == "Root Entry" {
+            entry.extend_from_slice(clsid);
+        }
+        entry.resize(128, 0);
+        data.extend_from_slice(&entry);
+    }
+    data.resize(1536, 0);
+    data
+}
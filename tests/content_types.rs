@@ -0,0 +1,142 @@
+#![cfg(feature = "reader-zip")]
+
+mod common;
+
+use common::{deflate_stored_block, zip, ZipEntry};
+use file_format::FileFormat;
+
+fn content_types(part_name: &str, content_type: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="xml" ContentType="application/xml"/><Override PartName="{}" ContentType="{}"/></Types>"#,
+        part_name, content_type
+    )
+}
+
+fn detect(part_name: &str, content_type: &str) -> FileFormat {
+    let content_types = content_types(part_name, content_type);
+    FileFormat::from_bytes(zip(&[
+        ZipEntry::new("[Content_Types].xml", 0, content_types.as_bytes()),
+        ZipEntry::new(&part_name[1..], 0, b""),
+    ]))
+}
+
+#[test]
+fn test_content_types_deflated() {
+    let content_types = content_types(
+        "/word/document.xml",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.template.main+xml",
+    );
+    let content_types = deflate_stored_block(content_types.as_bytes());
+    let detection = FileFormat::detect_bytes(zip(&[
+        ZipEntry::new("[Content_Types].xml", 8, &content_types),
+        ZipEntry::new("word/document.xml", 0, b""),
+    ]));
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocumentTemplate);
+    assert_eq!(
+        detection.marker.as_deref(),
+        Some("application/vnd.openxmlformats-officedocument.wordprocessingml.template.main+xml")
+    );
+}
+
+#[test]
+fn test_content_types_dotm() {
+    let fmt = detect(
+        "/word/document.xml",
+        "application/vnd.ms-word.template.macroEnabledTemplate.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlMacroEnabledDocumentTemplate);
+}
+
+#[test]
+fn test_content_types_dotx() {
+    let fmt = detect(
+        "/word/document.xml",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.template.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlDocumentTemplate);
+}
+
+#[test]
+fn test_content_types_potm() {
+    let fmt = detect(
+        "/ppt/presentation.xml",
+        "application/vnd.ms-powerpoint.template.macroEnabled.main+xml",
+    );
+    assert_eq!(
+        fmt,
+        FileFormat::OfficeOpenXmlMacroEnabledPresentationTemplate
+    );
+}
+
+#[test]
+fn test_content_types_potx() {
+    let fmt = detect(
+        "/ppt/presentation.xml",
+        "application/vnd.openxmlformats-officedocument.presentationml.template.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlPresentationTemplate);
+}
+
+#[test]
+fn test_content_types_ppam() {
+    let fmt = detect(
+        "/ppt/presentation.xml",
+        "application/vnd.ms-powerpoint.addin.macroEnabled.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlMacroEnabledPresentationAddIn);
+}
+
+#[test]
+fn test_content_types_ppsm() {
+    let fmt = detect(
+        "/ppt/presentation.xml",
+        "application/vnd.ms-powerpoint.slideshow.macroEnabled.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlMacroEnabledSlideshow);
+}
+
+#[test]
+fn test_content_types_ppsx() {
+    let fmt = detect(
+        "/ppt/presentation.xml",
+        "application/vnd.openxmlformats-officedocument.presentationml.slideshow.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlSlideshow);
+}
+
+#[test]
+fn test_content_types_unknown() {
+    let fmt = detect("/word/document.xml", "application/xml");
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlDocument);
+}
+
+#[test]
+fn test_content_types_xlam() {
+    let fmt = detect(
+        "/xl/workbook.xml",
+        "application/vnd.ms-excel.addin.macroEnabled.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetAddIn);
+}
+
+#[test]
+fn test_content_types_xltm() {
+    let fmt = detect(
+        "/xl/workbook.xml",
+        "application/vnd.ms-excel.template.macroEnabled.main+xml",
+    );
+    assert_eq!(
+        fmt,
+        FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetTemplate
+    );
+}
+
+#[test]
+fn test_content_types_xltx() {
+    let fmt = detect(
+        "/xl/workbook.xml",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.template.main+xml",
+    );
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlSpreadsheetTemplate);
+}
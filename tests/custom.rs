@@ -0,0 +1,69 @@
+use file_format::{CustomFormat, DetectedFormat, Detector, FileFormat, Kind, Precedence};
+
+fn foo() -> CustomFormat {
+    CustomFormat::new("Foo Archive", "application/x-foo", "foo", Kind::Archive)
+        .with_short_name("FOO")
+        .with_signature(&[(0, b"FOO\x01")])
+        .with_signature(&[(0, &b"BAR"[..]), (4, &b"\x02"[..])])
+}
+
+#[test]
+fn test_custom_after_builtin() {
+    let detector = Detector::builder()
+        .custom_format(foo(), Precedence::AfterBuiltin)
+        .build();
+    let fmt = detector.identify_bytes(b"FOO\x01 data");
+    assert_eq!(fmt.name(), "Foo Archive");
+    assert_eq!(fmt.short_name(), Some("FOO"));
+    assert_eq!(fmt.media_type(), "application/x-foo");
+    assert_eq!(fmt.extension(), "foo");
+    assert_eq!(fmt.kind(), Kind::Archive);
+    assert_eq!(fmt.builtin(), None);
+}
+
+#[test]
+fn test_custom_multipart_signature() {
+    let detector = Detector::builder()
+        .custom_format(foo(), Precedence::AfterBuiltin)
+        .build();
+    assert!(detector.identify_bytes(b"BAR\x00\x02").custom().is_some());
+    assert!(detector.identify_bytes(b"BAR\x00\x03").custom().is_none());
+}
+
+#[test]
+fn test_custom_does_not_shadow_builtin() {
+    let fmt = CustomFormat::new("Gzip Variant", "application/x-gz", "gzv", Kind::Compressed)
+        .with_signature(&[(0, b"\x1F\x8B")]);
+    let detector = Detector::builder()
+        .custom_format(fmt, Precedence::AfterBuiltin)
+        .build();
+    let fmt = detector.identify_bytes(b"\x1F\x8B\x08");
+    assert_eq!(fmt, DetectedFormat::Builtin(FileFormat::Gzip));
+}
+
+#[test]
+fn test_custom_before_builtin() {
+    let fmt = CustomFormat::new("Gzip Variant", "application/x-gz", "gzv", Kind::Compressed)
+        .with_signature(&[(0, b"\x1F\x8B")]);
+    let detector = Detector::builder()
+        .custom_format(fmt, Precedence::BeforeBuiltin)
+        .build();
+    let fmt = detector.identify_bytes(b"\x1F\x8B\x08");
+    assert_eq!(fmt.name(), "Gzip Variant");
+}
+
+#[test]
+fn test_custom_not_matched() {
+    let detector = Detector::builder()
+        .custom_format(foo(), Precedence::BeforeBuiltin)
+        .build();
+    let fmt = detector.identify_file("fixtures/image/sample.png").unwrap();
+    assert_eq!(
+        fmt,
+        DetectedFormat::Builtin(FileFormat::PortableNetworkGraphics)
+    );
+    assert_eq!(
+        detector.identify_bytes([]),
+        DetectedFormat::Builtin(FileFormat::Empty)
+    );
+}
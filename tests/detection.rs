@@ -0,0 +1,86 @@
+use file_format::FileFormat;
+#[cfg(any(
+    feature = "reader-cfb",
+    feature = "reader-txt",
+    feature = "reader-xml",
+    feature = "reader-zip"
+))]
+use file_format::Reader;
+
+#[test]
+fn test_detection_empty() {
+    let detection = FileFormat::detect_bytes([]);
+    assert_eq!(detection.format, FileFormat::Empty);
+    assert_eq!(detection.signature, None);
+    assert_eq!(detection.reader, None);
+}
+
+#[test]
+fn test_detection_signature() {
+    let detection = FileFormat::detect_file("fixtures/audio/sample.m4a").unwrap();
+    assert_eq!(detection.format, FileFormat::AppleItunesAudio);
+    let signature = detection.signature.unwrap();
+    assert_eq!(signature.format(), FileFormat::AppleItunesAudio);
+    assert_eq!(signature.parts(), &[(4, &b"ftypM4A"[..])]);
+    assert_eq!(signature.offset(), 4);
+    assert_eq!(signature.size(), 7);
+    assert_eq!(detection.reader, None);
+    assert_eq!(detection.marker, None);
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_detection_cfb_clsid() {
+    let detection = FileFormat::detect_file("fixtures/document/sample.doc").unwrap();
+    assert_eq!(detection.format, FileFormat::MicrosoftWordDocument);
+    assert_eq!(
+        detection.signature.unwrap().format(),
+        FileFormat::CompoundFileBinary
+    );
+    assert_eq!(detection.reader, Some(Reader::Cfb));
+    assert_eq!(
+        detection.marker.as_deref(),
+        Some("00020906-0000-0000-c000-000000000046")
+    );
+}
+
+#[cfg(feature = "reader-txt")]
+#[test]
+fn test_detection_generic_reader() {
+    let detection = FileFormat::detect_file("fixtures/other/sample.txt").unwrap();
+    assert_eq!(detection.format, FileFormat::PlainText);
+    assert_eq!(detection.signature, None);
+    assert_eq!(detection.reader, Some(Reader::Txt));
+}
+
+#[cfg(feature = "reader-xml")]
+#[test]
+fn test_detection_xml_root_marker() {
+    let detection = FileFormat::detect_file("fixtures/image/sample2.svg").unwrap();
+    assert_eq!(detection.format, FileFormat::ScalableVectorGraphics);
+    assert_eq!(detection.reader, Some(Reader::Xml));
+    assert_eq!(detection.marker.as_deref(), Some("<svg"));
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_detection_zip_entry() {
+    let detection = FileFormat::detect_file("fixtures/document/sample.docx").unwrap();
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    assert_eq!(detection.reader, Some(Reader::Zip));
+    assert_eq!(detection.marker.as_deref(), Some("word/"));
+    assert!(!detection.fell_back());
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_detection_fell_back() {
+    let detection = FileFormat::detect_bytes([&b"PK\x03\x04"[..], &[0; 64]].concat());
+    assert_eq!(detection.format, FileFormat::ArbitraryBinaryData);
+    assert_eq!(detection.signature.unwrap().format(), FileFormat::Zip);
+    assert!(detection.fell_back());
+    assert_eq!(
+        detection.reader_error.as_deref(),
+        Some("cannot find the EOCD")
+    );
+}
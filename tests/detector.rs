@@ -0,0 +1,87 @@
+#[cfg(any(feature = "reader-txt", feature = "reader-zip"))]
+use file_format::Reader;
+use file_format::{Detector, FileFormat, IncrementalDetector, Progress};
+
+#[test]
+fn test_detector_default() {
+    let detector = Detector::default();
+    let fmt = detector.from_file("fixtures/document/sample.docx").unwrap();
+    assert_eq!(
+        fmt,
+        FileFormat::from_file("fixtures/document/sample.docx").unwrap()
+    );
+}
+
+#[test]
+fn test_detector_probe_size() {
+    let detector = Detector::builder().probe_size(4).build();
+    let fmt = detector.from_file("fixtures/image/sample.png").unwrap();
+    assert_eq!(fmt, FileFormat::ArbitraryBinaryData);
+    let fmt = detector.from_file("fixtures/compressed/sample.gz").unwrap();
+    assert_eq!(fmt, FileFormat::Gzip);
+}
+
+#[test]
+fn test_detector_probe_size_zero() {
+    let detector = Detector::builder().probe_size(0).build();
+    assert_eq!(detector, Detector::builder().probe_size(1).build());
+    let fmt = detector.from_file("fixtures/image/sample.png").unwrap();
+    assert_eq!(fmt, FileFormat::ArbitraryBinaryData);
+    assert_eq!(detector.from_bytes([]), FileFormat::Empty);
+    let mut incremental = IncrementalDetector::with_detector(detector);
+    assert_eq!(
+        incremental.feed(b"\x89PNG\r\n\x1A\n"),
+        Progress::Done(FileFormat::ArbitraryBinaryData)
+    );
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_detector_pdf_read_limit() {
+    let detector = Detector::builder().pdf_read_limit(0).build();
+    let fmt = detector.from_file("fixtures/image/sample.ai").unwrap();
+    assert_eq!(fmt, FileFormat::PortableDocumentFormat);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_detector_zip_entry_limit() {
+    let detector = Detector::builder().zip_entry_limit(0).build();
+    let fmt = detector.from_file("fixtures/document/sample.docx").unwrap();
+    assert_eq!(fmt, FileFormat::Zip);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_detector_disabled_reader() {
+    let detector = Detector::builder().reader(Reader::Zip, false).build();
+    let detection = detector
+        .detect_file("fixtures/document/sample.docx")
+        .unwrap();
+    assert_eq!(detection.format, FileFormat::Zip);
+    assert_eq!(detection.reader, None);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_detector_reenabled_reader() {
+    let detector = Detector::builder()
+        .reader(Reader::Zip, false)
+        .reader(Reader::Zip, true)
+        .build();
+    assert_eq!(detector, Detector::default());
+}
+
+#[cfg(feature = "reader-txt")]
+#[test]
+fn test_detector_disabled_generic_reader() {
+    let detector = Detector::builder().reader(Reader::Txt, false).build();
+    assert_eq!(
+        detector.from_bytes(b"hello"),
+        FileFormat::ArbitraryBinaryData
+    );
+    assert_eq!(
+        Detector::default().from_bytes(b"hello"),
+        FileFormat::PlainText
+    );
+}
@@ -0,0 +1,69 @@
+use file_format::{EmbeddedScanner, FileFormat, Kind};
+use std::fs;
+#[cfg(feature = "std")]
+use std::io::Cursor;
+
+fn blob(paths: &[&str], padding: usize) -> (Vec<u8>, Vec<u64>) {
+    let mut data = vec![0; padding];
+    let mut offsets = Vec::new();
+    for path in paths {
+        offsets.push(data.len() as u64);
+        data.extend(fs::read(path).unwrap());
+        data.extend(vec![0; padding]);
+    }
+    (data, offsets)
+}
+
+#[test]
+fn test_scan_empty() {
+    assert!(EmbeddedScanner::default().scan_bytes([]).is_empty());
+}
+
+#[test]
+fn test_scan_png_and_7z() {
+    let (data, offsets) = blob(
+        &["fixtures/image/sample.png", "fixtures/archive/sample.7z"],
+        1000,
+    );
+    let matches = EmbeddedScanner::default().scan_bytes(&data);
+    assert!(matches.contains(&(offsets[0], FileFormat::PortableNetworkGraphics)));
+    assert!(matches.contains(&(offsets[1], FileFormat::SevenZip)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_scan_across_chunks() {
+    let (data, offsets) = blob(&["fixtures/image/sample.png"], 200_000);
+    let matches = EmbeddedScanner::default()
+        .scan_reader(Cursor::new(data))
+        .unwrap();
+    assert!(matches.contains(&(offsets[0], FileFormat::PortableNetworkGraphics)));
+}
+
+#[test]
+fn test_scan_zip() {
+    let (data, offsets) = blob(&["fixtures/archive/sample.zip"], 1000);
+    let matches = EmbeddedScanner::default().scan_bytes(&data);
+    assert_eq!(matches[0], (offsets[0], FileFormat::Zip));
+}
+
+#[test]
+fn test_scan_kinds() {
+    let (data, offsets) = blob(
+        &["fixtures/image/sample.png", "fixtures/archive/sample.7z"],
+        1000,
+    );
+    let scanner = EmbeddedScanner::builder().kinds([Kind::Archive]).build();
+    let matches = scanner.scan_bytes(&data);
+    assert_eq!(matches, [(offsets[1], FileFormat::SevenZip)]);
+}
+
+#[test]
+fn test_scan_min_signature_size() {
+    let (data, offsets) = blob(&["fixtures/compressed/sample.gz"], 1000);
+    let matches = EmbeddedScanner::default().scan_bytes(&data);
+    assert!(!matches.contains(&(offsets[0], FileFormat::Gzip)));
+    let scanner = EmbeddedScanner::builder().min_signature_size(2).build();
+    let matches = scanner.scan_bytes(&data);
+    assert!(matches.contains(&(offsets[0], FileFormat::Gzip)));
+}
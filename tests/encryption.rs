@@ -0,0 +1,208 @@
+mod common;
+
+#[cfg(feature = "reader-zip")]
+use common::{zip_local_file_header, ZipEntry};
+use file_format::FileFormat;
+
+#[cfg(feature = "reader-zip")]
+fn zip(filename: &str, flags: u16, compression_method: u16, extra_field: &[u8]) -> Vec<u8> {
+    common::zip(&[ZipEntry {
+        flags,
+        extra_field,
+        ..ZipEntry::new(filename, compression_method, b"")
+    }])
+}
+
+#[cfg(feature = "reader-cfb")]
+fn cfb(names: &[&str]) -> Vec<u8> {
+    common::cfb(&[0; 16], names)
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_encryption_cfb() {
+    let detection = FileFormat::detect_bytes(cfb(&["EncryptionInfo", "EncryptedPackage"]));
+    assert_eq!(detection.format, FileFormat::CompoundFileBinary);
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_encryption_cfb_without_encrypted_package() {
+    let detection = FileFormat::detect_bytes(cfb(&["EncryptionInfo", "Workbook"]));
+    assert_eq!(detection.format, FileFormat::CompoundFileBinary);
+    assert!(!detection.encrypted);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_encryption_pdf() {
+    let detection = FileFormat::detect_bytes(
+        b"%PDF-1.7\n1 0 obj\n<<>>\nendobj\ntrailer\n<< /Size 2 /Encrypt 1 0 R >>\n%%EOF\n",
+    );
+    assert_eq!(detection.format, FileFormat::PortableDocumentFormat);
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_encryption_pdf_encrypt_outside_trailer() {
+    let detection = FileFormat::detect_bytes(
+        b"%PDF-1.7\n1 0 obj\n<< /Title (/Encrypt) /Info << /Encrypt 2 0 R >> >>\nendobj\n2 0 obj\n<< /Encrypt true >>\nendobj\ntrailer\n<< /Size 3 /Info [/Encrypt] >>\n%%EOF\n",
+    );
+    assert_eq!(detection.format, FileFormat::PortableDocumentFormat);
+    assert!(!detection.encrypted);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_encryption_pdf_linearized() {
+    let mut data = b"%PDF-1.7\n1 0 obj\n<< /Linearized 1 >>\nendobj\ntrailer\n<< /Size 3 /Encrypt 2 0 R /ID [<AB> <CD>] >>\n".to_vec();
+    data.resize(16384, b' ');
+    data.extend_from_slice(b"trailer\n<< /Size 3 >>\n%%EOF\n");
+    let detection = FileFormat::detect_bytes(data);
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_encryption_pdf_cross_reference_stream() {
+    let detection = FileFormat::detect_bytes(
+        b"%PDF-1.7\n1 0 obj\n<< /Type /XRef % comment\n /Size 2 /Encrypt 2 0 R /W [1 2 1] >>\nstream\nendstream\nendobj\n%%EOF\n",
+    );
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_encryption_pdf_after_unterminated_objects() {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    for pattern in [
+        &b"obj<<("[..],
+        b"obj<<[obj<<[",
+        b"trailer<< /a (",
+        b"obj<< /k obj<< >> ",
+    ] {
+        data.extend(pattern.iter().cycle().take(4096));
+    }
+    data.extend_from_slice(b"\ntrailer\n<< /Encrypt 1 0 R >>\n%%EOF\n");
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::PortableDocumentFormat);
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_encryption_pdf_without_encrypt() {
+    let detection = FileFormat::detect_file("fixtures/document/sample.pdf").unwrap();
+    assert!(!detection.encrypted);
+}
+
+#[test]
+fn test_encryption_rar4_headers() {
+    let detection = FileFormat::detect_bytes(b"Rar!\x1A\x07\0\xCF\x90\x73\x80\0\x0D\0\0\0\0\0\0\0");
+    assert_eq!(detection.format, FileFormat::RoshalArchive);
+    assert!(detection.encrypted);
+}
+
+#[test]
+fn test_encryption_rar4_file() {
+    let mut data = b"Rar!\x1A\x07\0\xCF\x90\x73\0\0\x0D\0\0\0\0\0\0\0".to_vec();
+    data.extend_from_slice(b"\0\0\x74\x04\x80\x25\0\x10\0\0\0");
+    data.extend_from_slice(&[0; 26]);
+    let detection = FileFormat::detect_bytes(data);
+    assert!(detection.encrypted);
+}
+
+#[test]
+fn test_encryption_rar4_unencrypted() {
+    let mut data = b"Rar!\x1A\x07\0\xCF\x90\x73\0\0\x0D\0\0\0\0\0\0\0".to_vec();
+    data.extend_from_slice(b"\0\0\x74\0\x80\x25\0\x10\0\0\0");
+    data.extend_from_slice(&[0; 26]);
+    let detection = FileFormat::detect_bytes(data);
+    assert!(!detection.encrypted);
+}
+
+#[test]
+fn test_encryption_rar5_headers() {
+    let detection = FileFormat::detect_bytes(b"Rar!\x1A\x07\x01\0\0\0\0\0\x21\x04\0\0");
+    assert_eq!(detection.format, FileFormat::RoshalArchive);
+    assert!(detection.encrypted);
+}
+
+#[test]
+fn test_encryption_rar5_file() {
+    let mut data = b"Rar!\x1A\x07\x01\0\0\0\0\0\x03\x01\0\0".to_vec();
+    data.extend_from_slice(b"\0\0\0\0\x0C\x02\x03\x04\x00\0\0\0\0\x03\x01\0\0");
+    let detection = FileFormat::detect_bytes(data);
+    assert!(detection.encrypted);
+}
+
+#[test]
+fn test_encryption_seven_zip() {
+    let mut data = b"7z\xBC\xAF\x27\x1C\0\x04\0\0\0\0".to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&17u64.to_le_bytes());
+    data.extend_from_slice(&[0; 4]);
+    data.extend_from_slice(b"\x17\x06\0\x01\x09\x30\0\x07\x0B\x01\0\x01\x24\x06\xF1\x07\x01");
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::SevenZip);
+    assert!(detection.encrypted);
+}
+
+#[test]
+fn test_encryption_seven_zip_unencrypted() {
+    let mut data = b"7z\xBC\xAF\x27\x1C\0\x04\0\0\0\0".to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&2u64.to_le_bytes());
+    data.extend_from_slice(&[0; 4]);
+    data.extend_from_slice(b"\x01\0");
+    let detection = FileFormat::detect_bytes(data);
+    assert!(!detection.encrypted);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_encryption_zip() {
+    let detection = FileFormat::detect_bytes(zip("secret.txt", 0x0001, 8, b""));
+    assert_eq!(detection.format, FileFormat::Zip);
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_encryption_zip_after_mimetype() {
+    let entries = [
+        ZipEntry::new("mimetype", 0, b"application/epub+zip"),
+        ZipEntry {
+            flags: 0x0001,
+            ..ZipEntry::new("OEBPS/content.opf", 8, b"")
+        },
+    ];
+    let detection = FileFormat::detect_bytes(common::zip(&entries));
+    assert_eq!(detection.format, FileFormat::ElectronicPublication);
+    assert!(detection.encrypted);
+
+    // Without the central directory, the local file headers are browsed instead.
+    let data: Vec<u8> = entries.iter().flat_map(zip_local_file_header).collect();
+    let (detection, _) = FileFormat::detect_stream(&data[..]).unwrap();
+    assert_eq!(detection.format, FileFormat::ElectronicPublication);
+    assert!(detection.encrypted);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_encryption_zip_aes() {
+    let data = zip("secret.txt", 0, 0, b"\x01\x99\x07\0\x02\0AE\x03\x08\0");
+    assert!(FileFormat::detect_bytes(data).encrypted);
+    let data = zip("secret.txt", 0x0001, 99, b"");
+    assert!(FileFormat::detect_bytes(data).encrypted);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_encryption_zip_unencrypted() {
+    let detection = FileFormat::detect_bytes(zip("plain.txt", 0, 8, b"UT\x05\0\x03\0\0\0\0"));
+    assert_eq!(detection.format, FileFormat::Zip);
+    assert!(!detection.encrypted);
+}
@@ -1,5 +1,12 @@
 use file_format::FileFormat;
 
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_cabinet_self_extracting_archive() {
+    let fmt = FileFormat::from_file("fixtures/executable/sample8.exe").unwrap();
+    assert_eq!(fmt, FileFormat::CabinetSelfExtractingArchive);
+}
+
 #[test]
 fn test_commodore64_program() {
     let fmt = FileFormat::from_file("fixtures/executable/sample.prg").unwrap();
@@ -94,6 +101,20 @@ fn test_portable_executable() {
     assert_eq!(fmt, FileFormat::PortableExecutable);
 }
 
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_roshal_self_extracting_archive() {
+    let fmt = FileFormat::from_file("fixtures/executable/sample7.exe").unwrap();
+    assert_eq!(fmt, FileFormat::RoshalSelfExtractingArchive);
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_seven_zip_self_extracting_archive() {
+    let fmt = FileFormat::from_file("fixtures/executable/sample5.exe").unwrap();
+    assert_eq!(fmt, FileFormat::SevenZipSelfExtractingArchive);
+}
+
 #[test]
 fn test_webassembly_binary() {
     let fmt = FileFormat::from_file("fixtures/executable/sample.wasm").unwrap();
@@ -111,3 +132,10 @@ fn test_xbox_executable() {
     let fmt = FileFormat::from_file("fixtures/executable/sample.xbe").unwrap();
     assert_eq!(fmt, FileFormat::XboxExecutable);
 }
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_zip_self_extracting_archive() {
+    let fmt = FileFormat::from_file("fixtures/executable/sample6.exe").unwrap();
+    assert_eq!(fmt, FileFormat::ZipSelfExtractingArchive);
+}
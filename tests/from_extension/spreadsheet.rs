@@ -21,6 +21,34 @@ fn test_microsoft_works_spreadsheet() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::MicrosoftWorksSpreadsheet)), "{:?} does not contain {}", fmt, FileFormat::MicrosoftWorksSpreadsheet);
 }
 
+#[test]
+#[cfg(feature = "from-extension")]
+fn test_office_open_xml_binary_spreadsheet() {
+    let fmt = FileFormat::from_extension("xlsb");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlBinarySpreadsheet)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlBinarySpreadsheet);
+}
+
+#[test]
+#[cfg(feature = "from-extension")]
+fn test_office_open_xml_macro_enabled_spreadsheet() {
+    let fmt = FileFormat::from_extension("xlsm");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledSpreadsheet)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledSpreadsheet);
+}
+
+#[test]
+#[cfg(feature = "from-extension")]
+fn test_office_open_xml_macro_enabled_spreadsheet_add_in() {
+    let fmt = FileFormat::from_extension("xlam");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetAddIn)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetAddIn);
+}
+
+#[test]
+#[cfg(feature = "from-extension")]
+fn test_office_open_xml_macro_enabled_spreadsheet_template() {
+    let fmt = FileFormat::from_extension("xltm");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetTemplate)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledSpreadsheetTemplate);
+}
+
 #[test]
 #[cfg(feature = "from-extension")]
 fn test_office_open_xml_spreadsheet() {
@@ -28,6 +56,13 @@ fn test_office_open_xml_spreadsheet() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlSpreadsheet)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlSpreadsheet);
 }
 
+#[test]
+#[cfg(feature = "from-extension")]
+fn test_office_open_xml_spreadsheet_template() {
+    let fmt = FileFormat::from_extension("xltx");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlSpreadsheetTemplate)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlSpreadsheetTemplate);
+}
+
 #[test]
 #[cfg(feature = "from-extension")]
 fn test_opendocument_spreadsheet() {
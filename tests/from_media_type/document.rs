@@ -77,6 +77,27 @@ fn test_office_open_xml_document() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlDocument)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlDocument);
 }
 
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_document_template() {
+    let fmt = FileFormat::from_media_type("application/vnd.openxmlformats-officedocument.wordprocessingml.template");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlDocumentTemplate)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlDocumentTemplate);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_macro_enabled_document() {
+    let fmt = FileFormat::from_media_type("application/vnd.ms-word.document.macroenabled.12");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledDocument)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledDocument);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_macro_enabled_document_template() {
+    let fmt = FileFormat::from_media_type("application/vnd.ms-word.template.macroenabled.12");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledDocumentTemplate)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledDocumentTemplate);
+}
+
 #[test]
 #[cfg(feature = "from-media-type")]
 fn test_opendocument_text() {
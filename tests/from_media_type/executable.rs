@@ -1,5 +1,12 @@
 use file_format::FileFormat;
 
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_cabinet_self_extracting_archive() {
+    let fmt = FileFormat::from_media_type("application/vnd.microsoft.portable-executable");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::CabinetSelfExtractingArchive)), "{:?} does not contain {}", fmt, FileFormat::CabinetSelfExtractingArchive);
+}
+
 #[test]
 #[cfg(feature = "from-media-type")]
 fn test_commodore64_program() {
@@ -105,6 +112,20 @@ fn test_portable_executable() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::PortableExecutable)), "{:?} does not contain {}", fmt, FileFormat::PortableExecutable);
 }
 
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_roshal_self_extracting_archive() {
+    let fmt = FileFormat::from_media_type("application/vnd.microsoft.portable-executable");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::RoshalSelfExtractingArchive)), "{:?} does not contain {}", fmt, FileFormat::RoshalSelfExtractingArchive);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_seven_zip_self_extracting_archive() {
+    let fmt = FileFormat::from_media_type("application/vnd.microsoft.portable-executable");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::SevenZipSelfExtractingArchive)), "{:?} does not contain {}", fmt, FileFormat::SevenZipSelfExtractingArchive);
+}
+
 #[test]
 #[cfg(feature = "from-media-type")]
 fn test_webassembly_binary() {
@@ -126,3 +147,9 @@ fn test_xbox_executable() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::XboxExecutable)), "{:?} does not contain {}", fmt, FileFormat::XboxExecutable);
 }
 
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_zip_self_extracting_archive() {
+    let fmt = FileFormat::from_media_type("application/vnd.microsoft.portable-executable");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::ZipSelfExtractingArchive)), "{:?} does not contain {}", fmt, FileFormat::ZipSelfExtractingArchive);
+}
@@ -21,6 +21,34 @@ fn test_microsoft_powerpoint_presentation() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::MicrosoftPowerpointPresentation)), "{:?} does not contain {}", fmt, FileFormat::MicrosoftPowerpointPresentation);
 }
 
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_macro_enabled_presentation() {
+    let fmt = FileFormat::from_media_type("application/vnd.ms-powerpoint.presentation.macroenabled.12");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledPresentation)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledPresentation);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_macro_enabled_presentation_add_in() {
+    let fmt = FileFormat::from_media_type("application/vnd.ms-powerpoint.addin.macroenabled.12");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledPresentationAddIn)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledPresentationAddIn);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_macro_enabled_presentation_template() {
+    let fmt = FileFormat::from_media_type("application/vnd.ms-powerpoint.template.macroenabled.12");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledPresentationTemplate)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledPresentationTemplate);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_macro_enabled_slideshow() {
+    let fmt = FileFormat::from_media_type("application/vnd.ms-powerpoint.slideshow.macroenabled.12");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlMacroEnabledSlideshow)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlMacroEnabledSlideshow);
+}
+
 #[test]
 #[cfg(feature = "from-media-type")]
 fn test_office_open_xml_presentation() {
@@ -28,6 +56,20 @@ fn test_office_open_xml_presentation() {
     assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlPresentation)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlPresentation);
 }
 
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_presentation_template() {
+    let fmt = FileFormat::from_media_type("application/vnd.openxmlformats-officedocument.presentationml.template");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlPresentationTemplate)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlPresentationTemplate);
+}
+
+#[test]
+#[cfg(feature = "from-media-type")]
+fn test_office_open_xml_slideshow() {
+    let fmt = FileFormat::from_media_type("application/vnd.openxmlformats-officedocument.presentationml.slideshow");
+    assert!(fmt.is_some_and(|types| types.contains(&FileFormat::OfficeOpenXmlSlideshow)), "{:?} does not contain {}", fmt, FileFormat::OfficeOpenXmlSlideshow);
+}
+
 #[test]
 #[cfg(feature = "from-media-type")]
 fn test_opendocument_presentation() {
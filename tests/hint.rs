@@ -0,0 +1,49 @@
+#![cfg(feature = "from-extension")]
+
+use file_format::FileFormat;
+use std::io::Cursor;
+
+#[test]
+fn test_hint_container() {
+    let detection =
+        FileFormat::detect_reader_with_hint(Cursor::new(b"PK\x05\x06"), "sample.docx").unwrap();
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    assert_eq!(detection.hint.as_deref(), Some("docx"));
+}
+
+#[test]
+fn test_hint_text() {
+    let fmt = FileFormat::from_reader_with_hint(Cursor::new(b"print(1)\n"), "sample.py").unwrap();
+    assert_eq!(fmt, FileFormat::PythonScript);
+}
+
+#[test]
+fn test_hint_contradicting_content() {
+    let detection =
+        FileFormat::detect_reader_with_hint(Cursor::new(b"\x1F\x8B"), "sample.docx").unwrap();
+    assert_eq!(detection.format, FileFormat::Gzip);
+    assert_eq!(detection.hint, None);
+}
+
+#[test]
+fn test_hint_unrelated_extension() {
+    let detection =
+        FileFormat::detect_reader_with_hint(Cursor::new(b"PK\x05\x06"), "sample.png").unwrap();
+    assert_ne!(detection.format, FileFormat::PortableNetworkGraphics);
+    assert_eq!(detection.hint, None);
+}
+
+#[test]
+fn test_hint_binary_without_signature() {
+    let detection =
+        FileFormat::detect_reader_with_hint(Cursor::new(b"\x00\xFF\x00\xFF"), "sample.zip")
+            .unwrap();
+    assert_eq!(detection.format, FileFormat::ArbitraryBinaryData);
+    assert_eq!(detection.hint, None);
+}
+
+#[test]
+fn test_hint_file() {
+    let fmt = FileFormat::from_file_with_hint("fixtures/document/sample.docx").unwrap();
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlDocument);
+}
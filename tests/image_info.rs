@@ -0,0 +1,239 @@
+#![cfg(feature = "image-info")]
+
+use file_format::{ColorModel, FileFormat, ImageInfo};
+
+fn image_info<B: AsRef<[u8]>>(bytes: B) -> ImageInfo {
+    FileFormat::image_info_from_bytes(bytes).unwrap()
+}
+
+fn isobmff_box(box_type: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut data = (8 + payload.len() as u32).to_be_bytes().to_vec();
+    data.extend_from_slice(box_type);
+    data.extend_from_slice(payload);
+    data
+}
+
+#[test]
+fn test_image_info_apng() {
+    let mut data = b"\x89PNG\r\n\x1A\n\0\0\0\x0DIHDR\0\0\0\x64\0\0\0\x32\x10\x06\0\0\0".to_vec();
+    data.extend_from_slice(b"\0\0\0\0\0\0\0\x08acTL\0\0\0\x02\0\0\0\0");
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::AnimatedPortableNetworkGraphics);
+    assert_eq!((info.width, info.height), (100, 50));
+    assert_eq!(info.bit_depth, Some(16));
+    assert_eq!(info.color_model, Some(ColorModel::Rgba));
+}
+
+#[test]
+fn test_image_info_avif() {
+    let mut ispe = vec![0; 4];
+    ispe.extend_from_slice(&1920u32.to_be_bytes());
+    ispe.extend_from_slice(&1080u32.to_be_bytes());
+    let mut ipco = isobmff_box(b"ispe", &ispe);
+    ipco.extend(isobmff_box(b"av1C", b"\x81\x00\x4C\x00"));
+    let iprp = isobmff_box(b"ipco", &ipco);
+    let mut meta = vec![0; 4];
+    meta.extend(isobmff_box(b"hdlr", &[0; 25]));
+    meta.extend(isobmff_box(b"iprp", &iprp));
+    let mut data = isobmff_box(b"ftyp", b"avif\0\0\0\0mif1avif");
+    data.extend(isobmff_box(b"meta", &meta));
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::Av1ImageFileFormat);
+    assert_eq!((info.width, info.height), (1920, 1080));
+    assert_eq!(info.bit_depth, Some(10));
+    assert_eq!(info.color_model, Some(ColorModel::YCbCr));
+}
+
+#[test]
+fn test_image_info_bmp() {
+    let mut data = b"BM\0\0\0\0\0\0\0\0\x36\x04\0\0\x28\0\0\0".to_vec();
+    data.extend_from_slice(&70i32.to_le_bytes());
+    data.extend_from_slice(&(-46i32).to_le_bytes());
+    data.extend_from_slice(b"\x01\0\x08\0");
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::WindowsBitmap);
+    assert_eq!((info.width, info.height), (70, 46));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Indexed));
+}
+
+#[test]
+fn test_image_info_bmp_alpha() {
+    let mut data = b"BM\0\0\0\0\0\0\0\0\x8A\0\0\0\x7C\0\0\0".to_vec();
+    data.extend_from_slice(&16i32.to_le_bytes());
+    data.extend_from_slice(&16i32.to_le_bytes());
+    data.extend_from_slice(b"\x01\0\x20\0\x03\0\0\0");
+    data.resize(54, 0);
+    data.extend_from_slice(b"\0\0\xFF\0\0\xFF\0\0\xFF\0\0\0\0\0\0\xFF");
+    let info = image_info(data);
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Rgba));
+}
+
+#[test]
+fn test_image_info_exr() {
+    let mut data = b"\x76\x2F\x31\x01\x02\0\0\0".to_vec();
+    data.extend_from_slice(b"channels\0chlist\0\x37\0\0\0");
+    for name in [&b"B"[..], b"G", b"R"] {
+        data.extend_from_slice(name);
+        data.extend_from_slice(b"\0\x01\0\0\0\0\0\0\0\x01\0\0\0\x01\0\0\0");
+    }
+    data.push(0);
+    data.extend_from_slice(b"dataWindow\0box2i\0\x10\0\0\0");
+    for value in [0i32, 0, 1919, 1079] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.push(0);
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::Openexr);
+    assert_eq!((info.width, info.height), (1920, 1080));
+    assert_eq!(info.bit_depth, Some(16));
+    assert_eq!(info.color_model, Some(ColorModel::Rgb));
+}
+
+#[test]
+fn test_image_info_gif() {
+    let info = image_info(b"GIF89a\x40\x01\xF0\x00\x91\0\0");
+    assert_eq!(info.format, FileFormat::GraphicsInterchangeFormat);
+    assert_eq!((info.width, info.height), (320, 240));
+    assert_eq!(info.bit_depth, Some(2));
+    assert_eq!(info.color_model, Some(ColorModel::Indexed));
+}
+
+#[test]
+fn test_image_info_jpeg() {
+    let mut data = b"\xFF\xD8\xFF\xE0\0\x10JFIF\0\x01\x01\0\0\x01\0\x01\0\0".to_vec();
+    data.extend_from_slice(b"\xFF\xFF\xC2\0\x11\x08\x01\xE0\x02\x80\x03");
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::JointPhotographicExpertsGroup);
+    assert_eq!((info.width, info.height), (640, 480));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::YCbCr));
+}
+
+#[test]
+fn test_image_info_jpeg_without_frame() {
+    let data = b"\xFF\xD8\xFF\xE0\0\x10JFIF\0\x01\x01\0\0\x01\0\x01\0\0\xFF\xD9";
+    assert_eq!(FileFormat::image_info_from_bytes(data), None);
+}
+
+#[test]
+fn test_image_info_jxl() {
+    let info = image_info(b"\xFF\x0A\x4F\x02");
+    assert_eq!(info.format, FileFormat::JpegXl);
+    assert_eq!((info.width, info.height), (64, 64));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Rgb));
+}
+
+#[test]
+fn test_image_info_jxl_container() {
+    let mut data = b"\0\0\0\x0CJXL \r\n\x87\n".to_vec();
+    data.extend(isobmff_box(b"ftyp", b"jxl \0\0\0\0jxl "));
+    data.extend(isobmff_box(b"jxlc", b"\xFF\x0A\xBA\x21\x45"));
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::JpegXl);
+    assert_eq!((info.width, info.height), (1920, 1080));
+    assert_eq!(info.bit_depth, Some(10));
+    assert_eq!(info.color_model, None);
+}
+
+#[test]
+fn test_image_info_png() {
+    let info = image_info(b"\x89PNG\r\n\x1A\n\0\0\0\x0DIHDR\0\0\x02\x80\0\0\x01\xE0\x08\x03\0\0\0");
+    assert_eq!(info.format, FileFormat::PortableNetworkGraphics);
+    assert_eq!((info.width, info.height), (640, 480));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Indexed));
+}
+
+#[test]
+fn test_image_info_qoi() {
+    let info = image_info(b"qoif\0\0\x01\0\0\0\0\x80\x03\0");
+    assert_eq!(info.format, FileFormat::QuiteOkImage);
+    assert_eq!((info.width, info.height), (256, 128));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Rgb));
+}
+
+#[test]
+fn test_image_info_tiff() {
+    let mut data = b"II\x2A\0\x08\0\0\0\x05\0".to_vec();
+    for (tag, field_type, count, value) in [
+        (256u16, 3u16, 1u32, 640u32),
+        (257, 4, 1, 480),
+        (258, 3, 3, 74),
+        (262, 3, 1, 2),
+        (277, 3, 1, 3),
+    ] {
+        data.extend_from_slice(&tag.to_le_bytes());
+        data.extend_from_slice(&field_type.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(b"\0\0\0\0\x08\0\x08\0\x08\0");
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::TagImageFileFormat);
+    assert_eq!((info.width, info.height), (640, 480));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Rgb));
+}
+
+#[test]
+fn test_image_info_tiff_big() {
+    let mut data = b"MM\0\x2B\0\x08\0\0\0\0\0\0\0\0\0\x10\0\0\0\0\0\0\0\x03".to_vec();
+    for (tag, field_type, value) in [(256u16, 16u16, 100_000u64), (257, 4, 50_000), (262, 3, 1)] {
+        data.extend_from_slice(&tag.to_be_bytes());
+        data.extend_from_slice(&field_type.to_be_bytes());
+        data.extend_from_slice(&1u64.to_be_bytes());
+        let value = match field_type {
+            3 => (value << 48).to_be_bytes(),
+            4 => (value << 32).to_be_bytes(),
+            _ => value.to_be_bytes(),
+        };
+        data.extend_from_slice(&value);
+    }
+    let info = image_info(data);
+    assert_eq!((info.width, info.height), (100_000, 50_000));
+    assert_eq!(info.bit_depth, Some(1));
+    assert_eq!(info.color_model, Some(ColorModel::Grayscale));
+}
+
+#[test]
+fn test_image_info_webp_lossless() {
+    let bits = 399u32 | 299 << 14 | 1 << 28;
+    let mut data = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2F".to_vec();
+    data.extend_from_slice(&bits.to_le_bytes());
+    let info = image_info(data);
+    assert_eq!(info.format, FileFormat::Webp);
+    assert_eq!((info.width, info.height), (400, 300));
+    assert_eq!(info.bit_depth, Some(8));
+    assert_eq!(info.color_model, Some(ColorModel::Rgba));
+}
+
+#[test]
+fn test_image_info_webp_lossy() {
+    let info = image_info(b"RIFF\0\0\0\0WEBPVP8 \0\0\0\0\0\0\0\x9D\x01\x2A\x20\x03\x58\x02");
+    assert_eq!((info.width, info.height), (800, 600));
+    assert_eq!(info.color_model, Some(ColorModel::Rgb));
+}
+
+#[test]
+fn test_image_info_webp_extended() {
+    let info = image_info(b"RIFF\0\0\0\0WEBPVP8X\x0A\0\0\0\x10\0\0\0\xFF\x0F\0\xFF\x0B\0");
+    assert_eq!((info.width, info.height), (4096, 3072));
+    assert_eq!(info.color_model, Some(ColorModel::Rgba));
+}
+
+#[test]
+fn test_image_info_not_image() {
+    assert_eq!(FileFormat::image_info_from_bytes(b"\x1F\x8B"), None);
+    let info = FileFormat::image_info_from_file("fixtures/audio/sample.mp3").unwrap();
+    assert_eq!(info, None);
+}
+
+#[test]
+fn test_image_info_truncated() {
+    assert!(FileFormat::image_info_from_file("fixtures/image/sample.png").is_err());
+    assert_eq!(FileFormat::image_info_from_bytes(b"qoif\0\0\x01"), None);
+}
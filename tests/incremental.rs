@@ -0,0 +1,136 @@
+mod common;
+
+#[cfg(feature = "reader-zip")]
+use common::ZipEntry;
+use file_format::{FileFormat, IncrementalDetector, Progress};
+use std::fs;
+
+/// Reads a fixture, padded with zeros so that every signature can be ruled out.
+fn padded(path: &str) -> Vec<u8> {
+    let mut data = fs::read(path).unwrap();
+    data.resize(data.len() + 40_000, 0);
+    data
+}
+
+#[test]
+fn test_incremental_chunks() {
+    let data = padded("fixtures/image/sample.png");
+    let mut detector = IncrementalDetector::new();
+    let position = data
+        .chunks(1024)
+        .position(|chunk| detector.feed(chunk) != Progress::NeedMoreData)
+        .unwrap();
+    assert_eq!(position, 31);
+    assert_eq!(
+        detector.feed(b""),
+        Progress::Done(FileFormat::PortableNetworkGraphics)
+    );
+}
+
+#[test]
+fn test_incremental_matches_from_file() {
+    for kind in fs::read_dir("fixtures").unwrap() {
+        for entry in fs::read_dir(kind.unwrap().path()).unwrap() {
+            let path = entry.unwrap().path();
+            let data = fs::read(&path).unwrap();
+            let mut detector = IncrementalDetector::new();
+            let fmt = data
+                .chunks(512)
+                .find_map(|chunk| match detector.feed(chunk) {
+                    Progress::Done(fmt) => Some(fmt),
+                    _ => None,
+                })
+                .unwrap_or_else(|| detector.finish());
+            assert_eq!(
+                fmt,
+                FileFormat::from_file(&path).unwrap(),
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_incremental_finish() {
+    let mut detector = IncrementalDetector::new();
+    assert_eq!(detector.feed(b""), Progress::NeedMoreData);
+    assert_eq!(detector.finish(), FileFormat::Empty);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_incremental_reader() {
+    let data = padded("fixtures/document/sample.docx");
+    let mut detector = IncrementalDetector::new();
+    let progress = data
+        .chunks(1024)
+        .map(|chunk| detector.feed(chunk))
+        .find(|progress| *progress != Progress::NeedMoreData);
+    assert_eq!(
+        progress,
+        Some(Progress::Done(FileFormat::OfficeOpenXmlDocument))
+    );
+}
+
+#[cfg(feature = "reader-sqlite3")]
+#[test]
+fn test_incremental_reader_confirms_signature() {
+    let mut data = fs::read("fixtures/database/sample.sqlite").unwrap();
+    data.resize(70_000, 0);
+    let mut detector = IncrementalDetector::new();
+    let position = data
+        .chunks(1024)
+        .position(|chunk| detector.feed(chunk) != Progress::NeedMoreData)
+        .unwrap();
+    assert_eq!(position, 63);
+    assert_eq!(detector.feed(b""), Progress::Done(FileFormat::Sqlite3));
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_incremental_reader_within_limit() {
+    let data = padded("fixtures/document/sample.pdf");
+    let detector = file_format::Detector::builder()
+        .pdf_read_limit(4096)
+        .build();
+    let mut detector = IncrementalDetector::with_detector(detector);
+    let position = data
+        .chunks(1024)
+        .position(|chunk| detector.feed(chunk) != Progress::NeedMoreData)
+        .unwrap();
+    assert_eq!(position, 36);
+    assert_eq!(
+        detector.feed(b""),
+        Progress::Done(FileFormat::PortableDocumentFormat)
+    );
+}
+
+#[cfg(feature = "reader-pdf")]
+#[test]
+fn test_incremental_reader_at_prefix_size() {
+    let data = padded("fixtures/document/sample.pdf");
+    let detector = file_format::Detector::builder()
+        .stream_prefix_size(36_870)
+        .build();
+    let mut detector = IncrementalDetector::with_detector(detector);
+    assert_eq!(
+        detector.feed(&data),
+        Progress::Done(FileFormat::PortableDocumentFormat)
+    );
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_incremental_ambiguous() {
+    let payload = vec![0; 65_536];
+    let data = common::zip(&[ZipEntry::new("payload.bin", 0, &payload)]);
+    let detector = file_format::Detector::builder()
+        .stream_prefix_size(36_870)
+        .build();
+    let mut detector = IncrementalDetector::with_detector(detector);
+    match detector.feed(&data) {
+        Progress::Ambiguous(candidates) => assert_eq!(candidates[0].0, FileFormat::Zip),
+        progress => panic!("unexpected progress: {progress:?}"),
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "decompress")]
+
+use file_format::{Detector, FileFormat};
+
+#[test]
+fn test_layers_bzip2() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.bz2").unwrap();
+    assert_eq!(layers, [FileFormat::Bzip2, FileFormat::TapeArchive]);
+}
+
+#[test]
+fn test_layers_gzip() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.gz").unwrap();
+    assert_eq!(layers, [FileFormat::Gzip, FileFormat::TapeArchive]);
+}
+
+#[test]
+fn test_layers_lz4() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.lz4").unwrap();
+    assert_eq!(layers, [FileFormat::Lz4, FileFormat::TapeArchive]);
+}
+
+#[test]
+fn test_layers_xz() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.xz").unwrap();
+    assert_eq!(layers, [FileFormat::Xz, FileFormat::TapeArchive]);
+}
+
+#[test]
+fn test_layers_zstandard() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.zst").unwrap();
+    assert_eq!(layers, [FileFormat::Zstandard, FileFormat::TapeArchive]);
+}
+
+#[test]
+fn test_layers_svgz() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.svgz").unwrap();
+    assert_eq!(
+        layers,
+        [FileFormat::Gzip, FileFormat::ScalableVectorGraphics]
+    );
+}
+
+#[test]
+fn test_layers_nested() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/sample.tar.gz.xz").unwrap();
+    assert_eq!(
+        layers,
+        [FileFormat::Xz, FileFormat::Gzip, FileFormat::TapeArchive]
+    );
+}
+
+#[test]
+fn test_layers_layer_limit() {
+    let detector = Detector::builder().layer_limit(1).build();
+    let layers = detector
+        .layers_from_file("fixtures/layered/sample.tar.gz.xz")
+        .unwrap();
+    assert_eq!(layers, [FileFormat::Xz, FileFormat::Gzip]);
+    let layers = detector
+        .layers_from_file("fixtures/layered/sample.tar.gz")
+        .unwrap();
+    assert_eq!(layers, [FileFormat::Gzip, FileFormat::TapeArchive]);
+}
+
+#[test]
+fn test_layers_layer_limit_zero() {
+    let detector = Detector::builder().layer_limit(0).build();
+    let layers = detector
+        .layers_from_file("fixtures/layered/sample.tar.gz.xz")
+        .unwrap();
+    assert_eq!(layers, [FileFormat::Xz]);
+    let layers = detector.layers_from_bytes(b"\x1F\x8B\x08\x00\xFF\xFF\xFF\xFF");
+    assert_eq!(layers, [FileFormat::Gzip]);
+}
+
+#[test]
+fn test_layers_prefix_size() {
+    let detector = Detector::builder().layer_prefix_size(16).build();
+    let layers = detector
+        .layers_from_file("fixtures/layered/sample.tar.gz")
+        .unwrap();
+    assert_eq!(layers[0], FileFormat::Gzip);
+    assert_ne!(layers.last(), Some(&FileFormat::TapeArchive));
+}
+
+#[test]
+fn test_layers_corrupted() {
+    let layers = FileFormat::layers_from_bytes(b"\x1F\x8B\x08\x00\xFF\xFF\xFF\xFF");
+    assert_eq!(layers, [FileFormat::Gzip]);
+}
+
+#[test]
+fn test_layers_bomb() {
+    let layers = FileFormat::layers_from_file("fixtures/layered/zeros.gz").unwrap();
+    assert_eq!(layers, [FileFormat::Gzip, FileFormat::ArbitraryBinaryData]);
+}
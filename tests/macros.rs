@@ -0,0 +1,161 @@
+#![cfg(any(feature = "reader-cfb", feature = "reader-zip"))]
+
+mod common;
+
+#[cfg(feature = "reader-cfb")]
+use common::cfb;
+#[cfg(feature = "reader-zip")]
+use common::{deflate_stored_block, zip, zip_local_file_header, ZipEntry};
+use file_format::FileFormat;
+
+#[cfg(feature = "reader-zip")]
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="bin" ContentType="application/vnd.ms-office.vbaProject"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.ms-word.document.macroEnabled.main+xml"/></Types>"#;
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_macros_cfb_excel() {
+    let clsid = b"\x20\x08\x02\0\0\0\0\0\xC0\0\0\0\0\0\0\x46";
+    let detection = FileFormat::detect_bytes(cfb(clsid, &["Workbook", "_VBA_PROJECT_CUR"]));
+    assert_eq!(detection.format, FileFormat::MicrosoftExcelSpreadsheet);
+    assert!(detection.has_macros);
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_macros_cfb_word() {
+    let clsid = b"\x06\x09\x02\0\0\0\0\0\xC0\0\0\0\0\0\0\x46";
+    let detection = FileFormat::detect_bytes(cfb(clsid, &["WordDocument", "1Table", "Macros"]));
+    assert_eq!(detection.format, FileFormat::MicrosoftWordDocument);
+    assert!(detection.has_macros);
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_macros_cfb_without_vba_project() {
+    let clsid = b"\x06\x09\x02\0\0\0\0\0\xC0\0\0\0\0\0\0\x46";
+    let detection = FileFormat::detect_bytes(cfb(clsid, &["WordDocument", "1Table"]));
+    assert_eq!(detection.format, FileFormat::MicrosoftWordDocument);
+    assert!(!detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_docm() {
+    let content_types = deflate_stored_block(CONTENT_TYPES.as_bytes());
+    let detection = FileFormat::detect_bytes(zip(&[
+        ZipEntry::new("[Content_Types].xml", 8, &content_types),
+        ZipEntry::new("word/document.xml", 0, b""),
+        ZipEntry::new("word/vbaProject.bin", 0, b""),
+    ]));
+    assert_eq!(
+        detection.format,
+        FileFormat::OfficeOpenXmlMacroEnabledDocument
+    );
+    assert_eq!(
+        detection.marker.as_deref(),
+        Some("application/vnd.ms-word.document.macroEnabled.main+xml")
+    );
+    assert!(detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_docx_with_vba_project() {
+    let content_types = CONTENT_TYPES.replace(
+        "application/vnd.ms-word.document.macroEnabled.main+xml",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml",
+    );
+    let content_types = content_types.replace(
+        r#"<Default Extension="bin" ContentType="application/vnd.ms-office.vbaProject"/>"#,
+        "",
+    );
+    let entries = [
+        ZipEntry::new("[Content_Types].xml", 0, content_types.as_bytes()),
+        ZipEntry::new("word/document.xml", 0, b""),
+        ZipEntry::new("word/vbaProject.bin", 0, b""),
+    ];
+    let detection = FileFormat::detect_bytes(zip(&entries));
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    assert!(detection.has_macros);
+
+    // Without the central directory, the local file headers are browsed instead.
+    let data: Vec<u8> = entries.iter().flat_map(zip_local_file_header).collect();
+    let (detection, _) = FileFormat::detect_stream(&data[..]).unwrap();
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    assert!(detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_docm_truncated() {
+    let content_types = deflate_stored_block(CONTENT_TYPES.as_bytes());
+    let data = zip_local_file_header(&ZipEntry::new("[Content_Types].xml", 8, &content_types));
+    let (detection, _) = FileFormat::detect_stream(&data[..]).unwrap();
+    assert_eq!(
+        detection.format,
+        FileFormat::OfficeOpenXmlMacroEnabledDocument
+    );
+    assert!(detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_pptm_without_content_types() {
+    let detection = FileFormat::detect_bytes(zip(&[
+        ZipEntry::new("ppt/presentation.xml", 0, b""),
+        ZipEntry::new("ppt/slides/slide1.xml", 0, b""),
+        ZipEntry::new("ppt/vbaProject.bin", 0, b""),
+    ]));
+    assert_eq!(
+        detection.format,
+        FileFormat::OfficeOpenXmlMacroEnabledPresentation
+    );
+    assert_eq!(detection.marker.as_deref(), Some("ppt/presentation.xml"));
+    assert!(detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_xlsb() {
+    let content_types = CONTENT_TYPES.replace(
+        "/word/document.xml\" ContentType=\"application/vnd.ms-word.document.macroEnabled.main+xml",
+        "/xl/workbook.bin\" ContentType=\"application/vnd.ms-excel.sheet.binary.macroEnabled.main",
+    );
+    let detection = FileFormat::detect_bytes(zip(&[
+        ZipEntry::new("[Content_Types].xml", 0, content_types.as_bytes()),
+        ZipEntry::new("xl/workbook.bin", 0, b""),
+    ]));
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlBinarySpreadsheet);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_xlsb_without_content_types() {
+    let detection = FileFormat::detect_bytes(zip(&[
+        ZipEntry::new("xl/styles.bin", 0, b""),
+        ZipEntry::new("xl/workbook.bin", 0, b""),
+    ]));
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlBinarySpreadsheet);
+    assert!(!detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_xlsx() {
+    let content_types = r#"<Types><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/></Types>"#;
+    let detection = FileFormat::detect_bytes(zip(&[
+        ZipEntry::new("[Content_Types].xml", 0, content_types.as_bytes()),
+        ZipEntry::new("xl/workbook.xml", 0, b""),
+    ]));
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlSpreadsheet);
+    assert!(!detection.has_macros);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_macros_docx_fixture() {
+    let detection = FileFormat::detect_file("fixtures/document/sample.docx").unwrap();
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    assert!(!detection.has_macros);
+}
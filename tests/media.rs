@@ -0,0 +1,176 @@
+#![cfg(any(
+    feature = "reader-asf",
+    feature = "reader-ebml",
+    feature = "reader-mp4",
+    feature = "reader-rm"
+))]
+
+use file_format::{FileFormat, TrackKind};
+use std::time::Duration;
+
+#[cfg(feature = "reader-mp4")]
+fn mp4_box(box_type: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut data = (8 + payload.len() as u32).to_be_bytes().to_vec();
+    data.extend_from_slice(box_type);
+    data.extend_from_slice(payload);
+    data
+}
+
+#[cfg(feature = "reader-mp4")]
+fn mp4_track(handler_type: &[u8], sample_entry_type: &[u8]) -> Vec<u8> {
+    let mut hdlr = vec![0; 8];
+    hdlr.extend_from_slice(handler_type);
+    hdlr.extend_from_slice(&[0; 13]);
+    let mut stsd = b"\0\0\0\0\0\0\0\x01\0\0\0\x08".to_vec();
+    stsd.extend_from_slice(sample_entry_type);
+    let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", &stsd));
+    let mut minf = mp4_box(b"hdlr", b"\0\0\0\0dhlralis\0\0\0\0\0\0\0\0\0\0\0\0\0");
+    minf.extend(stbl);
+    let mut mdia = mp4_box(b"hdlr", &hdlr);
+    mdia.extend(mp4_box(b"minf", &minf));
+    mp4_box(b"trak", &mp4_box(b"mdia", &mdia))
+}
+
+#[cfg(feature = "reader-asf")]
+#[test]
+fn test_media_info_asf() {
+    let media = FileFormat::media_info_from_file("fixtures/video/sample.wmv")
+        .unwrap()
+        .unwrap();
+    assert_eq!(media.duration, None);
+    assert_eq!(media.tracks.len(), 1);
+    assert_eq!(media.tracks[0].kind, TrackKind::Video);
+    assert_eq!(
+        media.tracks[0].codec.as_deref(),
+        Some("bc19efc0-5b4d-11cf-a8fd-00805f5c442b")
+    );
+}
+
+#[cfg(feature = "reader-asf")]
+#[test]
+fn test_media_info_asf_duration() {
+    let mut data = b"\x30\x26\xB2\x75\x8E\x66\xCF\x11\xA6\xD9\x00\xAA\x00\x62\xCE\x6C".to_vec();
+    data.extend_from_slice(&[0; 8]);
+    data.extend_from_slice(b"\x01\0\0\0\x01\x02");
+    data.extend_from_slice(b"\xA1\xDC\xAB\x8C\x47\xA9\xCF\x11\x8E\xE4\x00\xC0\x0C\x20\x53\x65");
+    data.extend_from_slice(&104u64.to_le_bytes());
+    data.extend_from_slice(&[0; 40]);
+    data.extend_from_slice(&123_450_000u64.to_le_bytes());
+    data.extend_from_slice(&[0; 8]);
+    data.extend_from_slice(&3000u64.to_le_bytes());
+    data.extend_from_slice(&[0; 12]);
+    let media = FileFormat::media_info_from_bytes(data).unwrap();
+    assert_eq!(media.duration, Some(Duration::from_millis(9345)));
+    assert!(media.tracks.is_empty());
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_media_info_matroska_audio() {
+    let media = FileFormat::media_info_from_file("fixtures/audio/sample.mka")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        media.duration.map(|duration| duration.as_millis()),
+        Some(86_784)
+    );
+    assert_eq!(media.track_count(TrackKind::Audio), 1);
+    assert_eq!(media.tracks[0].codec.as_deref(), Some("A_MPEG/L3"));
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_media_info_matroska_video() {
+    let media = FileFormat::media_info_from_file("fixtures/video/sample.mkv")
+        .unwrap()
+        .unwrap();
+    assert_eq!(media.duration, Some(Duration::from_millis(11_982)));
+    assert_eq!(media.track_count(TrackKind::Video), 1);
+    assert_eq!(media.tracks[0].codec.as_deref(), Some("V_MS/VFW/FOURCC"));
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_media_info_matroska_3d_video() {
+    let detection = FileFormat::detect_file("fixtures/video/sample.mk3d").unwrap();
+    assert_eq!(detection.format, FileFormat::Matroska3dVideo);
+    let media = detection.media.unwrap();
+    assert_eq!(media.duration, Some(Duration::from_millis(24_172)));
+    assert_eq!(media.track_count(TrackKind::Video), 1);
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_media_info_webm() {
+    let detection = FileFormat::detect_file("fixtures/video/sample.webm").unwrap();
+    assert_eq!(detection.format, FileFormat::Webm);
+    assert_eq!(detection.media, Some(file_format::MediaInfo::default()));
+}
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_media_info_mp4() {
+    let mut mvhd = vec![0; 12];
+    mvhd.extend_from_slice(&1000u32.to_be_bytes());
+    mvhd.extend_from_slice(&61_500u32.to_be_bytes());
+    mvhd.extend_from_slice(&[0; 80]);
+    let mut moov = mp4_box(b"mvhd", &mvhd);
+    moov.extend(mp4_track(b"vide", b"avc1"));
+    moov.extend(mp4_track(b"soun", b"mp4a"));
+    moov.extend(mp4_track(b"soun", b"ac-3"));
+    let mut data = mp4_box(b"ftyp", b"isom\0\0\0\0isom");
+    data.extend(mp4_box(b"moov", &moov));
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::Mpeg4Part14Video);
+    let media = detection.media.unwrap();
+    assert_eq!(media.duration, Some(Duration::from_millis(61_500)));
+    assert_eq!(media.tracks.len(), 3);
+    assert_eq!(media.track_count(TrackKind::Video), 1);
+    assert_eq!(media.track_count(TrackKind::Audio), 2);
+    let codecs: Vec<_> = media
+        .tracks
+        .iter()
+        .map(|track| track.codec.as_deref())
+        .collect();
+    assert_eq!(codecs, vec![Some("avc1"), Some("mp4a"), Some("ac-3")]);
+}
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_media_info_mp4_without_track() {
+    let media = FileFormat::media_info_from_file("fixtures/video/sample.mp4")
+        .unwrap()
+        .unwrap();
+    assert_eq!(media.duration, None);
+    assert_eq!(media.track_count(TrackKind::Video), 1);
+    assert_eq!(media.tracks[0].codec, None);
+}
+
+#[cfg(feature = "reader-rm")]
+#[test]
+fn test_media_info_rm() {
+    let media = FileFormat::media_info_from_file("fixtures/audio/sample2.ra")
+        .unwrap()
+        .unwrap();
+    assert_eq!(media.track_count(TrackKind::Audio), 1);
+    assert_eq!(
+        media.tracks[0].codec.as_deref(),
+        Some("audio/x-pn-realaudio")
+    );
+}
+
+#[cfg(feature = "reader-rm")]
+#[test]
+fn test_media_info_rm_duration() {
+    let mut data = b".RMF\0\0\0\x12\0\0\0\0\0\0\0\0\0\x02PROP\0\0\0\x32\0\0".to_vec();
+    data.extend_from_slice(&[0; 20]);
+    data.extend_from_slice(&95_250u32.to_be_bytes());
+    data.extend_from_slice(&[0; 16]);
+    let media = FileFormat::media_info_from_bytes(data).unwrap();
+    assert_eq!(media.duration, Some(Duration::from_millis(95_250)));
+}
+
+#[test]
+fn test_media_info_not_media() {
+    assert_eq!(FileFormat::media_info_from_bytes(b"\x1F\x8B"), None);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "mmap")]
+
+use file_format::FileFormat;
+use std::fs;
+
+#[test]
+fn test_mmap_matches_from_file() {
+    for kind in fs::read_dir("fixtures").unwrap() {
+        for entry in fs::read_dir(kind.unwrap().path()).unwrap() {
+            let path = entry.unwrap().path();
+            assert_eq!(
+                unsafe { FileFormat::detect_file_mmap(&path) }.unwrap(),
+                FileFormat::detect_file(&path).unwrap(),
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_mmap_empty() {
+    let path = std::env::temp_dir().join("file-format-mmap-empty");
+    fs::write(&path, []).unwrap();
+    let fmt = unsafe { FileFormat::from_file_mmap(&path) };
+    fs::remove_file(&path).unwrap();
+    assert_eq!(fmt.unwrap(), FileFormat::Empty);
+}
+
+#[test]
+fn test_mmap_missing() {
+    assert!(unsafe { FileFormat::from_file_mmap("fixtures/missing") }.is_err());
+}
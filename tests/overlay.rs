@@ -0,0 +1,106 @@
+#![cfg(any(feature = "reader-elf", feature = "reader-exe"))]
+
+mod common;
+
+#[cfg(feature = "reader-zip")]
+use common::ZipEntry;
+use file_format::FileFormat;
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_overlay_none() {
+    let detection = FileFormat::detect_file("fixtures/executable/sample4.exe").unwrap();
+    assert_eq!(detection.format, FileFormat::PortableExecutable);
+    assert_eq!(detection.overlay, None);
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_overlay_after_sections() {
+    let detection = FileFormat::detect_file("fixtures/executable/sample5.exe").unwrap();
+    assert_eq!(detection.overlay, Some((0x200, FileFormat::SevenZip)));
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_overlay_dynamic_link_library() {
+    let mut data = std::fs::read("fixtures/executable/sample.dll").unwrap();
+    data.truncate(88);
+    data.extend(std::fs::read("fixtures/archive/sample.7z").unwrap());
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::DynamicLinkLibrary);
+    assert_eq!(detection.overlay, Some((88, FileFormat::SevenZip)));
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_overlay_unknown() {
+    let mut data = std::fs::read("fixtures/executable/sample4.exe").unwrap();
+    data.extend([0xAB; 16]);
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::PortableExecutable);
+    assert_eq!(
+        detection.overlay,
+        Some((88, FileFormat::ArbitraryBinaryData))
+    );
+}
+
+#[cfg(all(feature = "reader-exe", feature = "reader-zip"))]
+#[test]
+fn test_overlay_beyond_probe() {
+    let mut data = std::fs::read("fixtures/executable/sample4.exe").unwrap();
+    let payload = vec![0; 102_400];
+    data.extend(common::zip(&[ZipEntry::new("payload.bin", 0, &payload)]));
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::ZipSelfExtractingArchive);
+    assert_eq!(detection.overlay, Some((88, FileFormat::Zip)));
+}
+
+#[cfg(feature = "reader-elf")]
+#[test]
+fn test_overlay_executable_and_linkable_format() {
+    let detection = FileFormat::detect_file("fixtures/executable/sample1.elf").unwrap();
+    assert_eq!(detection.format, FileFormat::ExecutableAndLinkableFormat);
+    assert_eq!(detection.overlay, Some((64, FileFormat::Zip)));
+}
+
+#[cfg(feature = "reader-elf")]
+#[test]
+fn test_overlay_nested_executable_and_linkable_format() {
+    let data = std::fs::read("fixtures/executable/sample1.elf").unwrap();
+    let mut nested = data[..64].repeat(1024);
+    nested.extend_from_slice(&data[64..]);
+    let detection = FileFormat::detect_bytes(nested);
+    assert_eq!(detection.format, FileFormat::ExecutableAndLinkableFormat);
+    assert_eq!(
+        detection.overlay,
+        Some((64, FileFormat::ExecutableAndLinkableFormat))
+    );
+}
+
+#[cfg(feature = "reader-elf")]
+#[test]
+fn test_overlay_truncated_executable_and_linkable_format() {
+    let detection = FileFormat::detect_file("fixtures/executable/sample.elf").unwrap();
+    assert_eq!(detection.format, FileFormat::ExecutableAndLinkableFormat);
+    assert_eq!(detection.overlay, None);
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_overlay_signed() {
+    let detection = FileFormat::detect_file("fixtures/executable/sample9.exe").unwrap();
+    assert_eq!(detection.format, FileFormat::SevenZipSelfExtractingArchive);
+    assert_eq!(detection.overlay, Some((0x400, FileFormat::SevenZip)));
+}
+
+#[cfg(feature = "reader-exe")]
+#[test]
+fn test_overlay_signed_without_payload() {
+    let mut data = std::fs::read("fixtures/executable/sample9.exe").unwrap();
+    data.drain(0x400..0x408);
+    data[0xD8..0xDC].copy_from_slice(&0x400u32.to_le_bytes());
+    let detection = FileFormat::detect_bytes(data);
+    assert_eq!(detection.format, FileFormat::PortableExecutable);
+    assert_eq!(detection.overlay, None);
+}
@@ -0,0 +1,66 @@
+use file_format::FileFormat;
+use std::fs;
+
+fn concat(paths: &[&str]) -> Vec<u8> {
+    paths
+        .iter()
+        .flat_map(|path| fs::read(path).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_all_matches_single() {
+    let matches = FileFormat::all_matches_from_file("fixtures/image/sample.png").unwrap();
+    assert_eq!(matches, [FileFormat::PortableNetworkGraphics]);
+}
+
+#[test]
+fn test_all_matches_gifar() {
+    let data = concat(&["fixtures/image/sample.gif", "fixtures/package/sample.jar"]);
+    let matches = FileFormat::all_matches_from_bytes(data);
+    assert_eq!(matches[0], FileFormat::GraphicsInterchangeFormat);
+    #[cfg(feature = "reader-zip")]
+    assert!(matches.contains(&FileFormat::JavaArchive));
+    #[cfg(not(feature = "reader-zip"))]
+    assert!(matches.contains(&FileFormat::Zip));
+}
+
+#[test]
+fn test_all_matches_executable_with_zip() {
+    let data = concat(&[
+        "fixtures/executable/sample1.exe",
+        "fixtures/archive/sample.zip",
+    ]);
+    let matches = FileFormat::all_matches_from_bytes(data);
+    assert_eq!(
+        matches[0],
+        FileFormat::from_file("fixtures/executable/sample1.exe").unwrap()
+    );
+    assert!(matches.contains(&FileFormat::Zip));
+}
+
+#[test]
+fn test_all_matches_pdf_with_zip() {
+    let mut data = b"%PDF-1.7\n1 0 obj\n<<>>\nendobj\ntrailer\n<<>>\n%%EOF\n".to_vec();
+    data.extend(fs::read("fixtures/archive/sample.zip").unwrap());
+    let matches = FileFormat::all_matches_from_bytes(data);
+    assert_eq!(matches[0], FileFormat::PortableDocumentFormat);
+    assert!(matches.contains(&FileFormat::Zip));
+}
+
+#[test]
+fn test_all_matches_zip_with_pdf_trailer() {
+    let mut data = fs::read("fixtures/archive/sample.zip").unwrap();
+    data.extend(b"%PDF-1.7\n%%EOF\n");
+    let matches = FileFormat::all_matches_from_bytes(data);
+    assert_eq!(
+        matches,
+        [FileFormat::Zip, FileFormat::PortableDocumentFormat]
+    );
+}
+
+#[test]
+fn test_all_matches_pdf_without_trailer() {
+    let matches = FileFormat::all_matches_from_bytes(b"\x1F\x8B%PDF-1.7\n");
+    assert_eq!(matches, [FileFormat::Gzip]);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "scan")]
+
+use file_format::{FileFormat, Scanner};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+fn temp_tree(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("sub/deeper")).unwrap();
+    fs::create_dir_all(root.join(".hidden")).unwrap();
+    fs::copy("fixtures/image/sample.png", root.join("a.png")).unwrap();
+    fs::copy("fixtures/image/sample.png", root.join(".b.png")).unwrap();
+    fs::copy("fixtures/archive/sample.zip", root.join("sub/c.zip")).unwrap();
+    fs::copy("fixtures/archive/sample.zip", root.join("sub/deeper/d.zip")).unwrap();
+    fs::copy("fixtures/archive/sample.zip", root.join(".hidden/e.zip")).unwrap();
+    root
+}
+
+fn scan(scanner: Scanner, root: &PathBuf) -> HashMap<PathBuf, FileFormat> {
+    scanner
+        .scan(root)
+        .map(|(path, result)| {
+            (
+                path.strip_prefix(root).unwrap().to_path_buf(),
+                result.unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_scan_matches_from_file() {
+    for (path, result) in Scanner::builder().threads(3).build().scan("fixtures/image") {
+        assert_eq!(result.unwrap(), FileFormat::from_file(&path).unwrap());
+    }
+}
+
+#[test]
+fn test_scan_all() {
+    let root = temp_tree("file-format-scan-all");
+    let results = scan(Scanner::default(), &root);
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!(results.len(), 5);
+    assert_eq!(
+        results[&PathBuf::from("a.png")],
+        FileFormat::PortableNetworkGraphics
+    );
+    assert_eq!(results[&PathBuf::from("sub/deeper/d.zip")], FileFormat::Zip);
+}
+
+#[test]
+fn test_scan_skip_hidden() {
+    let root = temp_tree("file-format-scan-hidden");
+    let results = scan(Scanner::builder().skip_hidden(true).build(), &root);
+    fs::remove_dir_all(&root).unwrap();
+    let mut paths: Vec<_> = results.into_keys().collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            PathBuf::from("a.png"),
+            PathBuf::from("sub/c.zip"),
+            PathBuf::from("sub/deeper/d.zip")
+        ]
+    );
+}
+
+#[test]
+fn test_scan_max_depth() {
+    let root = temp_tree("file-format-scan-depth");
+    let results = scan(Scanner::builder().max_depth(2).build(), &root);
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!(results.len(), 4);
+    assert!(!results.contains_key(&PathBuf::from("sub/deeper/d.zip")));
+}
+
+#[test]
+fn test_scan_read_limit() {
+    let root = temp_tree("file-format-scan-limit");
+    let results = scan(Scanner::builder().read_limit(0).build(), &root);
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!(results[&PathBuf::from("a.png")], FileFormat::Empty);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_symlinks() {
+    let root = temp_tree("file-format-scan-symlinks");
+    std::os::unix::fs::symlink(&root, root.join("sub/loop")).unwrap();
+    std::os::unix::fs::symlink(root.join("a.png"), root.join("link.png")).unwrap();
+    let skipped = scan(Scanner::default(), &root);
+    let followed = scan(Scanner::builder().follow_symlinks(true).build(), &root);
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!(skipped.len(), 5);
+    assert_eq!(followed.len(), 6);
+    assert_eq!(
+        followed[&PathBuf::from("link.png")],
+        FileFormat::PortableNetworkGraphics
+    );
+}
+
+#[test]
+fn test_scan_missing_root() {
+    let results: Vec<_> = Scanner::default().scan("fixtures/missing").collect();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].1.is_err());
+}
@@ -0,0 +1,125 @@
+use file_format::{CustomFormat, Detector, Kind, Precedence};
+
+const DATABASE: &str = r#"
+# In-house formats.
+format = FooArchive
+name = "Foo Archive"
+short_name = "FOO"
+media_type = "application/x-foo"
+extension = "foo"
+kind = Archive
+value = b"FOO\x01"
+value = b"FOO\x02", b"\x00\x01" offset = 8
+
+// Bar documents.
+format = BarDocument
+name = "Bar Document"
+media_type = "application/x-bar"
+extension = "bar"
+kind = Document
+value = b"\x1F\x8BBAR" offset = 0x10
+"#;
+
+#[test]
+fn test_database() {
+    let formats = CustomFormat::from_database(DATABASE).unwrap();
+    assert_eq!(formats.len(), 2);
+    assert_eq!(formats[0].name(), "Foo Archive");
+    assert_eq!(formats[0].short_name(), Some("FOO"));
+    assert_eq!(formats[1].short_name(), None);
+    assert_eq!(formats[1].kind(), Kind::Document);
+}
+
+#[test]
+fn test_database_detection() {
+    let formats = CustomFormat::from_database(DATABASE).unwrap();
+    let detector = Detector::builder()
+        .custom_formats(formats, Precedence::AfterBuiltin)
+        .build();
+    let fmt = detector.identify_bytes(b"FOO\x01");
+    assert_eq!(fmt.name(), "Foo Archive");
+    let fmt = detector.identify_bytes(b"FOO\x02\x00\x00\x00\x00\x00\x01");
+    assert_eq!(fmt.name(), "Foo Archive");
+    let fmt = detector.identify_bytes(b"FOO\x02\x00\x00\x00\x00\x00\x02");
+    assert!(fmt.custom().is_none());
+    let fmt = detector.identify_bytes([&[0; 16][..], b"\x1F\x8BBAR"].concat());
+    assert_eq!(fmt.name(), "Bar Document");
+}
+
+fn error(source: &str) -> (usize, String) {
+    let error = CustomFormat::from_database(source).unwrap_err();
+    (error.line(), error.message().to_string())
+}
+
+#[test]
+fn test_database_unknown_key() {
+    let source = "format = Foo\nnmae = \"Foo\"";
+    assert_eq!(error(source), (2, "unknown key `nmae`".to_string()));
+}
+
+#[test]
+fn test_database_missing_field() {
+    let source = "format = Foo\nname = \"Foo\"\nextension = \"foo\"\nkind = Other\nvalue = b\"F\"";
+    assert_eq!(
+        error(source),
+        (1, "format `Foo` is missing `media_type`".to_string())
+    );
+}
+
+#[test]
+fn test_database_missing_value() {
+    let source =
+        "format = Foo\nname = \"Foo\"\nmedia_type = \"x/foo\"\nextension = \"foo\"\nkind = Other";
+    assert_eq!(
+        error(source),
+        (1, "format `Foo` is missing `value`".to_string())
+    );
+}
+
+#[test]
+fn test_database_malformed_entries() {
+    assert_eq!(
+        error("name = \"Foo\"").1,
+        "expected `format = <identifier>`"
+    );
+    assert_eq!(error("format = 1Foo").1, "invalid format identifier `1Foo`");
+    assert_eq!(
+        error("format = Foo\nformat = Foo").1,
+        "duplicate format `Foo`"
+    );
+    assert_eq!(
+        error("format = Foo\nkind = Thing").1,
+        "unknown kind `Thing`"
+    );
+    assert_eq!(error("format = Foo\nvalue").1, "expected `<key> = <value>`");
+    assert_eq!(error("format = Foo\nvalue = b\"\"").1, "empty value");
+    assert_eq!(
+        error("format = Foo\nvalue = b\"F").1,
+        "unterminated string literal"
+    );
+    assert_eq!(
+        error("format = Foo\nvalue = b\"\\xG0\"").1,
+        "invalid escape sequence `\\xG0`"
+    );
+    assert_eq!(
+        error("format = Foo\nvalue = b\"\\x+1\"").1,
+        "invalid escape sequence `\\x+1`"
+    );
+    assert_eq!(
+        error("format = Foo\nvalue = b\"F\" offset = x").1,
+        "invalid offset `x`"
+    );
+    assert_eq!(
+        error("format = Foo\nvalue = b\"F\" b\"O\"").1,
+        "unexpected `b\"O\"`"
+    );
+    assert_eq!(
+        error("format = Foo\nname = \"A\"\nname = \"B\"").1,
+        "duplicate key `name`"
+    );
+}
+
+#[test]
+fn test_database_file_not_found() {
+    assert!(CustomFormat::from_database_file("fixtures/missing.db").is_err());
+}
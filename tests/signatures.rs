@@ -0,0 +1,74 @@
+use file_format::Signature;
+use std::path::Path;
+
+/// Returns the first signature matched by the bytes, checking every signature in turn.
+fn find_linear(bytes: &[u8]) -> Option<&'static Signature> {
+    Signature::all().iter().find(|signature| {
+        signature
+            .parts()
+            .iter()
+            .all(|&(offset, value)| bytes.get(offset..offset + value.len()) == Some(value))
+    })
+}
+
+/// Returns the smallest bytes matching all the parts of the signature.
+fn sample(signature: &Signature, filler: u8) -> Vec<u8> {
+    let len = signature
+        .parts()
+        .iter()
+        .map(|(offset, value)| offset + value.len())
+        .max()
+        .unwrap_or_default();
+    let mut bytes = vec![filler; len];
+    for &(offset, value) in signature.parts() {
+        bytes[offset..offset + value.len()].copy_from_slice(value);
+    }
+    bytes
+}
+
+#[test]
+fn test_signatures_equivalence_samples() {
+    for signature in Signature::all() {
+        for filler in [0x00, 0x20, 0xFF] {
+            let bytes = sample(signature, filler);
+            assert_eq!(
+                Signature::find(&bytes),
+                find_linear(&bytes),
+                "{:?}",
+                signature
+            );
+            assert_eq!(Signature::find(&bytes[1..]), find_linear(&bytes[1..]));
+        }
+    }
+}
+
+#[test]
+fn test_signatures_equivalence_fixtures() {
+    for kind in Path::new("fixtures").read_dir().unwrap() {
+        for fixture in kind.unwrap().path().read_dir().unwrap() {
+            let bytes = std::fs::read(fixture.unwrap().path()).unwrap();
+            assert_eq!(Signature::find(&bytes), find_linear(&bytes));
+        }
+    }
+}
+
+#[test]
+fn test_signatures_equivalence_pseudo_random() {
+    let mut state: u32 = 0x2545_F491;
+    for len in 0..4096 {
+        let bytes: Vec<u8> = (0..len % 512)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        assert_eq!(Signature::find(&bytes), find_linear(&bytes));
+    }
+}
+
+#[test]
+fn test_signatures_empty() {
+    assert_eq!(Signature::find(&[]), None);
+}
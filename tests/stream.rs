@@ -0,0 +1,43 @@
+use file_format::FileFormat;
+use std::io::Read;
+
+#[test]
+fn test_stream_empty() {
+    let (fmt, _) = FileFormat::from_stream(std::io::empty()).unwrap();
+    assert_eq!(fmt, FileFormat::Empty);
+}
+
+#[test]
+fn test_stream_keeps_data() {
+    let data = [
+        &std::fs::read("fixtures/image/sample.png").unwrap()[..],
+        &[0; 2_000_000],
+    ]
+    .concat();
+    let (fmt, mut stream) = FileFormat::from_stream(&data[..]).unwrap();
+    assert_eq!(fmt, FileFormat::PortableNetworkGraphics);
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, data);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_stream_zip_local_file_headers() {
+    let data = [
+        &std::fs::read("fixtures/document/sample.docx").unwrap()[..],
+        &[0; 2_000_000],
+    ]
+    .concat();
+    let (detection, _) = FileFormat::detect_stream(&data[..]).unwrap();
+    assert_eq!(detection.format, FileFormat::OfficeOpenXmlDocument);
+    assert!(!detection.fell_back());
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_stream_zip_mimetype() {
+    let data = std::fs::read("fixtures/ebook/sample.epub").unwrap();
+    let (fmt, _) = FileFormat::from_stream(&data[..data.len() / 2]).unwrap();
+    assert_eq!(fmt, FileFormat::ElectronicPublication);
+}
@@ -0,0 +1,145 @@
+use file_format::{DetectionError, FileFormat};
+
+#[test]
+fn test_strict_empty() {
+    let fmt = FileFormat::try_from_bytes([]).unwrap();
+    assert_eq!(fmt, FileFormat::Empty);
+}
+
+#[test]
+fn test_strict_io() {
+    let error = FileFormat::try_from_file("fixtures/missing").unwrap_err();
+    assert!(matches!(error, DetectionError::Io(_)));
+    assert_eq!(error.format(), None);
+}
+
+#[cfg(feature = "reader-cfb")]
+#[test]
+fn test_strict_truncated() {
+    let error = FileFormat::try_from_bytes(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1").unwrap_err();
+    assert!(matches!(
+        error,
+        DetectionError::Truncated {
+            format: FileFormat::CompoundFileBinary
+        }
+    ));
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_strict_malformed() {
+    let error = FileFormat::try_from_bytes(b"\x1A\x45\xDF\xA3\x00").unwrap_err();
+    match error {
+        DetectionError::Malformed { format, reason } => {
+            assert_eq!(format, FileFormat::ExtensibleBinaryMetaLanguage);
+            assert_eq!(reason, "invalid EBML size");
+        }
+        _ => panic!("unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "reader-ebml")]
+#[test]
+fn test_lenient_malformed() {
+    let detection = FileFormat::detect_bytes(b"\x1A\x45\xDF\xA3\x00");
+    assert_eq!(detection.format, FileFormat::ArbitraryBinaryData);
+    assert_eq!(detection.reader_error.as_deref(), Some("invalid EBML size"));
+}
+
+#[cfg(feature = "reader-mp4")]
+const MP4_INVALID_BOX_SIZE: &[u8] = b"\0\0\0\x10ftypisom\0\0\0\0\0\0\0\x41moov\0\0\0\x39trak\0\0\0\x31mdia\0\0\0\x21hdlr\0\0\0\0\0\0\0\0vide\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x04free";
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_strict_mp4_invalid_box_size() {
+    let error = FileFormat::try_from_bytes(MP4_INVALID_BOX_SIZE).unwrap_err();
+    match error {
+        DetectionError::Malformed { format, reason } => {
+            assert_eq!(format, FileFormat::Mpeg4Part14);
+            assert_eq!(reason, "invalid MP4 box size");
+        }
+        _ => panic!("unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_lenient_mp4_invalid_box_size() {
+    let detection = FileFormat::detect_bytes(MP4_INVALID_BOX_SIZE);
+    assert_eq!(detection.format, FileFormat::Mpeg4Part14Video);
+    assert_eq!(detection.marker.as_deref(), Some("vide"));
+    assert!(!detection.fell_back());
+}
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_strict_limit_exceeded() {
+    let detector = file_format::Detector::builder().mp4_box_limit(1).build();
+    let error = detector
+        .try_detect_file("fixtures/audio/sample.mp4")
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        DetectionError::LimitExceeded {
+            format: FileFormat::Mpeg4Part14,
+            limit: "mp4_box_limit"
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "MP4 reader exceeded the `mp4_box_limit` limit"
+    );
+}
+
+#[cfg(feature = "reader-mp4")]
+#[test]
+fn test_lenient_limit_exceeded() {
+    let detector = file_format::Detector::builder().mp4_box_limit(1).build();
+    let detection = detector.detect_file("fixtures/audio/sample.mp4").unwrap();
+    assert_eq!(detection.format, FileFormat::Mpeg4Part14);
+    assert_eq!(detection.limit_exceeded, Some("mp4_box_limit"));
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_strict_truncated_zip() {
+    let data = std::fs::read("fixtures/archive/sample.zip").unwrap();
+    let error = FileFormat::try_from_bytes(&data[..60]).unwrap_err();
+    assert!(matches!(
+        error,
+        DetectionError::Truncated {
+            format: FileFormat::Zip
+        }
+    ));
+    assert_eq!(error.to_string(), "truncated ZIP stream");
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_strict_short_zip() {
+    let data = std::fs::read("fixtures/archive/sample.zip").unwrap();
+    let error = FileFormat::try_from_bytes(&data[..8]).unwrap_err();
+    assert!(matches!(
+        error,
+        DetectionError::Truncated {
+            format: FileFormat::Zip
+        }
+    ));
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_lenient_truncated_zip() {
+    let data = std::fs::read("fixtures/archive/sample.zip").unwrap();
+    let (detection, _) = FileFormat::detect_stream(&data[..60]).unwrap();
+    assert_eq!(detection.format, FileFormat::Zip);
+    assert!(detection.truncated);
+    assert!(!detection.fell_back());
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_strict_zip() {
+    let fmt = FileFormat::try_from_file("fixtures/document/sample.docx").unwrap();
+    assert_eq!(fmt, FileFormat::OfficeOpenXmlDocument);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "from-extension")]
+
+use file_format::{FileFormat, Verdict};
+
+#[test]
+fn test_verify_match() {
+    let verification = FileFormat::verify_path("fixtures/image/sample.png").unwrap();
+    assert_eq!(verification.detected, FileFormat::PortableNetworkGraphics);
+    assert_eq!(
+        verification.expected,
+        &[FileFormat::PortableNetworkGraphics]
+    );
+    assert_eq!(verification.verdict, Verdict::Match);
+}
+
+#[test]
+fn test_verify_match_uppercase() {
+    let data = std::fs::read("fixtures/image/sample.png").unwrap();
+    let verification = FileFormat::verify_bytes(data, "PNG");
+    assert_eq!(verification.verdict, Verdict::Match);
+}
+
+#[test]
+fn test_verify_mismatch() {
+    let path = std::env::temp_dir().join("file-format-verify-mismatch.jpg");
+    std::fs::copy("fixtures/executable/sample1.exe", &path).unwrap();
+    let verification = FileFormat::verify_path(&path);
+    std::fs::remove_file(&path).unwrap();
+    let verification = verification.unwrap();
+    assert_eq!(
+        verification.expected,
+        &[FileFormat::JointPhotographicExpertsGroup]
+    );
+    assert_eq!(verification.verdict, Verdict::Mismatch);
+}
+
+#[cfg(not(feature = "reader-zip"))]
+#[test]
+fn test_verify_compatible_container() {
+    let verification = FileFormat::verify_path("fixtures/package/sample.jar").unwrap();
+    assert_eq!(verification.detected, FileFormat::Zip);
+    assert_eq!(verification.verdict, Verdict::Compatible);
+}
+
+#[cfg(feature = "reader-zip")]
+#[test]
+fn test_verify_compatible_variant() {
+    let data = std::fs::read("fixtures/package/sample.jar").unwrap();
+    let verification = FileFormat::verify_bytes(data, "zip");
+    assert_eq!(verification.detected, FileFormat::JavaArchive);
+    assert_eq!(verification.expected, &[FileFormat::Zip]);
+    assert_eq!(verification.verdict, Verdict::Compatible);
+}
+
+#[test]
+fn test_verify_unknown() {
+    let verification = FileFormat::verify_bytes(b"\x1F\x8B", "unknown");
+    assert_eq!(verification.detected, FileFormat::Gzip);
+    assert!(verification.expected.is_empty());
+    assert_eq!(verification.verdict, Verdict::Unknown);
+}
+
+#[test]
+fn test_verify_without_extension() {
+    let verification = FileFormat::verify_path("LICENSE-MIT").unwrap();
+    assert_eq!(verification.verdict, Verdict::Unknown);
+}
@@ -0,0 +1,107 @@
+use file_format::FileFormat;
+
+fn version<B: AsRef<[u8]>>(bytes: B) -> Option<String> {
+    FileFormat::detect_bytes(bytes).version
+}
+
+#[test]
+fn test_version_ebml() {
+    let detection = FileFormat::detect_file("fixtures/video/sample.mkv").unwrap();
+    assert_eq!(detection.version.as_deref(), Some("1"));
+    let detection = FileFormat::detect_file("fixtures/video/sample.webm").unwrap();
+    assert_eq!(detection.version, None);
+}
+
+#[test]
+fn test_version_elf() {
+    let mut data = b"\x7FELF\x02\x01\x01".to_vec();
+    data.resize(64, 0);
+    assert_eq!(version(data).as_deref(), Some("ELF64"));
+    assert_eq!(version(b"\x7FELF\x01").as_deref(), Some("ELF32"));
+}
+
+#[test]
+fn test_version_gif() {
+    let detection = FileFormat::detect_file("fixtures/image/sample.gif").unwrap();
+    assert_eq!(detection.version.as_deref(), Some("87a"));
+}
+
+#[test]
+fn test_version_java_class() {
+    let version = version(b"\xCA\xFE\xBA\xBE\x00\x00\x00\x34");
+    assert_eq!(version.as_deref(), Some("52.0"));
+}
+
+#[test]
+fn test_version_opentype() {
+    let mut data = b"OTTO\x00\x02\x00\x20\x00\x01\x00\x00".to_vec();
+    data.extend_from_slice(b"CFF \0\0\0\0\0\0\0\x2C\0\0\0\x01");
+    data.extend_from_slice(b"OS/2\0\0\0\0\0\0\0\x2D\0\0\0\x01");
+    assert_eq!(version(data).as_deref(), Some("CFF"));
+    assert_eq!(version(b"OTTO\x00\x01").as_deref(), None);
+}
+
+#[test]
+fn test_version_pdf() {
+    assert_eq!(
+        version(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n").as_deref(),
+        Some("1.4")
+    );
+    assert_eq!(version(b"%PDF-").as_deref(), None);
+}
+
+#[test]
+fn test_version_png() {
+    let mut data = b"\x89PNG\r\n\x1A\n\0\0\0\x0DIHDR".to_vec();
+    data.extend_from_slice(&[0; 17]);
+    let mut png = data.clone();
+    png.extend_from_slice(b"\0\0\0\0IDAT");
+    assert_eq!(version(png).as_deref(), Some("PNG"));
+    let mut apng = data.clone();
+    apng.extend_from_slice(b"\0\0\0\x01tEXt\0\0\0\0\0\0\0\0\x08acTL");
+    let detection = FileFormat::detect_bytes(apng);
+    assert_eq!(detection.format, FileFormat::PortableNetworkGraphics);
+    assert_eq!(detection.version.as_deref(), Some("APNG"));
+    assert_eq!(version(data).as_deref(), None);
+}
+
+#[test]
+fn test_version_rar() {
+    let detection = FileFormat::detect_file("fixtures/archive/sample.rar").unwrap();
+    assert_eq!(detection.version.as_deref(), Some("5.0"));
+    assert_eq!(version(b"Rar!\x1A\x07\x00").as_deref(), Some("1.5"));
+}
+
+#[test]
+fn test_version_sqlite3() {
+    let mut data = b"SQLite format 3\0".to_vec();
+    data.resize(96, 0);
+    data.extend(3_045_001u32.to_be_bytes());
+    assert_eq!(version(data).as_deref(), Some("3.45.1"));
+}
+
+#[test]
+fn test_version_tiff() {
+    let detection = FileFormat::detect_file("fixtures/image/sample.tiff").unwrap();
+    assert_eq!(detection.version.as_deref(), Some("TIFF"));
+    assert_eq!(version(b"II\x2B\x00").as_deref(), Some("BigTIFF"));
+}
+
+#[test]
+fn test_version_truetype() {
+    let mut data = b"\x00\x01\x00\x00\x00\x01\x00\x10\x00\x00\x00\x00".to_vec();
+    data.extend_from_slice(b"glyf\0\0\0\0\0\0\0\x1C\0\0\0\x01");
+    assert_eq!(version(data).as_deref(), Some("TrueType"));
+}
+
+#[test]
+fn test_version_zip() {
+    let detection = FileFormat::detect_file("fixtures/archive/sample.zip").unwrap();
+    assert_eq!(detection.version.as_deref(), Some("2.0"));
+}
+
+#[test]
+fn test_version_unknown() {
+    let detection = FileFormat::detect_file("fixtures/image/sample.bmp").unwrap();
+    assert_eq!(detection.version, None);
+}